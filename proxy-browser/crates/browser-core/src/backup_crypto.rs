@@ -0,0 +1,324 @@
+//! Backup Encryption Module
+//!
+//! Client-side authenticated encryption for backup payloads. A user password is run
+//! through Argon2id to derive a symmetric data key (the salt and KDF cost parameters
+//! are recorded in an unencrypted [`EncryptionHeader`] so the same password can re-derive
+//! it later). The payload is encrypted in fixed-size chunks with XChaCha20-Poly1305: each
+//! chunk gets a fresh random nonce, and its AAD binds the backup id and chunk index so
+//! chunks can't be reordered or spliced in from a different backup. The data key can
+//! optionally also be wrapped for an X25519 "recovery" keypair, so a backup can be
+//! decrypted with either the password or a separately stored recovery private key.
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use x25519_dalek::{PublicKey as X25519PublicKey, StaticSecret as X25519StaticSecret};
+
+/// Current on-disk format version for [`EncryptionHeader`]. Bump when the KDF, cipher,
+/// or header layout changes in a way that breaks compatibility with older backups.
+pub const ENCRYPTION_HEADER_VERSION: u32 = 1;
+
+/// How much plaintext each encrypted chunk covers.
+pub const CHUNK_PLAINTEXT_SIZE: usize = 64 * 1024;
+
+/// Argon2id cost parameters, recorded alongside the salt so a backup stays decryptable
+/// even if this crate's defaults change later.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Argon2Params {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl Default for Argon2Params {
+    fn default() -> Self {
+        Self {
+            memory_kib: 19 * 1024,
+            iterations: 2,
+            parallelism: 1,
+        }
+    }
+}
+
+/// The symmetric data key, additionally wrapped for an X25519 recovery keypair so it
+/// can be recovered without the password.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryWrappedKey {
+    /// The recovery keypair's public key, recorded so callers can confirm which
+    /// recovery key a backup was wrapped for.
+    pub recipient_public_key: [u8; 32],
+    /// An ephemeral public key generated for this wrap, used as the other half of the
+    /// X25519 key agreement (so the wrapping key isn't reused across backups).
+    pub ephemeral_public_key: [u8; 32],
+    pub nonce: [u8; 24],
+    pub wrapped_key: Vec<u8>,
+}
+
+/// The unencrypted header written alongside an encrypted backup payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptionHeader {
+    pub version: u32,
+    /// The backup id bound into every chunk's AAD at encryption time. Recorded here
+    /// (rather than relying on the caller to pass the same id back in) so decryption
+    /// is self-contained and still works after a backup has been imported under a new
+    /// local catalog id.
+    pub backup_id: String,
+    pub salt: [u8; 16],
+    pub kdf_params: Argon2Params,
+    /// A value derived from the data key that lets [`decrypt_payload`] reject a wrong
+    /// password immediately, instead of failing chunk-by-chunk.
+    pub verification_tag: [u8; 32],
+    pub chunk_plaintext_size: usize,
+    pub recovery: Option<RecoveryWrappedKey>,
+}
+
+/// One authenticated chunk of an encrypted payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptedChunk {
+    pub nonce: [u8; 24],
+    pub ciphertext: Vec<u8>,
+}
+
+/// An encrypted backup payload: the header needed to decrypt it, plus its chunks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptedPayload {
+    pub header: EncryptionHeader,
+    pub chunks: Vec<EncryptedChunk>,
+}
+
+/// What about a [`BackupCryptoError`] went wrong.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackupCryptoErrorKind {
+    /// The password (or recovery key) didn't reproduce the data key.
+    WrongCredentials,
+    /// No recovery key was wrapped for this backup.
+    NoRecoveryKey,
+    /// A chunk failed authentication, meaning it was corrupted or tampered with.
+    CorruptChunk,
+    /// The header declares a format version this crate doesn't understand.
+    UnsupportedVersion,
+    /// The Argon2id KDF itself rejected its parameters or inputs.
+    Kdf,
+}
+
+/// Error returned by the backup encryption/decryption routines.
+#[derive(Debug, Clone)]
+pub struct BackupCryptoError {
+    pub kind: BackupCryptoErrorKind,
+    pub message: String,
+}
+
+impl BackupCryptoError {
+    fn new(kind: BackupCryptoErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for BackupCryptoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}: {}", self.kind, self.message)
+    }
+}
+
+impl std::error::Error for BackupCryptoError {}
+
+fn derive_key(password: &str, salt: &[u8; 16], params: &Argon2Params) -> Result<[u8; 32], BackupCryptoError> {
+    let argon2_params = Params::new(params.memory_kib, params.iterations, params.parallelism, Some(32))
+        .map_err(|e| BackupCryptoError::new(BackupCryptoErrorKind::Kdf, e.to_string()))?;
+    let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, argon2_params);
+
+    let mut key = [0u8; 32];
+    argon2
+        .hash_password_into(password.as_bytes(), salt, &mut key)
+        .map_err(|e| BackupCryptoError::new(BackupCryptoErrorKind::Kdf, e.to_string()))?;
+    Ok(key)
+}
+
+fn verification_tag(key: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(key);
+    hasher.update(b"browser-core-backup-verification-tag");
+    hasher.finalize().into()
+}
+
+fn chunk_aad(backup_id: &str, chunk_index: usize) -> Vec<u8> {
+    format!("{backup_id}:{chunk_index}").into_bytes()
+}
+
+fn wrap_key_for_recovery(data_key: &[u8; 32], recipient_public_key: &[u8; 32]) -> Result<RecoveryWrappedKey, BackupCryptoError> {
+    let mut ephemeral_bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut ephemeral_bytes);
+    let ephemeral_secret = X25519StaticSecret::from(ephemeral_bytes);
+    let ephemeral_public = X25519PublicKey::from(&ephemeral_secret);
+
+    let recipient = X25519PublicKey::from(*recipient_public_key);
+    let shared_secret = ephemeral_secret.diffie_hellman(&recipient);
+
+    let mut hasher = Sha256::new();
+    hasher.update(shared_secret.as_bytes());
+    hasher.update(b"browser-core-backup-recovery-wrap");
+    let wrapping_key: [u8; 32] = hasher.finalize().into();
+
+    let mut nonce_bytes = [0u8; 24];
+    rand::thread_rng().fill_bytes(&mut nonce_bytes);
+
+    let cipher = XChaCha20Poly1305::new_from_slice(&wrapping_key)
+        .map_err(|e| BackupCryptoError::new(BackupCryptoErrorKind::Kdf, e.to_string()))?;
+    let wrapped_key = cipher
+        .encrypt(XNonce::from_slice(&nonce_bytes), data_key.as_slice())
+        .map_err(|e| BackupCryptoError::new(BackupCryptoErrorKind::Kdf, e.to_string()))?;
+
+    Ok(RecoveryWrappedKey {
+        recipient_public_key: *recipient_public_key,
+        ephemeral_public_key: ephemeral_public.to_bytes(),
+        nonce: nonce_bytes,
+        wrapped_key,
+    })
+}
+
+fn unwrap_key_with_recovery(wrapped: &RecoveryWrappedKey, recipient_private_key: &[u8; 32]) -> Result<[u8; 32], BackupCryptoError> {
+    let recipient_secret = X25519StaticSecret::from(*recipient_private_key);
+    let ephemeral_public = X25519PublicKey::from(wrapped.ephemeral_public_key);
+    let shared_secret = recipient_secret.diffie_hellman(&ephemeral_public);
+
+    let mut hasher = Sha256::new();
+    hasher.update(shared_secret.as_bytes());
+    hasher.update(b"browser-core-backup-recovery-wrap");
+    let wrapping_key: [u8; 32] = hasher.finalize().into();
+
+    let cipher = XChaCha20Poly1305::new_from_slice(&wrapping_key)
+        .map_err(|e| BackupCryptoError::new(BackupCryptoErrorKind::Kdf, e.to_string()))?;
+    let plaintext = cipher
+        .decrypt(XNonce::from_slice(&wrapped.nonce), wrapped.wrapped_key.as_slice())
+        .map_err(|_| BackupCryptoError::new(BackupCryptoErrorKind::WrongCredentials, "recovery key did not unwrap the data key"))?;
+
+    plaintext
+        .try_into()
+        .map_err(|_| BackupCryptoError::new(BackupCryptoErrorKind::CorruptChunk, "unwrapped data key had the wrong length"))
+}
+
+/// Generate a fresh X25519 recovery keypair: `(public_key, private_key)`, both as raw
+/// 32-byte arrays. The private key must be stored somewhere separate from the backups
+/// it protects -- that's the point of a recovery key.
+pub fn generate_recovery_keypair() -> ([u8; 32], [u8; 32]) {
+    let mut secret_bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut secret_bytes);
+    let secret = X25519StaticSecret::from(secret_bytes);
+    let public = X25519PublicKey::from(&secret);
+    (public.to_bytes(), secret.to_bytes())
+}
+
+/// Derive a data key from `password`, encrypt `payload` in authenticated chunks, and
+/// optionally wrap the data key for a recovery keypair.
+pub fn encrypt_payload(
+    payload: &[u8],
+    password: &str,
+    backup_id: &str,
+    recovery_public_key: Option<&[u8; 32]>,
+) -> Result<EncryptedPayload, BackupCryptoError> {
+    let mut salt = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut salt);
+    let kdf_params = Argon2Params::default();
+    let key = derive_key(password, &salt, &kdf_params)?;
+
+    let cipher = XChaCha20Poly1305::new_from_slice(&key)
+        .map_err(|e| BackupCryptoError::new(BackupCryptoErrorKind::Kdf, e.to_string()))?;
+
+    let mut chunks = Vec::with_capacity(payload.len() / CHUNK_PLAINTEXT_SIZE + 1);
+    for (index, plaintext) in payload.chunks(CHUNK_PLAINTEXT_SIZE.max(1)).enumerate() {
+        let mut nonce_bytes = [0u8; 24];
+        rand::thread_rng().fill_bytes(&mut nonce_bytes);
+        let ciphertext = cipher
+            .encrypt(
+                XNonce::from_slice(&nonce_bytes),
+                Payload {
+                    msg: plaintext,
+                    aad: &chunk_aad(backup_id, index),
+                },
+            )
+            .map_err(|e| BackupCryptoError::new(BackupCryptoErrorKind::Kdf, e.to_string()))?;
+        chunks.push(EncryptedChunk {
+            nonce: nonce_bytes,
+            ciphertext,
+        });
+    }
+
+    let recovery = match recovery_public_key {
+        Some(recipient) => Some(wrap_key_for_recovery(&key, recipient)?),
+        None => None,
+    };
+
+    let header = EncryptionHeader {
+        version: ENCRYPTION_HEADER_VERSION,
+        backup_id: backup_id.to_string(),
+        salt,
+        kdf_params,
+        verification_tag: verification_tag(&key),
+        chunk_plaintext_size: CHUNK_PLAINTEXT_SIZE,
+        recovery,
+    };
+
+    Ok(EncryptedPayload { header, chunks })
+}
+
+/// Decrypt an [`EncryptedPayload`] with either the password or a recovery private key.
+/// Fails with [`BackupCryptoErrorKind::WrongCredentials`] before touching any chunk if
+/// the derived/unwrapped key doesn't match the header's verification tag, so a wrong
+/// password can't produce silently-garbled output.
+pub fn decrypt_payload(
+    encrypted: &EncryptedPayload,
+    password: Option<&str>,
+    recovery_private_key: Option<&[u8; 32]>,
+) -> Result<Vec<u8>, BackupCryptoError> {
+    if encrypted.header.version != ENCRYPTION_HEADER_VERSION {
+        return Err(BackupCryptoError::new(
+            BackupCryptoErrorKind::UnsupportedVersion,
+            format!("unsupported encryption header version {}", encrypted.header.version),
+        ));
+    }
+
+    let key = if let Some(password) = password {
+        derive_key(password, &encrypted.header.salt, &encrypted.header.kdf_params)?
+    } else if let Some(recovery_private_key) = recovery_private_key {
+        let wrapped = encrypted
+            .header
+            .recovery
+            .as_ref()
+            .ok_or_else(|| BackupCryptoError::new(BackupCryptoErrorKind::NoRecoveryKey, "backup has no recovery key wrapped"))?;
+        unwrap_key_with_recovery(wrapped, recovery_private_key)?
+    } else {
+        return Err(BackupCryptoError::new(
+            BackupCryptoErrorKind::WrongCredentials,
+            "no password or recovery key provided",
+        ));
+    };
+
+    if verification_tag(&key) != encrypted.header.verification_tag {
+        return Err(BackupCryptoError::new(BackupCryptoErrorKind::WrongCredentials, "incorrect password"));
+    }
+
+    let cipher = XChaCha20Poly1305::new_from_slice(&key)
+        .map_err(|e| BackupCryptoError::new(BackupCryptoErrorKind::Kdf, e.to_string()))?;
+
+    let mut plaintext = Vec::new();
+    for (index, chunk) in encrypted.chunks.iter().enumerate() {
+        let decrypted = cipher
+            .decrypt(
+                XNonce::from_slice(&chunk.nonce),
+                Payload {
+                    msg: &chunk.ciphertext,
+                    aad: &chunk_aad(&encrypted.header.backup_id, index),
+                },
+            )
+            .map_err(|_| BackupCryptoError::new(BackupCryptoErrorKind::CorruptChunk, format!("chunk {index} failed authentication")))?;
+        plaintext.extend_from_slice(&decrypted);
+    }
+
+    Ok(plaintext)
+}
@@ -0,0 +1,343 @@
+//! Remote Backup Destinations
+//!
+//! A pluggable [`BackupDestination`] trait for storing and retrieving backup objects
+//! somewhere other than the local filesystem, so [`crate::backup::BackupManager`] can
+//! keep an off-device copy for disaster recovery. Ships with a local-filesystem
+//! implementation (mostly useful for testing the rest of this module) and an
+//! S3-compatible object-store implementation that also works against self-hosted
+//! gateways via an endpoint override.
+//!
+//! Objects are addressed by a flat string key. By convention callers store a backup's
+//! index/manifest under `{prefix}/{backup_id}.json` and its chunk payloads under
+//! `{prefix}/chunks/{digest}`, so [`BackupDestination::list_objects`] can enumerate
+//! backups without downloading any chunk data.
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::Client as S3Client;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Multipart upload kicks in once an object's bytes exceed this size.
+pub const MULTIPART_THRESHOLD: usize = 8 * 1024 * 1024;
+/// Size of each part in a multipart upload.
+pub const MULTIPART_PART_SIZE: usize = 8 * 1024 * 1024;
+
+/// HTTP-style cache validators for a single remote object, used to decide whether a
+/// sync needs to actually transfer it. See [`crate::backup::BackupManager::sync_backups`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ObjectMetadata {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub size: u64,
+}
+
+/// Somewhere a backup's objects (archive index and chunk payloads) can be stored and
+/// retrieved by key.
+#[async_trait]
+pub trait BackupDestination: Send + Sync {
+    async fn put_object(&self, key: &str, data: &[u8]) -> Result<()>;
+    async fn get_object(&self, key: &str) -> Result<Vec<u8>>;
+    async fn object_exists(&self, key: &str) -> Result<bool>;
+    /// List every object key stored under `prefix`.
+    async fn list_objects(&self, prefix: &str) -> Result<Vec<String>>;
+    async fn delete_object(&self, key: &str) -> Result<()>;
+    /// Fetch `key`'s current cache validators without downloading its body, or `None`
+    /// if it doesn't exist. Used to skip re-uploading or re-downloading unchanged
+    /// objects during a [`crate::backup::BackupManager::sync_backups`] pass.
+    async fn head_object(&self, key: &str) -> Result<Option<ObjectMetadata>>;
+}
+
+/// Stores objects as files under a root directory, mirroring the key as a relative path.
+pub struct LocalFilesystemDestination {
+    root: PathBuf,
+}
+
+impl LocalFilesystemDestination {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    fn path_for(&self, key: &str) -> PathBuf {
+        self.root.join(key)
+    }
+}
+
+#[async_trait]
+impl BackupDestination for LocalFilesystemDestination {
+    async fn put_object(&self, key: &str, data: &[u8]) -> Result<()> {
+        let path = self.path_for(key);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(path, data).await?;
+        Ok(())
+    }
+
+    async fn get_object(&self, key: &str) -> Result<Vec<u8>> {
+        Ok(tokio::fs::read(self.path_for(key)).await?)
+    }
+
+    async fn object_exists(&self, key: &str) -> Result<bool> {
+        Ok(tokio::fs::try_exists(self.path_for(key)).await.unwrap_or(false))
+    }
+
+    async fn list_objects(&self, prefix: &str) -> Result<Vec<String>> {
+        let dir = self.path_for(prefix);
+        if !tokio::fs::try_exists(&dir).await.unwrap_or(false) {
+            return Ok(Vec::new());
+        }
+
+        let mut keys = Vec::new();
+        let mut pending = vec![dir];
+        while let Some(current) = pending.pop() {
+            let mut entries = tokio::fs::read_dir(&current).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let file_type = entry.file_type().await?;
+                if file_type.is_dir() {
+                    pending.push(entry.path());
+                } else if file_type.is_file() {
+                    let relative = entry.path().strip_prefix(&self.root)?.to_string_lossy().replace('\\', "/");
+                    keys.push(relative);
+                }
+            }
+        }
+        Ok(keys)
+    }
+
+    async fn delete_object(&self, key: &str) -> Result<()> {
+        let path = self.path_for(key);
+        if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            tokio::fs::remove_file(path).await?;
+        }
+        Ok(())
+    }
+
+    async fn head_object(&self, key: &str) -> Result<Option<ObjectMetadata>> {
+        let path = self.path_for(key);
+        if !tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            return Ok(None);
+        }
+
+        let metadata = tokio::fs::metadata(&path).await?;
+        let data = tokio::fs::read(&path).await?;
+        let digest = crate::chunk_store::hash_chunk(&data);
+        let last_modified = metadata
+            .modified()
+            .ok()
+            .map(|time| DateTime::<Utc>::from(time).to_rfc3339());
+
+        Ok(Some(ObjectMetadata {
+            etag: Some(digest),
+            last_modified,
+            size: metadata.len(),
+        }))
+    }
+}
+
+/// Connection details for an S3-compatible object store.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct S3DestinationConfig {
+    pub bucket: String,
+    pub region: String,
+    /// Overrides the endpoint URL, for self-hosted S3-compatible gateways (MinIO,
+    /// Backblaze B2, etc.) instead of AWS's own regional endpoints.
+    pub endpoint: Option<String>,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+}
+
+/// Stores objects in an S3-compatible bucket, using multipart upload for large objects.
+pub struct S3Destination {
+    config: S3DestinationConfig,
+    client: S3Client,
+}
+
+impl S3Destination {
+    pub async fn new(config: S3DestinationConfig) -> Result<Self> {
+        let credentials = aws_sdk_s3::config::Credentials::new(
+            &config.access_key_id,
+            &config.secret_access_key,
+            None,
+            None,
+            "browser-core-backup-destination",
+        );
+
+        let mut builder = aws_sdk_s3::config::Builder::new()
+            .region(aws_sdk_s3::config::Region::new(config.region.clone()))
+            .credentials_provider(credentials)
+            .force_path_style(config.endpoint.is_some());
+
+        if let Some(endpoint) = &config.endpoint {
+            builder = builder.endpoint_url(endpoint);
+        }
+
+        let client = S3Client::from_conf(builder.build());
+        Ok(Self { config, client })
+    }
+
+    async fn put_object_simple(&self, key: &str, data: &[u8]) -> Result<()> {
+        self.client
+            .put_object()
+            .bucket(&self.config.bucket)
+            .key(key)
+            .body(ByteStream::from(data.to_vec()))
+            .send()
+            .await
+            .map_err(|e| anyhow!("S3 put_object failed for '{key}': {e}"))?;
+        Ok(())
+    }
+
+    async fn put_object_multipart(&self, key: &str, data: &[u8]) -> Result<()> {
+        let create = self
+            .client
+            .create_multipart_upload()
+            .bucket(&self.config.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| anyhow!("S3 create_multipart_upload failed for '{key}': {e}"))?;
+        let upload_id = create
+            .upload_id()
+            .ok_or_else(|| anyhow!("S3 did not return an upload id for '{key}'"))?
+            .to_string();
+
+        let mut completed_parts = Vec::new();
+        for (index, part) in data.chunks(MULTIPART_PART_SIZE).enumerate() {
+            let part_number = (index + 1) as i32;
+            let uploaded = self
+                .client
+                .upload_part()
+                .bucket(&self.config.bucket)
+                .key(key)
+                .upload_id(&upload_id)
+                .part_number(part_number)
+                .body(ByteStream::from(part.to_vec()))
+                .send()
+                .await
+                .map_err(|e| anyhow!("S3 upload_part {part_number} failed for '{key}': {e}"))?;
+
+            completed_parts.push(
+                aws_sdk_s3::types::CompletedPart::builder()
+                    .part_number(part_number)
+                    .set_e_tag(uploaded.e_tag().map(|s| s.to_string()))
+                    .build(),
+            );
+        }
+
+        self.client
+            .complete_multipart_upload()
+            .bucket(&self.config.bucket)
+            .key(key)
+            .upload_id(&upload_id)
+            .multipart_upload(
+                aws_sdk_s3::types::CompletedMultipartUpload::builder()
+                    .set_parts(Some(completed_parts))
+                    .build(),
+            )
+            .send()
+            .await
+            .map_err(|e| anyhow!("S3 complete_multipart_upload failed for '{key}': {e}"))?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl BackupDestination for S3Destination {
+    async fn put_object(&self, key: &str, data: &[u8]) -> Result<()> {
+        if data.len() > MULTIPART_THRESHOLD {
+            self.put_object_multipart(key, data).await
+        } else {
+            self.put_object_simple(key, data).await
+        }
+    }
+
+    async fn get_object(&self, key: &str) -> Result<Vec<u8>> {
+        let output = self
+            .client
+            .get_object()
+            .bucket(&self.config.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| anyhow!("S3 get_object failed for '{key}': {e}"))?;
+        let bytes = output
+            .body
+            .collect()
+            .await
+            .map_err(|e| anyhow!("failed to read S3 object body for '{key}': {e}"))?;
+        Ok(bytes.into_bytes().to_vec())
+    }
+
+    async fn object_exists(&self, key: &str) -> Result<bool> {
+        match self.client.head_object().bucket(&self.config.bucket).key(key).send().await {
+            Ok(_) => Ok(true),
+            Err(aws_sdk_s3::error::SdkError::ServiceError(service_err)) if service_err.err().is_not_found() => Ok(false),
+            Err(e) => Err(anyhow!("S3 head_object failed for '{key}': {e}")),
+        }
+    }
+
+    async fn list_objects(&self, prefix: &str) -> Result<Vec<String>> {
+        let mut keys = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let mut request = self.client.list_objects_v2().bucket(&self.config.bucket).prefix(prefix);
+            if let Some(token) = &continuation_token {
+                request = request.continuation_token(token);
+            }
+            let output = request
+                .send()
+                .await
+                .map_err(|e| anyhow!("S3 list_objects_v2 failed for prefix '{prefix}': {e}"))?;
+
+            keys.extend(output.contents().iter().filter_map(|obj| obj.key().map(|k| k.to_string())));
+
+            if output.is_truncated().unwrap_or(false) {
+                continuation_token = output.next_continuation_token().map(|s| s.to_string());
+            } else {
+                break;
+            }
+        }
+        Ok(keys)
+    }
+
+    async fn delete_object(&self, key: &str) -> Result<()> {
+        self.client
+            .delete_object()
+            .bucket(&self.config.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| anyhow!("S3 delete_object failed for '{key}': {e}"))?;
+        Ok(())
+    }
+
+    async fn head_object(&self, key: &str) -> Result<Option<ObjectMetadata>> {
+        match self.client.head_object().bucket(&self.config.bucket).key(key).send().await {
+            Ok(output) => Ok(Some(ObjectMetadata {
+                etag: output.e_tag().map(|tag| tag.trim_matches('"').to_string()),
+                last_modified: output.last_modified().map(|ts| ts.to_string()),
+                size: output.content_length().unwrap_or(0).max(0) as u64,
+            })),
+            Err(aws_sdk_s3::error::SdkError::ServiceError(service_err)) if service_err.err().is_not_found() => Ok(None),
+            Err(e) => Err(anyhow!("S3 head_object failed for '{key}': {e}")),
+        }
+    }
+}
+
+/// Configuration for [`crate::backup::BackupManager::configure_remote_destination`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RemoteDestinationConfig {
+    LocalFilesystem { root: PathBuf },
+    S3(S3DestinationConfig),
+}
+
+/// Build the [`BackupDestination`] described by `config`.
+pub async fn build_destination(config: RemoteDestinationConfig) -> Result<Box<dyn BackupDestination>> {
+    match config {
+        RemoteDestinationConfig::LocalFilesystem { root } => Ok(Box::new(LocalFilesystemDestination::new(root))),
+        RemoteDestinationConfig::S3(s3_config) => Ok(Box::new(S3Destination::new(s3_config).await?)),
+    }
+}
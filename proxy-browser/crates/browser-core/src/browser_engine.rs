@@ -0,0 +1,127 @@
+//! Pluggable browser-engine abstraction
+//!
+//! [`ChromiumEngine`] was originally the only way to drive a browser from this crate.
+//! [`BrowserEngine`] pulls its tab/lifecycle surface out into a trait so a caller can
+//! target a different backend (e.g. [`crate::gecko_engine::GeckoEngine`] driving Firefox
+//! over Marionette) without branching on which engine it has. [`EngineCapabilities`]
+//! (already used by [`ChromiumEngineConfig::from_capabilities`]) is what a caller
+//! negotiates against to learn what a given implementation actually supports.
+//!
+//! [`ChromiumEngine`]: crate::chromium_engine::ChromiumEngine
+//! [`ChromiumEngineConfig::from_capabilities`]: crate::chromium_engine::ChromiumEngineConfig::from_capabilities
+
+use crate::chromium_engine::{ChromiumError, ChromiumErrorKind, ChromiumTab, EngineCapabilities};
+use crate::proxy::ProxySettings;
+use async_trait::async_trait;
+
+/// Kind of error raised by a [`BrowserEngine`] implementation, independent of which
+/// backend raised it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EngineErrorKind {
+    NotRunning,
+    TabNotFound,
+    ExecutableNotFound,
+    LaunchFailed,
+    /// The implementation doesn't support the requested operation or config knob.
+    Unsupported,
+    /// An unexpected failure in a dependency (process I/O, protocol framing) that
+    /// isn't itself one of the above outcomes.
+    Internal,
+}
+
+/// Error returned by a [`BrowserEngine`] implementation.
+#[derive(Debug, Clone)]
+pub struct EngineError {
+    pub kind: EngineErrorKind,
+    pub message: String,
+}
+
+impl EngineError {
+    pub fn new(kind: EngineErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for EngineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}: {}", self.kind, self.message)
+    }
+}
+
+impl std::error::Error for EngineError {}
+
+impl From<ChromiumError> for EngineError {
+    fn from(err: ChromiumError) -> Self {
+        let kind = match err.kind {
+            ChromiumErrorKind::NotRunning => EngineErrorKind::NotRunning,
+            ChromiumErrorKind::TabNotFound => EngineErrorKind::TabNotFound,
+            ChromiumErrorKind::ExecutableNotFound => EngineErrorKind::ExecutableNotFound,
+            ChromiumErrorKind::LaunchFailed
+            | ChromiumErrorKind::NoAvailablePorts
+            | ChromiumErrorKind::DebugPortInUse
+            | ChromiumErrorKind::PortOpenTimeout => EngineErrorKind::LaunchFailed,
+            ChromiumErrorKind::IncompatibleExtensionConfig
+            | ChromiumErrorKind::ProfileLocked
+            | ChromiumErrorKind::RequestBlocked => EngineErrorKind::Internal,
+        };
+        EngineError::new(kind, err.message)
+    }
+}
+
+/// Common lifecycle and tab operations every browser backend exposes, so callers
+/// (the engine manager, Tauri commands, ...) can hold a `dyn BrowserEngine` instead of
+/// depending on a concrete engine type.
+///
+/// Config is negotiated as a WebDriver-style JSON object (the same shape
+/// [`ChromiumEngineConfig::from_capabilities`]/[`ChromiumEngine::effective_capabilities`]
+/// already use) rather than a single concrete config struct, since each backend's
+/// native config shape differs.
+///
+/// [`ChromiumEngineConfig::from_capabilities`]: crate::chromium_engine::ChromiumEngineConfig::from_capabilities
+/// [`ChromiumEngine::effective_capabilities`]: crate::chromium_engine::ChromiumEngine::effective_capabilities
+#[async_trait]
+pub trait BrowserEngine: Send + Sync {
+    /// Launch the underlying browser process.
+    async fn launch(&mut self) -> Result<(), EngineError>;
+
+    /// Terminate the browser process and release any resources this instance owns.
+    async fn shutdown(&mut self) -> Result<(), EngineError>;
+
+    /// Whether the underlying browser process is currently alive.
+    async fn is_running(&self) -> bool;
+
+    /// Open a new tab, optionally navigating to `url` and assigning it a dedicated proxy.
+    async fn create_tab(
+        &self,
+        url: Option<&str>,
+        proxy: Option<ProxySettings>,
+    ) -> Result<ChromiumTab, EngineError>;
+
+    /// Navigate `tab_id` to `url`.
+    async fn navigate(&self, tab_id: &str, url: &str) -> Result<(), EngineError>;
+
+    /// Close a tab. Idempotent: closing an already-closed or unknown tab succeeds.
+    async fn close_tab(&self, tab_id: &str) -> Result<(), EngineError>;
+
+    /// Make `tab_id` the active tab.
+    async fn set_active_tab(&self, tab_id: &str) -> Result<(), EngineError>;
+
+    /// All currently open tabs.
+    async fn get_tabs(&self) -> Vec<ChromiumTab>;
+
+    /// Assign (or clear) a dedicated proxy for `tab_id`.
+    async fn set_tab_proxy(&self, tab_id: &str, proxy: Option<ProxySettings>) -> Result<(), EngineError>;
+
+    /// Current engine configuration as a WebDriver-style JSON object.
+    fn get_config(&self) -> serde_json::Value;
+
+    /// Replace the engine configuration from a WebDriver-style JSON object. Takes
+    /// effect on the next `launch`/tab creation.
+    fn set_config(&mut self, config: &serde_json::Value) -> Result<(), EngineError>;
+
+    /// What this backend build is capable of.
+    fn capabilities(&self) -> EngineCapabilities;
+}
@@ -6,14 +6,34 @@
 //! - Profile import/export
 //! - Isolated storage per profile
 
+use crate::secret::{Secret, SecretKey};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use tokio::sync::RwLock;
+use tracing::warn;
 use uuid::Uuid;
 
+/// On-disk index file name beneath a manager's `base_data_dir`, written by
+/// `persist_index` after every mutating operation so a manager can resume across
+/// process restarts instead of starting empty every time.
+const INDEX_FILE_NAME: &str = "profiles.json";
+
+/// File beneath a manager's `base_data_dir` holding the raw 32-byte key that encrypts
+/// [`ProfileProxyConfig`]'s username/password at rest. Generated once on first
+/// [`BrowserProfileManager::load`] and reused after that; losing this file makes any
+/// already-persisted secrets undecryptable, same as losing any other encryption key.
+const SECRET_KEY_FILE_NAME: &str = ".secret_key";
+
+/// Serialized snapshot of a [`BrowserProfileManager`]'s in-memory state.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct ProfileIndex {
+    profiles: Vec<BrowserProfile>,
+    active_profile_id: Option<String>,
+}
+
 /// Browser profile configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BrowserProfile {
@@ -38,14 +58,29 @@ pub struct ProfileSettings {
     pub fingerprint_protection: bool,
 }
 
-/// Profile proxy configuration
+/// Profile proxy configuration. `username`/`password` are encrypted at rest via
+/// [`Secret`]; use [`ProfileProxyConfig::set_username`]/[`ProfileProxyConfig::set_password`]
+/// to encrypt a new value and [`BrowserProfileManager::secret_key`] with
+/// [`Secret::expose_secret`] to read one back.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProfileProxyConfig {
     pub host: String,
     pub port: u16,
     pub protocol: String,
-    pub username: Option<String>,
-    pub password: Option<String>,
+    pub username: Option<Secret>,
+    pub password: Option<Secret>,
+}
+
+impl ProfileProxyConfig {
+    pub fn set_username(&mut self, key: &SecretKey, username: &str) -> Result<()> {
+        self.username = Some(Secret::encrypt(key, username)?);
+        Ok(())
+    }
+
+    pub fn set_password(&mut self, key: &SecretKey, password: &str) -> Result<()> {
+        self.password = Some(Secret::encrypt(key, password)?);
+        Ok(())
+    }
 }
 
 /// Geolocation settings
@@ -61,18 +96,110 @@ pub struct BrowserProfileManager {
     profiles: RwLock<HashMap<String, BrowserProfile>>,
     active_profile_id: RwLock<Option<String>>,
     base_data_dir: PathBuf,
+    /// Encrypts/decrypts [`ProfileProxyConfig`]'s username/password. `new` generates
+    /// an ephemeral one (any secrets set against it are unreadable after the process
+    /// exits); `load` persists it to [`SECRET_KEY_FILE_NAME`] so secrets survive a restart.
+    secret_key: SecretKey,
 }
 
 impl BrowserProfileManager {
-    /// Create a new profile manager
+    /// Create a new profile manager with a throwaway, unpersisted secret key. Prefer
+    /// [`BrowserProfileManager::load`] for anything whose profile secrets need to
+    /// survive a process restart.
     pub fn new(base_data_dir: PathBuf) -> Self {
         Self {
             profiles: RwLock::new(HashMap::new()),
             active_profile_id: RwLock::new(None),
             base_data_dir,
+            secret_key: SecretKey::generate(),
         }
     }
 
+    /// The key encrypting/decrypting this manager's [`ProfileProxyConfig`] secrets.
+    pub fn secret_key(&self) -> &SecretKey {
+        &self.secret_key
+    }
+
+    /// Read `<base_data_dir>/.secret_key`, generating and persisting one on first use.
+    async fn load_or_generate_secret_key(base_data_dir: &PathBuf) -> Result<SecretKey> {
+        let key_path = base_data_dir.join(SECRET_KEY_FILE_NAME);
+
+        if let Ok(bytes) = tokio::fs::read(&key_path).await {
+            if let Ok(key_bytes) = <[u8; 32]>::try_from(bytes.as_slice()) {
+                return Ok(SecretKey::from_bytes(key_bytes));
+            }
+            warn!("'{:?}' did not contain a valid 32-byte key; generating a new one", key_path);
+        }
+
+        let key = SecretKey::generate();
+        tokio::fs::create_dir_all(base_data_dir).await?;
+
+        // Create the file with owner-only permissions from the start and write through
+        // a temp file renamed into place, so the plaintext key is never briefly
+        // world/group-readable the way a write-then-chmod would leave it.
+        let temp_path = base_data_dir.join(format!("{SECRET_KEY_FILE_NAME}.tmp"));
+        write_owner_only(&temp_path, &key.to_bytes()).await?;
+        tokio::fs::rename(&temp_path, &key_path).await?;
+        Ok(key)
+    }
+
+    /// Restore a profile manager from its on-disk index (`<base_data_dir>/profiles.json`),
+    /// reconciling it against the directories actually present so a profile whose
+    /// `data_dir` was deleted out-of-band doesn't resurrect as a phantom entry. Falls
+    /// back to an empty manager if no index exists yet (e.g. first run).
+    pub async fn load(base_data_dir: PathBuf) -> Result<Self> {
+        let secret_key = Self::load_or_generate_secret_key(&base_data_dir).await?;
+
+        let index_path = base_data_dir.join(INDEX_FILE_NAME);
+        let index = match tokio::fs::read(&index_path).await {
+            Ok(bytes) => serde_json::from_slice::<ProfileIndex>(&bytes).unwrap_or_default(),
+            Err(_) => ProfileIndex::default(),
+        };
+
+        let mut profiles = HashMap::new();
+        for profile in index.profiles {
+            if profile.data_dir.exists() {
+                profiles.insert(profile.id.clone(), profile);
+            } else {
+                warn!(
+                    "dropping profile '{}' from the index: its data dir {:?} no longer exists",
+                    profile.id, profile.data_dir
+                );
+            }
+        }
+
+        let active_profile_id = index.active_profile_id.filter(|id| profiles.contains_key(id));
+
+        let manager = Self {
+            profiles: RwLock::new(profiles),
+            active_profile_id: RwLock::new(active_profile_id),
+            base_data_dir,
+            secret_key,
+        };
+        manager.persist_index().await?;
+        Ok(manager)
+    }
+
+    fn index_path(&self) -> PathBuf {
+        self.base_data_dir.join(INDEX_FILE_NAME)
+    }
+
+    /// Write the current in-memory state to `profiles.json` via a temp file renamed
+    /// into place, so a crash mid-write can't leave a truncated/corrupt index behind
+    /// for the next `load` to choke on.
+    async fn persist_index(&self) -> Result<()> {
+        let index = ProfileIndex {
+            profiles: self.profiles.read().await.values().cloned().collect(),
+            active_profile_id: self.active_profile_id.read().await.clone(),
+        };
+
+        tokio::fs::create_dir_all(&self.base_data_dir).await?;
+        let temp_path = self.base_data_dir.join(format!(".{INDEX_FILE_NAME}.tmp"));
+        tokio::fs::write(&temp_path, serde_json::to_vec_pretty(&index)?).await?;
+        tokio::fs::rename(&temp_path, self.index_path()).await?;
+        Ok(())
+    }
+
     /// Create a new profile
     pub async fn create_profile(&self, name: &str, is_default: bool) -> Result<BrowserProfile> {
         let id = Uuid::new_v4().to_string();
@@ -93,6 +220,7 @@ impl BrowserProfileManager {
         };
 
         self.profiles.write().await.insert(id, profile.clone());
+        self.persist_index().await?;
         Ok(profile)
     }
 
@@ -108,47 +236,69 @@ impl BrowserProfileManager {
 
     /// Switch to a profile
     pub async fn switch_profile(&self, profile_id: &str) -> Result<()> {
-        let mut profiles = self.profiles.write().await;
-        if let Some(profile) = profiles.get_mut(profile_id) {
-            profile.last_used = Utc::now();
-            *self.active_profile_id.write().await = Some(profile_id.to_string());
-            Ok(())
-        } else {
-            Err(anyhow::anyhow!("Profile not found"))
+        {
+            let mut profiles = self.profiles.write().await;
+            match profiles.get_mut(profile_id) {
+                Some(profile) => profile.last_used = Utc::now(),
+                None => return Err(anyhow::anyhow!("Profile not found")),
+            }
         }
+        *self.active_profile_id.write().await = Some(profile_id.to_string());
+        self.persist_index().await?;
+        Ok(())
     }
 
     /// Delete a profile
     pub async fn delete_profile(&self, profile_id: &str) -> Result<()> {
-        let mut profiles = self.profiles.write().await;
-        if let Some(profile) = profiles.remove(profile_id) {
+        let removed = self.profiles.write().await.remove(profile_id);
+        if let Some(profile) = removed {
             // Remove profile directory
             if profile.data_dir.exists() {
                 tokio::fs::remove_dir_all(&profile.data_dir).await?;
             }
+
+            let mut active_profile_id = self.active_profile_id.write().await;
+            if active_profile_id.as_deref() == Some(profile_id) {
+                *active_profile_id = None;
+            }
+            drop(active_profile_id);
+            self.persist_index().await?;
         }
         Ok(())
     }
 
     /// Update profile settings
     pub async fn update_settings(&self, profile_id: &str, settings: ProfileSettings) -> Result<()> {
-        let mut profiles = self.profiles.write().await;
-        if let Some(profile) = profiles.get_mut(profile_id) {
-            profile.settings = settings;
-            Ok(())
-        } else {
-            Err(anyhow::anyhow!("Profile not found"))
+        {
+            let mut profiles = self.profiles.write().await;
+            match profiles.get_mut(profile_id) {
+                Some(profile) => profile.settings = settings,
+                None => return Err(anyhow::anyhow!("Profile not found")),
+            }
         }
+        self.persist_index().await?;
+        Ok(())
     }
 
-    /// Export profile to JSON
-    pub async fn export_profile(&self, profile_id: &str) -> Result<String> {
+    /// Export profile to JSON. The exported proxy username/password (if any) remain
+    /// [`Secret`] envelopes rather than plaintext either way; when `include_secrets`
+    /// is `false` they're stripped entirely instead of exported still-encrypted, for
+    /// a sharable export that doesn't carry proxy credentials at all (encrypted or
+    /// not) to whoever receives it.
+    pub async fn export_profile(&self, profile_id: &str, include_secrets: bool) -> Result<String> {
         let profiles = self.profiles.read().await;
-        if let Some(profile) = profiles.get(profile_id) {
-            Ok(serde_json::to_string_pretty(profile)?)
-        } else {
-            Err(anyhow::anyhow!("Profile not found"))
+        let profile = profiles.get(profile_id).ok_or_else(|| anyhow::anyhow!("Profile not found"))?;
+
+        if include_secrets {
+            return Ok(serde_json::to_string_pretty(profile)?);
         }
+
+        let mut sanitized = profile.clone();
+        if let Some(proxy_config) = sanitized.settings.proxy_config.as_mut() {
+            proxy_config.username = None;
+            proxy_config.password = None;
+        }
+        Ok(serde_json::to_string_pretty(&sanitized)?)
     }
 
     /// Import profile from JSON
@@ -162,6 +312,7 @@ impl BrowserProfileManager {
             .write()
             .await
             .insert(profile.id.clone(), profile.clone());
+        self.persist_index().await?;
 
         Ok(profile)
     }
@@ -182,3 +333,28 @@ impl Default for BrowserProfileManager {
         Self::new(PathBuf::from("./profiles"))
     }
 }
+
+/// Write `bytes` to `path`, creating the file with owner-only permissions from the
+/// moment it's created rather than chmod-ing it afterward -- avoids the TOCTOU window
+/// where another local user/process could read the plaintext key before permissions
+/// were tightened.
+#[cfg(unix)]
+async fn write_owner_only(path: &std::path::Path, bytes: &[u8]) -> Result<()> {
+    use tokio::io::AsyncWriteExt;
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)
+        .await?;
+    file.write_all(bytes).await?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+async fn write_owner_only(path: &std::path::Path, bytes: &[u8]) -> Result<()> {
+    tokio::fs::write(path, bytes).await?;
+    Ok(())
+}
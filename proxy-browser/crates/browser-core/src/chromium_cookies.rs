@@ -0,0 +1,104 @@
+//! Third-party cookie partitioning and heuristic grace grants
+//!
+//! Backs [`crate::chromium_engine::CookieIsolationMode::Partitioned`] (CHIPS-style: a
+//! cookie set for a third-party site is keyed by the top-level site embedding it,
+//! rather than shared across every site that embeds it). On top of that, Chrome-style
+//! third-party-cookie deprecation isn't strictly all-or-nothing: a qualifying
+//! first-party interaction with the embedding site (e.g. completing a popup/redirect
+//! auth flow) earns the embedded third party a short, bounded grace window in which
+//! its unpartitioned cookies are still allowed. [`ThirdPartyCookieGrants`] tracks those
+//! grants per `(top-level site, third-party site)` pair with a TTL and a cap on how
+//! many can be active at once, so a long-running session can't accumulate unbounded
+//! grant state.
+
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+
+/// Active (top-level-site, third-party-site) grace grants earned by a qualifying
+/// first-party interaction, each expiring `ttl` after it was recorded.
+pub struct ThirdPartyCookieGrants {
+    grants: RwLock<HashMap<(String, String), DateTime<Utc>>>,
+    ttl: Duration,
+    max_active: usize,
+}
+
+impl ThirdPartyCookieGrants {
+    pub fn new(ttl_secs: u64, max_active: usize) -> Self {
+        Self {
+            grants: RwLock::new(HashMap::new()),
+            ttl: Duration::seconds(ttl_secs as i64),
+            max_active,
+        }
+    }
+
+    /// Record a qualifying first-party interaction with `top_level_site`, granting
+    /// `third_party_site` unpartitioned cookies there until the grant expires. Prunes
+    /// already-expired grants first; if still at `max_active`, evicts whichever grant
+    /// expires soonest to make room.
+    pub async fn grant(&self, top_level_site: &str, third_party_site: &str) {
+        let key = (top_level_site.to_string(), third_party_site.to_string());
+        let mut grants = self.grants.write().await;
+        let now = Utc::now();
+        grants.retain(|_, expires_at| *expires_at > now);
+
+        if grants.len() >= self.max_active && !grants.contains_key(&key) {
+            if let Some(oldest) = grants.iter().min_by_key(|(_, expires_at)| **expires_at).map(|(k, _)| k.clone()) {
+                grants.remove(&oldest);
+            }
+        }
+
+        grants.insert(key, now + self.ttl);
+    }
+
+    /// Whether `third_party_site` currently has an active grant under `top_level_site`.
+    /// Lazily drops the entry if it's expired.
+    pub async fn is_granted(&self, top_level_site: &str, third_party_site: &str) -> bool {
+        let key = (top_level_site.to_string(), third_party_site.to_string());
+        let mut grants = self.grants.write().await;
+        match grants.get(&key) {
+            Some(expires_at) if *expires_at > Utc::now() => true,
+            Some(_) => {
+                grants.remove(&key);
+                false
+            }
+            None => false,
+        }
+    }
+
+    /// How many grants are currently active (expired ones pruned as part of the count).
+    pub async fn active_grant_count(&self) -> usize {
+        let mut grants = self.grants.write().await;
+        let now = Utc::now();
+        grants.retain(|_, expires_at| *expires_at > now);
+        grants.len()
+    }
+}
+
+/// Decide whether a cookie belonging to `third_party_site`, embedded under
+/// `top_level_site`, should be sent/stored right now.
+///
+/// First-party requests (`top_level_site == third_party_site`) are always allowed.
+/// Under [`crate::chromium_engine::CookieIsolationMode::Partitioned`], a third party is
+/// otherwise blocked unless it's in `allowlist` (sites exempt from partitioning
+/// entirely) or currently holds an active grant in `grants`. The coarser isolation
+/// modes don't block third parties by site at all -- they isolate storage by tab/domain
+/// scope, not by blocking cross-site cookies -- so this always allows for them.
+pub async fn third_party_cookie_allowed(
+    mode: crate::chromium_engine::CookieIsolationMode,
+    allowlist: &[String],
+    grants: &ThirdPartyCookieGrants,
+    top_level_site: &str,
+    third_party_site: &str,
+) -> bool {
+    if top_level_site == third_party_site {
+        return true;
+    }
+
+    match mode {
+        crate::chromium_engine::CookieIsolationMode::Partitioned => {
+            allowlist.iter().any(|site| site == third_party_site) || grants.is_granted(top_level_site, third_party_site).await
+        }
+        _ => true,
+    }
+}
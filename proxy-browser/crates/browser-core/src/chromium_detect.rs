@@ -0,0 +1,209 @@
+//! Cross-platform Chrome/Chromium Discovery
+//!
+//! Locates a system-installed Chrome/Chromium executable without relying on a
+//! single hardcoded path list, so [`crate::chromium_engine::ChromiumEngine::resolve_executable`]
+//! and the integration-test `skip_if_no_chrome!` macro both resolve to the same binary.
+//! Candidates are tried in priority order: an explicit `CHROME`/`CHROMIUM_PATH`
+//! environment variable, the Windows registry, `$PATH`, then a fixed list of
+//! well-known install locations.
+
+use std::path::PathBuf;
+
+/// Names searched for on `$PATH` when no environment override or registry entry
+/// resolved a binary, in priority order.
+const PATH_CANDIDATES: &[&str] = &[
+    "google-chrome",
+    "google-chrome-stable",
+    "chromium",
+    "chromium-browser",
+];
+
+/// Fixed filesystem locations a system-installed Chrome/Chromium is commonly found
+/// at, tried last after the environment, registry and `$PATH` lookups.
+fn well_known_paths() -> &'static [&'static str] {
+    if cfg!(target_os = "windows") {
+        &[
+            r"C:\Program Files\Google\Chrome\Application\chrome.exe",
+            r"C:\Program Files (x86)\Google\Chrome\Application\chrome.exe",
+            r"C:\Program Files\Chromium\Application\chrome.exe",
+        ]
+    } else if cfg!(target_os = "macos") {
+        &[
+            "/Applications/Google Chrome.app/Contents/MacOS/Google Chrome",
+            "/Applications/Chromium.app/Contents/MacOS/Chromium",
+        ]
+    } else {
+        &[
+            "/usr/bin/google-chrome",
+            "/usr/bin/chromium",
+            "/usr/bin/chromium-browser",
+            "/snap/bin/chromium",
+        ]
+    }
+}
+
+/// Why [`default_executable`] could not locate a Chrome/Chromium binary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DetectionErrorKind {
+    /// No candidate in any source (env var, registry, `$PATH`, well-known paths)
+    /// pointed at a file that actually exists.
+    NotFound,
+}
+
+/// Error returned by [`default_executable`].
+#[derive(Debug, Clone)]
+pub struct DetectionError {
+    pub kind: DetectionErrorKind,
+    pub message: String,
+}
+
+impl DetectionError {
+    fn not_found(message: impl Into<String>) -> Self {
+        Self {
+            kind: DetectionErrorKind::NotFound,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for DetectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}: {}", self.kind, self.message)
+    }
+}
+
+impl std::error::Error for DetectionError {}
+
+/// `CHROME` / `CHROMIUM_PATH`, whichever is set first, if it points at a file that exists.
+fn from_env() -> Option<PathBuf> {
+    for var in ["CHROME", "CHROMIUM_PATH"] {
+        if let Some(path) = std::env::var_os(var) {
+            let path = PathBuf::from(path);
+            if path.exists() {
+                return Some(path);
+            }
+        }
+    }
+    None
+}
+
+/// Search every directory on `$PATH` for each name in [`PATH_CANDIDATES`].
+fn from_path_env() -> Option<PathBuf> {
+    let path_var = std::env::var_os("PATH")?;
+    for dir in std::env::split_paths(&path_var) {
+        for name in PATH_CANDIDATES {
+            let candidate = dir.join(name);
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+            if cfg!(target_os = "windows") {
+                let with_ext = dir.join(format!("{name}.exe"));
+                if with_ext.is_file() {
+                    return Some(with_ext);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// The first existing path among [`well_known_paths`].
+fn from_well_known() -> Option<PathBuf> {
+    well_known_paths().iter().map(PathBuf::from).find(|p| p.exists())
+}
+
+#[cfg(target_os = "windows")]
+mod registry {
+    use std::path::PathBuf;
+    use winreg::enums::HKEY_LOCAL_MACHINE;
+    use winreg::RegKey;
+
+    /// Resolve `chrome.exe`/`chromium.exe` via the `App Paths` registry key Windows
+    /// installers register so shell/`Start-Process` launches can find the binary
+    /// without it being on `PATH`.
+    pub(super) fn from_registry() -> Option<PathBuf> {
+        let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+        for exe_name in ["chrome.exe", "chromium.exe"] {
+            let key_path = format!(r"SOFTWARE\Microsoft\Windows\CurrentVersion\App Paths\{exe_name}");
+            if let Ok(key) = hklm.open_subkey(key_path) {
+                if let Ok(default_value) = key.get_value::<String, _>("") {
+                    let path = PathBuf::from(default_value);
+                    if path.exists() {
+                        return Some(path);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod registry {
+    use std::path::PathBuf;
+
+    pub(super) fn from_registry() -> Option<PathBuf> {
+        None
+    }
+}
+
+/// Resolve a Chrome/Chromium executable to launch, checking each source in
+/// priority order: `CHROME`/`CHROMIUM_PATH` env var, the Windows registry, `$PATH`,
+/// then a fixed list of well-known install locations.
+pub fn default_executable() -> Result<PathBuf, DetectionError> {
+    from_env()
+        .or_else(registry::from_registry)
+        .or_else(from_path_env)
+        .or_else(from_well_known)
+        .ok_or_else(|| {
+            DetectionError::not_found(
+                "no CHROME/CHROMIUM_PATH override, registry entry, $PATH binary or well-known install was found",
+            )
+        })
+}
+
+/// Whether [`default_executable`] can resolve a Chrome/Chromium binary on this machine.
+pub fn is_chrome_available() -> bool {
+    default_executable().is_ok()
+}
+
+/// Resolves a Chrome/Chromium binary to launch, combining system discovery
+/// ([`default_executable`]) with an optional download fallback
+/// ([`crate::chromium_fetcher::fetch_chromium`]), so CI machines without a
+/// preinstalled browser can still launch one instead of failing the precondition.
+pub struct ChromeLocator {
+    /// If set, download a pinned Chromium build when no system install is found.
+    fetcher: Option<crate::chromium_fetcher::FetcherOptions>,
+}
+
+impl ChromeLocator {
+    /// Only look for a system-installed binary; never download one.
+    pub fn system_only() -> Self {
+        Self { fetcher: None }
+    }
+
+    /// Fall back to downloading a build per `fetcher` when no system install is found.
+    pub fn with_fetcher(fetcher: crate::chromium_fetcher::FetcherOptions) -> Self {
+        Self { fetcher: Some(fetcher) }
+    }
+
+    /// Resolve a Chrome/Chromium executable: a system install if [`default_executable`]
+    /// finds one, else (if configured) a downloaded pinned build.
+    pub async fn locate(&self) -> Result<PathBuf, DetectionError> {
+        if let Ok(path) = default_executable() {
+            return Ok(path);
+        }
+
+        if let Some(fetcher) = &self.fetcher {
+            return crate::chromium_fetcher::fetch_chromium(fetcher).await.map_err(|e| {
+                DetectionError::not_found(format!(
+                    "no system Chrome/Chromium found and auto-fetch failed: {e}"
+                ))
+            });
+        }
+
+        Err(DetectionError::not_found(
+            "no CHROME/CHROMIUM_PATH override, registry entry, $PATH binary or well-known install was found, and no fetcher was configured",
+        ))
+    }
+}
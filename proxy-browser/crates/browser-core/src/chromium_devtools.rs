@@ -0,0 +1,183 @@
+//! Chromium DevTools Endpoint Discovery
+//!
+//! Spawns a Chromium process with a free `--remote-debugging-port` and parses its
+//! stderr `DevTools listening on ws://...` banner into the CDP endpoint
+//! [`crate::chromium_engine::ChromiumEngine::launch`] needs to drive the browser.
+
+use regex::Regex;
+use reqwest::Url;
+use std::net::TcpListener;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::{Child, Command};
+use tokio::sync::oneshot;
+
+/// Kind of error raised while launching Chromium and discovering its DevTools endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DevToolsErrorKind {
+    /// No free port was found in the configured scan range.
+    NoAvailablePorts,
+    /// A caller-requested fixed debugging port is already held by another process.
+    DebugPortInUse,
+    /// The `DevTools listening on ws://...` banner never appeared within the timeout.
+    PortOpenTimeout,
+    /// The child process itself could not be spawned.
+    SpawnFailed,
+}
+
+/// Error returned by [`launch_with_devtools`].
+#[derive(Debug)]
+pub struct DevToolsError {
+    pub kind: DevToolsErrorKind,
+    pub message: String,
+}
+
+impl DevToolsError {
+    fn new(kind: DevToolsErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for DevToolsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}: {}", self.kind, self.message)
+    }
+}
+
+impl std::error::Error for DevToolsError {}
+
+/// Controls how [`launch_with_devtools`] picks a debugging port and how long it
+/// waits for the DevTools banner before giving up.
+#[derive(Debug, Clone)]
+pub struct DevToolsDiscoveryOptions {
+    /// Use this exact port instead of picking one, failing with `DebugPortInUse` if
+    /// it's already bound.
+    pub fixed_port: Option<u16>,
+    /// Scan this range for a free port instead of letting the OS assign one.
+    pub port_range: Option<RangeInclusive<u16>>,
+    /// How long to wait for the `DevTools listening on ws://...` banner.
+    pub spawn_timeout: Duration,
+}
+
+impl Default for DevToolsDiscoveryOptions {
+    fn default() -> Self {
+        Self {
+            fixed_port: None,
+            port_range: None,
+            spawn_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+/// A spawned Chromium process plus the DevTools endpoint discovered for it.
+pub struct LaunchedProcess {
+    pub child: Child,
+    pub devtools_url: Url,
+    pub port: u16,
+}
+
+fn allocate_port(options: &DevToolsDiscoveryOptions) -> Result<u16, DevToolsError> {
+    if let Some(port) = options.fixed_port {
+        return TcpListener::bind(("127.0.0.1", port))
+            .map(|listener| {
+                drop(listener);
+                port
+            })
+            .map_err(|e| DevToolsError::new(DevToolsErrorKind::DebugPortInUse, format!("port {port} is already in use: {e}")));
+    }
+
+    if let Some(range) = &options.port_range {
+        for port in range.clone() {
+            if let Ok(listener) = TcpListener::bind(("127.0.0.1", port)) {
+                drop(listener);
+                return Ok(port);
+            }
+        }
+        return Err(DevToolsError::new(
+            DevToolsErrorKind::NoAvailablePorts,
+            format!("no free port found in range {}-{}", range.start(), range.end()),
+        ));
+    }
+
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .map_err(|e| DevToolsError::new(DevToolsErrorKind::NoAvailablePorts, format!("failed to bind an ephemeral port: {e}")))?;
+    listener
+        .local_addr()
+        .map(|addr| addr.port())
+        .map_err(|e| DevToolsError::new(DevToolsErrorKind::NoAvailablePorts, e.to_string()))
+}
+
+/// Spawn `executable` with `args` plus a `--remote-debugging-port`, and wait up to
+/// `options.spawn_timeout` for its stderr to print the DevTools banner. Stderr keeps
+/// being drained on a background task for the life of the child so it never blocks
+/// on a full pipe, even after the banner line has already been found.
+pub async fn launch_with_devtools(executable: &Path, args: &[String], options: &DevToolsDiscoveryOptions) -> Result<LaunchedProcess, DevToolsError> {
+    let port = allocate_port(options)?;
+
+    let mut child = Command::new(executable)
+        .args(args)
+        .arg(format!("--remote-debugging-port={port}"))
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| DevToolsError::new(DevToolsErrorKind::SpawnFailed, format!("failed to spawn '{}': {e}", executable.display())))?;
+
+    let stderr = child
+        .stderr
+        .take()
+        .ok_or_else(|| DevToolsError::new(DevToolsErrorKind::SpawnFailed, "child process did not inherit a stderr pipe"))?;
+
+    let (banner_tx, banner_rx) = oneshot::channel();
+    tokio::spawn(async move {
+        let banner = Regex::new(r"DevTools listening on (ws://\S+)").expect("DevTools banner regex is a fixed, valid pattern");
+        let mut lines = BufReader::new(stderr).lines();
+        let mut banner_tx = Some(banner_tx);
+
+        while let Ok(Some(line)) = lines.next_line().await {
+            if let Some(tx) = banner_tx.take() {
+                if let Some(captures) = banner.captures(&line) {
+                    let _ = tx.send(Some(captures[1].to_string()));
+                } else {
+                    banner_tx = Some(tx);
+                }
+            }
+            // Keep looping (and dropping lines) even once the banner's been found, so
+            // the child never blocks writing to a full stderr pipe.
+        }
+
+        if let Some(tx) = banner_tx {
+            let _ = tx.send(None);
+        }
+    });
+
+    match tokio::time::timeout(options.spawn_timeout, banner_rx).await {
+        Ok(Ok(Some(url))) => match Url::parse(&url) {
+            Ok(devtools_url) => Ok(LaunchedProcess { child, devtools_url, port }),
+            Err(e) => {
+                let _ = child.kill().await;
+                Err(DevToolsError::new(DevToolsErrorKind::PortOpenTimeout, format!("DevTools banner had an invalid URL '{url}': {e}")))
+            }
+        },
+        Ok(Ok(None)) => {
+            let _ = child.kill().await;
+            Err(DevToolsError::new(DevToolsErrorKind::PortOpenTimeout, "child process closed stderr before printing a DevTools banner"))
+        }
+        Ok(Err(_)) => {
+            let _ = child.kill().await;
+            Err(DevToolsError::new(DevToolsErrorKind::PortOpenTimeout, "DevTools banner reader task ended unexpectedly"))
+        }
+        Err(_) => {
+            let _ = child.kill().await;
+            Err(DevToolsError::new(
+                DevToolsErrorKind::PortOpenTimeout,
+                format!("no DevTools banner seen within {:?}", options.spawn_timeout),
+            ))
+        }
+    }
+}
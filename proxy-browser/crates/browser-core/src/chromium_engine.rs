@@ -0,0 +1,1885 @@
+//! Chromium Engine Module
+//!
+//! Provides an integrated Chromium browser engine as an alternative to driving the
+//! system browser, including:
+//! - Per-tab lifecycle management (create, navigate, close, activate)
+//! - Fingerprinting, geolocation and network-throttling configuration
+//! - Per-tab proxy assignment
+//! - Per-tab CDP event streaming (console messages, exceptions, network responses)
+//! - Per-tab/per-engine request interception via [`crate::chromium_interception::RequestFilter`]
+//! - A WebDriver-style capabilities negotiation layer for building a
+//!   [`ChromiumEngineConfig`] from a serialized remote session description
+
+use crate::chromium_detect;
+use crate::chromium_devtools::{self, DevToolsDiscoveryOptions, DevToolsErrorKind};
+use crate::chromium_fetcher::FetcherOptions;
+use crate::chromium_http_cache::HttpCacheConfig;
+use crate::chromium_interception::{
+    FilterAction, InterceptedRequest, InterceptedResponse, RequestFilter,
+};
+use crate::proxy::ProxySettings;
+use crate::proxy_pool::{ProxyPool, ProxyPoolStatus, ProxyRotationStrategy};
+use async_trait::async_trait;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::process::Child;
+use tokio::sync::{mpsc, RwLock};
+use tracing::warn;
+use uuid::Uuid;
+
+/// Bound on each tab's [`TabEvent`] channel created by [`ChromiumEngine::subscribe_events`].
+/// Events are dropped (not blocked on) past this so a slow/absent subscriber can't stall
+/// the page activity that produces them.
+const TAB_EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// Default [`ChromiumEngineConfig::third_party_grace_window_secs`]: 15 minutes, long
+/// enough to cover a popup/redirect auth flow completing but short enough that a grant
+/// doesn't linger for the rest of the session.
+const DEFAULT_THIRD_PARTY_GRACE_WINDOW_SECS: u64 = 15 * 60;
+
+/// Default [`ChromiumEngineConfig::max_active_cookie_grants`].
+const DEFAULT_MAX_ACTIVE_COOKIE_GRANTS: usize = 1000;
+
+/// Which browser engine is currently driving the UI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum BrowserEngineType {
+    /// Drive the user's installed system browser via the OS/webview shell.
+    #[default]
+    System,
+    /// Drive a bundled/managed Chromium instance directly over CDP.
+    IntegratedChromium,
+}
+
+/// Simulated network throttling profile, modeled on Chrome DevTools' presets.
+///
+/// `get_params` returns `(download_throughput, upload_throughput, latency)` in
+/// bytes/sec and milliseconds respectively, matching the shape CDP's
+/// `Network.emulateNetworkConditions` expects. `-1.0` throughput means "no limit".
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum NetworkCondition {
+    #[default]
+    None,
+    Slow3G,
+    Fast3G,
+    LTE,
+    Custom {
+        download_throughput: f64,
+        upload_throughput: f64,
+        latency: f64,
+    },
+}
+
+impl NetworkCondition {
+    /// Resolve this condition to `(download_throughput, upload_throughput, latency)`.
+    pub fn get_params(&self) -> (f64, f64, f64) {
+        match self {
+            NetworkCondition::None => (-1.0, -1.0, 0.0),
+            NetworkCondition::Slow3G => (500.0 * 1024.0 / 8.0, 500.0 * 1024.0 / 8.0, 400.0),
+            NetworkCondition::Fast3G => (1.5 * 1024.0 * 1024.0 / 8.0, 750.0 * 1024.0 / 8.0, 150.0),
+            NetworkCondition::LTE => (
+                12.0 * 1024.0 * 1024.0 / 8.0,
+                5.0 * 1024.0 * 1024.0 / 8.0,
+                50.0,
+            ),
+            NetworkCondition::Custom {
+                download_throughput,
+                upload_throughput,
+                latency,
+            } => (*download_throughput, *upload_throughput, *latency),
+        }
+    }
+}
+
+/// Browser fingerprint spoofing/randomization knobs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FingerprintConfig {
+    pub randomize_canvas: bool,
+    pub randomize_webgl: bool,
+    pub randomize_audio: bool,
+    pub spoof_screen: bool,
+    pub screen_width: u32,
+    pub screen_height: u32,
+    pub spoof_hardware_concurrency: bool,
+    pub hardware_concurrency: u32,
+    pub spoof_device_memory: bool,
+    pub device_memory: u32,
+    pub spoof_timezone: bool,
+    pub timezone: String,
+    pub spoof_language: bool,
+    pub language: String,
+    pub spoof_platform: bool,
+    pub platform: String,
+}
+
+impl Default for FingerprintConfig {
+    fn default() -> Self {
+        Self {
+            randomize_canvas: true,
+            randomize_webgl: true,
+            randomize_audio: true,
+            spoof_screen: false,
+            screen_width: 1920,
+            screen_height: 1080,
+            spoof_hardware_concurrency: false,
+            hardware_concurrency: 8,
+            spoof_device_memory: false,
+            device_memory: 8,
+            spoof_timezone: false,
+            timezone: "America/New_York".to_string(),
+            spoof_language: false,
+            language: "en-US".to_string(),
+            spoof_platform: false,
+            platform: "Win32".to_string(),
+        }
+    }
+}
+
+/// Credentials presented to an upstream proxy via `Proxy-Authorization`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProxyAuth {
+    pub username: String,
+    pub password: String,
+}
+
+/// How cookies are partitioned across tabs/contexts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum CookieIsolationMode {
+    #[default]
+    None,
+    PerTab,
+    PerDomain,
+    FullContext,
+    /// CHIPS-style partitioning: a cookie set by a third-party site is keyed by the
+    /// top-level site embedding it instead of being shared across every embedder, and
+    /// third-party cookies are blocked outright except for sites in
+    /// [`ChromiumEngineConfig::cookie_partition_allowlist`] or currently holding a
+    /// grace grant from [`crate::chromium_cookies::ThirdPartyCookieGrants`]. See
+    /// [`crate::chromium_cookies`].
+    Partitioned,
+}
+
+/// A spoofed geolocation fix, in the same shape CDP's `Emulation.setGeolocationOverride` takes.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Geolocation {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub accuracy: f64,
+}
+
+/// Configuration for a [`ChromiumEngine`] instance.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChromiumEngineConfig {
+    pub executable_path: Option<PathBuf>,
+    pub headless: bool,
+    pub user_data_dir: Option<PathBuf>,
+    pub sandbox: bool,
+    pub extra_args: Vec<String>,
+    pub proxy: Option<ProxySettings>,
+    pub proxy_auth: Option<ProxyAuth>,
+    pub stealth_mode: bool,
+    pub user_agent: Option<String>,
+    pub viewport_width: u32,
+    pub viewport_height: u32,
+    pub webrtc_protection: bool,
+    pub doh_server: Option<String>,
+    pub network_condition: NetworkCondition,
+    pub fingerprint: FingerprintConfig,
+    pub cookie_isolation: CookieIsolationMode,
+    /// Sites exempt from [`CookieIsolationMode::Partitioned`] entirely: their
+    /// third-party cookies are always allowed, no grant needed.
+    pub cookie_partition_allowlist: Vec<String>,
+    /// How long a [`CookieIsolationMode::Partitioned`] grace grant (earned by a
+    /// qualifying first-party interaction, e.g. a popup/redirect auth flow) stays
+    /// active before the third party goes back to being blocked. See
+    /// [`crate::chromium_cookies::ThirdPartyCookieGrants`].
+    pub third_party_grace_window_secs: u64,
+    /// Cap on how many [`CookieIsolationMode::Partitioned`] grace grants can be active
+    /// at once; granting past this evicts whichever grant expires soonest.
+    pub max_active_cookie_grants: usize,
+    pub blocked_urls: Vec<String>,
+    pub enable_interception: bool,
+    /// Revalidating HTTP cache for intercepted requests (see
+    /// [`crate::chromium_http_cache::HttpCache`]), layered in front of whatever filter
+    /// `enable_interception`'s blocklist already installs. Disabled by default; has no
+    /// effect unless `enable_interception` is also on.
+    pub http_cache: HttpCacheConfig,
+    pub geolocation: Option<Geolocation>,
+    /// When `executable_path` isn't set and no system Chrome/Chromium is detected,
+    /// download a pinned build via [`crate::chromium_fetcher::fetch_chromium`] instead of
+    /// failing to launch. Off by default since it may reach the network.
+    pub auto_fetch: bool,
+    /// Revision, platform and cache-directory settings used when `auto_fetch` falls
+    /// back to downloading a build.
+    pub fetcher: FetcherOptions,
+    /// Scan this inclusive `(start, end)` range for a free DevTools debugging port
+    /// instead of letting the OS assign one from an ephemeral `127.0.0.1:0` bind.
+    /// Defaults to `8000..=9000`.
+    pub devtools_port_range: Option<(u16, u16)>,
+    /// How long to wait for the child process's `DevTools listening on ws://...`
+    /// banner before [`ChromiumEngine::launch`] fails with `PortOpenTimeout`.
+    pub devtools_timeout_secs: u64,
+    /// Give this instance a fresh, isolated temp directory as its `user_data_dir` on
+    /// every [`ChromiumEngine::launch`], overriding any path set here, and delete it on
+    /// `shutdown`/drop. Keeps parallel/repeated launches from sharing cookies, caches
+    /// or crash state.
+    pub ephemeral_profile: bool,
+    /// Unpacked extension directories to load via `--load-extension`. Requires a
+    /// persistent (non-incognito) profile; [`ChromiumEngine::launch`] provisions a
+    /// `user_data_dir` automatically if one isn't already set.
+    pub extensions: Vec<PathBuf>,
+    /// Restrict to just these extension ids/paths via `--disable-extensions-except`,
+    /// disabling any other extension already installed in `user_data_dir`.
+    pub disable_extensions_except: Vec<PathBuf>,
+    /// Extra Chromium command-line flags appended verbatim to the launch arguments,
+    /// e.g. `--disable-gpu`, `--lang=fr`, or a custom `--proxy-bypass-list`. A flag
+    /// that collides with one this engine already manages (`--headless`,
+    /// `--proxy-server`, `--user-agent`) is dropped with a warning rather than
+    /// duplicated -- the engine's own setting always wins.
+    pub extra_chrome_flags: Vec<String>,
+}
+
+/// Flag name prefixes (before any `=value`) this engine already manages via its own
+/// config fields, and so won't let [`ChromiumEngineConfig::extra_chrome_flags`]
+/// override or duplicate.
+const RESERVED_CHROME_FLAGS: &[&str] = &["--headless", "--proxy-server", "--user-agent"];
+
+impl Default for ChromiumEngineConfig {
+    fn default() -> Self {
+        Self {
+            executable_path: None,
+            headless: false,
+            user_data_dir: None,
+            sandbox: true,
+            extra_args: Vec::new(),
+            proxy: None,
+            proxy_auth: None,
+            stealth_mode: true,
+            user_agent: None,
+            viewport_width: 1920,
+            viewport_height: 1080,
+            webrtc_protection: true,
+            doh_server: Some("https://cloudflare-dns.com/dns-query".to_string()),
+            network_condition: NetworkCondition::None,
+            fingerprint: FingerprintConfig::default(),
+            cookie_isolation: CookieIsolationMode::None,
+            cookie_partition_allowlist: Vec::new(),
+            third_party_grace_window_secs: DEFAULT_THIRD_PARTY_GRACE_WINDOW_SECS,
+            max_active_cookie_grants: DEFAULT_MAX_ACTIVE_COOKIE_GRANTS,
+            blocked_urls: Vec::new(),
+            enable_interception: false,
+            http_cache: HttpCacheConfig::default(),
+            geolocation: None,
+            auto_fetch: false,
+            fetcher: FetcherOptions::default(),
+            devtools_port_range: Some((8000, 9000)),
+            devtools_timeout_secs: 10,
+            ephemeral_profile: false,
+            extensions: Vec::new(),
+            disable_extensions_except: Vec::new(),
+            extra_chrome_flags: Vec::new(),
+        }
+    }
+}
+
+impl ChromiumEngineConfig {
+    /// Build the command-line arguments [`ChromiumEngine::launch`] passes to the
+    /// Chromium process, based on this config. The `--remote-debugging-port` flag is
+    /// appended separately once a port has been allocated.
+    fn build_launch_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+
+        if self.headless {
+            args.push("--headless=new".to_string());
+        }
+        if !self.sandbox {
+            args.push("--no-sandbox".to_string());
+        }
+        if let Some(user_data_dir) = &self.user_data_dir {
+            args.push(format!("--user-data-dir={}", user_data_dir.display()));
+        }
+        args.push(format!(
+            "--window-size={},{}",
+            self.viewport_width, self.viewport_height
+        ));
+        if let Some(user_agent) = &self.user_agent {
+            args.push(format!("--user-agent={user_agent}"));
+        }
+        if !self.extensions.is_empty() {
+            args.push(format!("--load-extension={}", join_paths(&self.extensions)));
+        }
+        if !self.disable_extensions_except.is_empty() {
+            args.push(format!(
+                "--disable-extensions-except={}",
+                join_paths(&self.disable_extensions_except)
+            ));
+        }
+        // `--disable-extensions` and `--load-extension` are contradictory; loading
+        // extensions always wins over a blanket disable carried in `extra_args`.
+        let loading_extensions = !self.extensions.is_empty();
+        args.extend(
+            self.extra_args
+                .iter()
+                .filter(|arg| !(loading_extensions && arg.as_str() == "--disable-extensions"))
+                .cloned(),
+        );
+
+        args.extend(self.extra_chrome_flags.iter().cloned());
+
+        args
+    }
+
+    /// Drop any `extra_chrome_flags` entry that collides with a flag this engine
+    /// already manages, logging a warning for each. Called from
+    /// [`ChromiumEngine::launch`] before [`ChromiumEngineConfig::build_launch_args`]
+    /// so the dropped entries also disappear from what [`ChromiumEngine::get_config`]
+    /// reports afterward -- the engine's own setting always wins over a user-supplied
+    /// duplicate.
+    fn sanitize_extra_chrome_flags(&mut self) {
+        self.extra_chrome_flags.retain(|flag| {
+            let name = flag.split('=').next().unwrap_or(flag);
+            let reserved = RESERVED_CHROME_FLAGS.contains(&name);
+            if reserved {
+                warn!(
+                    "dropping extra_chrome_flags entry '{flag}': '{name}' is already managed by this engine's own config"
+                );
+            }
+            !reserved
+        });
+    }
+}
+
+/// Join extension directories into the comma-separated list Chromium's
+/// `--load-extension`/`--disable-extensions-except` flags expect.
+fn join_paths(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|p| p.display().to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Derive Chrome's unpacked-extension id: SHA-256 the extension's canonicalized path,
+/// take the first 16 bytes, and map each nibble (0-15) onto the letters `a`-`p`.
+fn unpacked_extension_id(path: &Path) -> String {
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let mut hasher = Sha256::new();
+    hasher.update(canonical.to_string_lossy().as_bytes());
+    let digest = hasher.finalize();
+    digest[..16]
+        .iter()
+        .flat_map(|byte| [byte >> 4, byte & 0x0f])
+        .map(|nibble| (b'a' + nibble) as char)
+        .collect()
+}
+
+/// The `name` field from `<path>/manifest.json`, or the directory's own file name if
+/// the manifest is missing, unreadable, or has no `name` field.
+fn extension_manifest_name(path: &Path) -> String {
+    let fallback = || {
+        path.file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string())
+    };
+
+    std::fs::read_to_string(path.join("manifest.json"))
+        .ok()
+        .and_then(|contents| serde_json::from_str::<serde_json::Value>(&contents).ok())
+        .and_then(|manifest| {
+            manifest
+                .get("name")
+                .and_then(|n| n.as_str())
+                .map(str::to_string)
+        })
+        .unwrap_or_else(fallback)
+}
+
+/// A single requested capability that this engine could not satisfy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnsupportedCapability {
+    pub capability: String,
+    pub reason: String,
+}
+
+/// Raised when a WebDriver-style capabilities object fails to negotiate against this
+/// engine, e.g. an unknown key or a value outside what the engine supports.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapabilitiesError {
+    pub failures: Vec<UnsupportedCapability>,
+}
+
+impl std::fmt::Display for CapabilitiesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "capabilities negotiation failed: ")?;
+        for (i, failure) in self.failures.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}: {}", failure.capability, failure.reason)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for CapabilitiesError {}
+
+/// The set of capabilities recognized by [`ChromiumEngineConfig::from_capabilities`].
+///
+/// Anything not in this list is rejected as an unknown capability rather than
+/// silently ignored, so a caller driving us from a remote session description finds
+/// out immediately when it asked for something we don't support.
+const SUPPORTED_CAPABILITY_KEYS: &[&str] = &[
+    "browserName",
+    "headless",
+    "sandbox",
+    "proxy",
+    "userAgent",
+    "geolocation",
+    "viewport",
+    "stealthMode",
+    "webrtcProtection",
+    "dohServer",
+    "cookieIsolation",
+    "cookiePartitionAllowlist",
+    "thirdPartyGraceWindowSecs",
+    "blockedUrls",
+    "extraArgs",
+    "networkCondition",
+];
+
+impl ChromiumEngineConfig {
+    /// Negotiate a WebDriver-style `capabilities` JSON object into a `ChromiumEngineConfig`,
+    /// validating each requested key against what this engine supports. Unknown keys and
+    /// values that can't be satisfied are collected into a [`CapabilitiesError`] rather than
+    /// failing on the first mismatch, so a caller sees every problem at once.
+    pub fn from_capabilities(capabilities: &serde_json::Value) -> Result<Self, CapabilitiesError> {
+        let mut config = ChromiumEngineConfig::default();
+        let mut failures = Vec::new();
+
+        let Some(requested) = capabilities.as_object() else {
+            failures.push(UnsupportedCapability {
+                capability: "<root>".to_string(),
+                reason: "capabilities must be a JSON object".to_string(),
+            });
+            return Err(CapabilitiesError { failures });
+        };
+
+        for (key, value) in requested {
+            if !SUPPORTED_CAPABILITY_KEYS.contains(&key.as_str()) {
+                failures.push(UnsupportedCapability {
+                    capability: key.clone(),
+                    reason: "unknown capability".to_string(),
+                });
+                continue;
+            }
+
+            match key.as_str() {
+                "browserName" => match value.as_str() {
+                    Some(name)
+                        if name.eq_ignore_ascii_case("chrome")
+                            || name.eq_ignore_ascii_case("chromium") => {}
+                    Some(other) => failures.push(UnsupportedCapability {
+                        capability: key.clone(),
+                        reason: format!(
+                            "unsupported browser '{other}', this engine is Chromium-only"
+                        ),
+                    }),
+                    None => failures.push(UnsupportedCapability {
+                        capability: key.clone(),
+                        reason: "expected a string".to_string(),
+                    }),
+                },
+                "headless" => match value.as_bool() {
+                    Some(v) => config.headless = v,
+                    None => failures.push(UnsupportedCapability {
+                        capability: key.clone(),
+                        reason: "expected a boolean".to_string(),
+                    }),
+                },
+                "sandbox" => match value.as_bool() {
+                    Some(v) => config.sandbox = v,
+                    None => failures.push(UnsupportedCapability {
+                        capability: key.clone(),
+                        reason: "expected a boolean".to_string(),
+                    }),
+                },
+                "stealthMode" => match value.as_bool() {
+                    Some(v) => config.stealth_mode = v,
+                    None => failures.push(UnsupportedCapability {
+                        capability: key.clone(),
+                        reason: "expected a boolean".to_string(),
+                    }),
+                },
+                "webrtcProtection" => match value.as_bool() {
+                    Some(v) => config.webrtc_protection = v,
+                    None => failures.push(UnsupportedCapability {
+                        capability: key.clone(),
+                        reason: "expected a boolean".to_string(),
+                    }),
+                },
+                "userAgent" => match value.as_str() {
+                    Some(v) => config.user_agent = Some(v.to_string()),
+                    None => failures.push(UnsupportedCapability {
+                        capability: key.clone(),
+                        reason: "expected a string".to_string(),
+                    }),
+                },
+                "dohServer" => match value.as_str() {
+                    Some(v) => config.doh_server = Some(v.to_string()),
+                    None => failures.push(UnsupportedCapability {
+                        capability: key.clone(),
+                        reason: "expected a string".to_string(),
+                    }),
+                },
+                "extraArgs" => match value.as_array() {
+                    Some(items) if items.iter().all(|v| v.is_string()) => {
+                        config.extra_args = items
+                            .iter()
+                            .map(|v| v.as_str().unwrap_or_default().to_string())
+                            .collect();
+                    }
+                    _ => failures.push(UnsupportedCapability {
+                        capability: key.clone(),
+                        reason: "expected an array of strings".to_string(),
+                    }),
+                },
+                "blockedUrls" => match value.as_array() {
+                    Some(items) if items.iter().all(|v| v.is_string()) => {
+                        config.blocked_urls = items
+                            .iter()
+                            .map(|v| v.as_str().unwrap_or_default().to_string())
+                            .collect();
+                        config.enable_interception = true;
+                    }
+                    _ => failures.push(UnsupportedCapability {
+                        capability: key.clone(),
+                        reason: "expected an array of strings".to_string(),
+                    }),
+                },
+                "cookieIsolation" => match value.as_str() {
+                    Some("none") => config.cookie_isolation = CookieIsolationMode::None,
+                    Some("perTab") => config.cookie_isolation = CookieIsolationMode::PerTab,
+                    Some("perDomain") => config.cookie_isolation = CookieIsolationMode::PerDomain,
+                    Some("fullContext") => {
+                        config.cookie_isolation = CookieIsolationMode::FullContext
+                    }
+                    Some("partitioned") => {
+                        config.cookie_isolation = CookieIsolationMode::Partitioned
+                    }
+                    Some(other) => failures.push(UnsupportedCapability {
+                        capability: key.clone(),
+                        reason: format!("unknown cookie isolation mode '{other}'"),
+                    }),
+                    None => failures.push(UnsupportedCapability {
+                        capability: key.clone(),
+                        reason: "expected a string".to_string(),
+                    }),
+                },
+                "cookiePartitionAllowlist" => match value.as_array() {
+                    Some(items) if items.iter().all(|v| v.is_string()) => {
+                        config.cookie_partition_allowlist = items
+                            .iter()
+                            .map(|v| v.as_str().unwrap_or_default().to_string())
+                            .collect();
+                    }
+                    _ => failures.push(UnsupportedCapability {
+                        capability: key.clone(),
+                        reason: "expected an array of strings".to_string(),
+                    }),
+                },
+                "thirdPartyGraceWindowSecs" => match value.as_u64() {
+                    Some(v) => config.third_party_grace_window_secs = v,
+                    None => failures.push(UnsupportedCapability {
+                        capability: key.clone(),
+                        reason: "expected a non-negative integer".to_string(),
+                    }),
+                },
+                "networkCondition" => match value.as_str() {
+                    Some("none") => config.network_condition = NetworkCondition::None,
+                    Some("slow3g") => config.network_condition = NetworkCondition::Slow3G,
+                    Some("fast3g") => config.network_condition = NetworkCondition::Fast3G,
+                    Some("lte") => config.network_condition = NetworkCondition::LTE,
+                    Some(other) => failures.push(UnsupportedCapability {
+                        capability: key.clone(),
+                        reason: format!("unknown network condition '{other}'"),
+                    }),
+                    None => failures.push(UnsupportedCapability {
+                        capability: key.clone(),
+                        reason: "expected a string".to_string(),
+                    }),
+                },
+                "viewport" => match (
+                    value.get("width").and_then(|v| v.as_u64()),
+                    value.get("height").and_then(|v| v.as_u64()),
+                ) {
+                    (Some(width), Some(height)) => {
+                        config.viewport_width = width as u32;
+                        config.viewport_height = height as u32;
+                    }
+                    _ => failures.push(UnsupportedCapability {
+                        capability: key.clone(),
+                        reason: "expected an object with numeric 'width' and 'height'".to_string(),
+                    }),
+                },
+                "geolocation" => match (
+                    value.get("latitude").and_then(|v| v.as_f64()),
+                    value.get("longitude").and_then(|v| v.as_f64()),
+                    value.get("accuracy").and_then(|v| v.as_f64()),
+                ) {
+                    (Some(latitude), Some(longitude), Some(accuracy)) => {
+                        config.geolocation = Some(Geolocation {
+                            latitude,
+                            longitude,
+                            accuracy,
+                        });
+                    }
+                    _ => failures.push(UnsupportedCapability {
+                        capability: key.clone(),
+                        reason:
+                            "expected an object with numeric 'latitude', 'longitude' and 'accuracy'"
+                                .to_string(),
+                    }),
+                },
+                "proxy" => match (
+                    value.get("proxyType").and_then(|v| v.as_str()),
+                    value.get("httpProxy").and_then(|v| v.as_str()),
+                ) {
+                    (Some(proxy_type), Some(host_port)) => {
+                        match parse_proxy_capability(proxy_type, host_port) {
+                            Ok(proxy) => config.proxy = Some(proxy),
+                            Err(reason) => failures.push(UnsupportedCapability {
+                                capability: key.clone(),
+                                reason,
+                            }),
+                        }
+                    }
+                    _ => failures.push(UnsupportedCapability {
+                        capability: key.clone(),
+                        reason: "expected an object with 'proxyType' and 'httpProxy'".to_string(),
+                    }),
+                },
+                _ => unreachable!("key was checked against SUPPORTED_CAPABILITY_KEYS above"),
+            }
+        }
+
+        if failures.is_empty() {
+            Ok(config)
+        } else {
+            Err(CapabilitiesError { failures })
+        }
+    }
+}
+
+/// Parse a WebDriver `proxy.proxyType` / `proxy.httpProxy` pair into [`ProxySettings`].
+fn parse_proxy_capability(proxy_type: &str, host_port: &str) -> Result<ProxySettings, String> {
+    use crate::proxy::ProxyType;
+
+    let kind = match proxy_type {
+        "manual" | "http" => ProxyType::Http,
+        "https" => ProxyType::Https,
+        "socks5" | "socks" => ProxyType::Socks5,
+        other => return Err(format!("unsupported proxyType '{other}'")),
+    };
+
+    let (host, port) = host_port
+        .rsplit_once(':')
+        .ok_or_else(|| format!("httpProxy '{host_port}' must be in 'host:port' form"))?;
+    let port: u16 = port
+        .parse()
+        .map_err(|_| format!("httpProxy '{host_port}' has a non-numeric port"))?;
+
+    Ok(ProxySettings {
+        proxy_type: kind,
+        host: Some(host.to_string()),
+        port: Some(port),
+        username: None,
+        password: None,
+        dns_servers: Vec::new(),
+        bypass_list: Vec::new(),
+    })
+}
+
+/// What this engine build is capable of, reported back through
+/// [`ChromiumEngine::effective_capabilities`] so a remote session description can
+/// tell what it's negotiating with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EngineCapabilities {
+    pub per_tab_proxy: bool,
+    pub webrtc_protection: bool,
+    pub stealth_mode: bool,
+    pub dns_over_https: bool,
+    pub custom_user_agent: bool,
+    pub javascript_injection: bool,
+    pub network_interception: bool,
+    /// Whether this engine can isolate cookies per [`CookieIsolationMode`], including
+    /// [`CookieIsolationMode::Partitioned`]'s CHIPS-style per-top-level-site keying and
+    /// heuristic third-party grace grants (see [`crate::chromium_cookies`]).
+    pub cookie_management: bool,
+    pub extensions: bool,
+}
+
+impl Default for EngineCapabilities {
+    fn default() -> Self {
+        Self {
+            per_tab_proxy: true,
+            webrtc_protection: true,
+            stealth_mode: true,
+            dns_over_https: true,
+            custom_user_agent: true,
+            javascript_injection: true,
+            network_interception: true,
+            cookie_management: true,
+            extensions: true,
+        }
+    }
+}
+
+impl EngineCapabilities {
+    /// Whether this engine build can load unpacked extensions via
+    /// [`ChromiumEngineConfig::extensions`].
+    pub fn supports_extensions(&self) -> bool {
+        self.extensions
+    }
+
+    /// Whether this engine can key cookies by top-level site via
+    /// [`CookieIsolationMode::Partitioned`].
+    pub fn supports_partitioned_cookies(&self) -> bool {
+        self.cookie_management
+    }
+}
+
+/// A single extension [`ChromiumEngine::list_loaded_extensions`] found loaded,
+/// whether enabled outright or filtered out by `disable_extensions_except`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoadedExtension {
+    /// Chrome's unpacked-extension id: the first 16 bytes of the SHA-256 hash of the
+    /// extension's canonicalized path, with each nibble mapped to `a`-`p`.
+    pub id: String,
+    /// The extension's `manifest.json` `name` field, or its directory name if the
+    /// manifest is missing or unreadable.
+    pub name: String,
+    /// `false` when `disable_extensions_except` is non-empty and doesn't include this
+    /// extension's path.
+    pub enabled: bool,
+}
+
+/// Per-tab counters and blocked-origin list, tracked from CDP `Network`/`Storage`
+/// events so a privacy panel can show exactly what a site did: cookies set or
+/// blocked, local storage touched, geolocation prompted for, and third-party
+/// requests seen.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContentSettings {
+    pub cookies_set: u32,
+    pub cookies_blocked: u32,
+    pub local_storage_accesses: u32,
+    pub geolocation_prompts: u32,
+    pub third_party_requests: u32,
+    /// Origins a cookie, storage access, or request was blocked for, de-duplicated.
+    pub blocked_origins: Vec<String>,
+}
+
+/// Append `origin` to `settings.blocked_origins` unless it's already recorded.
+fn push_blocked_origin(settings: &mut ContentSettings, origin: &str) {
+    if !settings.blocked_origins.iter().any(|o| o == origin) {
+        settings.blocked_origins.push(origin.to_string());
+    }
+}
+
+/// A single open tab within a [`ChromiumEngine`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChromiumTab {
+    pub id: String,
+    pub url: String,
+    pub title: String,
+    pub proxy: Option<ProxySettings>,
+    pub is_loading: bool,
+    pub can_go_back: bool,
+    pub can_go_forward: bool,
+    /// CDP browser context (`Target.createBrowserContext`) this tab is hosted in.
+    /// `None` means the tab shares the engine's default context; tabs given a
+    /// dedicated proxy via [`ChromiumEngine::set_tab_proxy`] get one of their own, so
+    /// the proxy only applies to that tab's requests.
+    pub browser_context_id: Option<String>,
+    /// Resource access/blocking counters for this tab, updated via
+    /// [`ChromiumEngine::record_cookie_event`] and friends.
+    pub content_settings: ContentSettings,
+}
+
+/// A CDP event observed on a single tab, as delivered by [`ChromiumEngine::subscribe_events`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TabEvent {
+    /// `Runtime.consoleAPICalled`: a `console.*` call made by page script.
+    ConsoleMessage {
+        /// e.g. `"log"`, `"warn"`, `"error"`.
+        level: String,
+        /// Each argument's serialized `RemoteObject` description/value.
+        args: Vec<String>,
+    },
+    /// `Runtime.exceptionThrown`: an uncaught exception in page script.
+    ExceptionThrown {
+        message: String,
+        stack: Option<String>,
+    },
+    /// `Network.responseReceived`: a response came back for a request the page made.
+    NetworkResponse {
+        url: String,
+        status: u16,
+        mime_type: String,
+        headers: HashMap<String, String>,
+        body: Vec<u8>,
+    },
+}
+
+/// Kind of error raised by [`ChromiumEngine`] operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChromiumErrorKind {
+    NotRunning,
+    TabNotFound,
+    /// No `executable_path` was configured, no system Chrome/Chromium was detected,
+    /// and either `auto_fetch` was off or the fetch itself failed.
+    ExecutableNotFound,
+    /// The Chromium process could not be spawned at all.
+    LaunchFailed,
+    /// No free port was found in `config.devtools_port_range`.
+    NoAvailablePorts,
+    /// A fixed debugging port was already held by another process (mapped from
+    /// [`DevToolsErrorKind::DebugPortInUse`]). `launch` scans `config.devtools_port_range`
+    /// without a fixed port, so it reports a taken range as `NoAvailablePorts` instead.
+    DebugPortInUse,
+    /// The child process's DevTools banner never appeared within `devtools_timeout_secs`.
+    PortOpenTimeout,
+    /// `config.extensions` was set alongside a flag extensions can't coexist with,
+    /// e.g. `--incognito` in `extra_args`.
+    IncompatibleExtensionConfig,
+    /// `config.user_data_dir` already has a `SingletonLock`/`Lockfile` held by another
+    /// live Chrome process.
+    ProfileLocked,
+    /// A [`RequestFilter`] returned [`FilterAction::Block`] for a navigation.
+    RequestBlocked,
+}
+
+impl From<DevToolsErrorKind> for ChromiumErrorKind {
+    fn from(kind: DevToolsErrorKind) -> Self {
+        match kind {
+            DevToolsErrorKind::NoAvailablePorts => ChromiumErrorKind::NoAvailablePorts,
+            DevToolsErrorKind::DebugPortInUse => ChromiumErrorKind::DebugPortInUse,
+            DevToolsErrorKind::PortOpenTimeout => ChromiumErrorKind::PortOpenTimeout,
+            DevToolsErrorKind::SpawnFailed => ChromiumErrorKind::LaunchFailed,
+        }
+    }
+}
+
+/// Error returned by the Chromium engine module.
+#[derive(Debug, Clone)]
+pub struct ChromiumError {
+    pub kind: ChromiumErrorKind,
+    pub message: String,
+}
+
+impl ChromiumError {
+    pub fn new(kind: ChromiumErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ChromiumError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}: {}", self.kind, self.message)
+    }
+}
+
+impl std::error::Error for ChromiumError {}
+
+/// An integrated Chromium browser engine instance.
+///
+/// Tab state lives behind internal locks so read operations (`get_tabs`,
+/// `is_running`, ...) work through a shared `&ChromiumEngine`, e.g. an `Arc` handed
+/// to multiple tasks; only swapping the whole [`ChromiumEngineConfig`] requires
+/// exclusive access.
+pub struct ChromiumEngine {
+    config: ChromiumEngineConfig,
+    running: RwLock<bool>,
+    tabs: RwLock<HashMap<String, ChromiumTab>>,
+    active_tab: RwLock<Option<String>>,
+    /// The executable path [`ChromiumEngine::launch`] resolved, whether from
+    /// `config.executable_path`, system detection, or the fetcher.
+    resolved_executable: RwLock<Option<PathBuf>>,
+    /// The spawned Chromium process, once [`ChromiumEngine::launch`] has succeeded.
+    child_process: RwLock<Option<Child>>,
+    /// The DevTools CDP endpoint parsed from the child's stderr banner.
+    devtools_url: RwLock<Option<Url>>,
+    /// The ephemeral `user_data_dir` this instance created for `config.ephemeral_profile`,
+    /// if any, so `shutdown`/[`Drop`] can remove it again.
+    owned_profile_dir: RwLock<Option<PathBuf>>,
+    /// Per-tab [`TabEvent`] subscribers registered via [`ChromiumEngine::subscribe_events`].
+    tab_event_subscribers: RwLock<HashMap<String, mpsc::Sender<TabEvent>>>,
+    /// Per-tab [`RequestFilter`]s registered via [`ChromiumEngine::set_tab_request_filter`].
+    tab_request_filters: RwLock<HashMap<String, Arc<dyn RequestFilter>>>,
+    /// The fallback [`RequestFilter`] set via [`ChromiumEngine::set_request_filter`], used
+    /// by any tab without its own filter.
+    engine_request_filter: RwLock<Option<Arc<dyn RequestFilter>>>,
+    /// Active [`CookieIsolationMode::Partitioned`] grace grants, sized from
+    /// `config.third_party_grace_window_secs`/`config.max_active_cookie_grants`.
+    cookie_grants: crate::chromium_cookies::ThirdPartyCookieGrants,
+}
+
+impl ChromiumEngine {
+    /// Create a new, not-yet-launched engine with the given configuration.
+    pub fn new(config: ChromiumEngineConfig) -> Self {
+        let cookie_grants = crate::chromium_cookies::ThirdPartyCookieGrants::new(
+            config.third_party_grace_window_secs,
+            config.max_active_cookie_grants,
+        );
+        Self {
+            config,
+            running: RwLock::new(false),
+            tabs: RwLock::new(HashMap::new()),
+            active_tab: RwLock::new(None),
+            resolved_executable: RwLock::new(None),
+            child_process: RwLock::new(None),
+            devtools_url: RwLock::new(None),
+            owned_profile_dir: RwLock::new(None),
+            tab_event_subscribers: RwLock::new(HashMap::new()),
+            tab_request_filters: RwLock::new(HashMap::new()),
+            engine_request_filter: RwLock::new(None),
+            cookie_grants,
+        }
+    }
+
+    /// Find the Chromium executable to launch: an explicit `config.executable_path`
+    /// override, then a system-installed Chrome/Chromium, then (if `config.auto_fetch`
+    /// is set) a downloaded pinned build.
+    pub async fn resolve_executable(&self) -> Result<PathBuf, ChromiumError> {
+        if let Some(path) = &self.config.executable_path {
+            return Ok(path.clone());
+        }
+
+        let locator = if self.config.auto_fetch {
+            chromium_detect::ChromeLocator::with_fetcher(self.config.fetcher.clone())
+        } else {
+            chromium_detect::ChromeLocator::system_only()
+        };
+
+        locator
+            .locate()
+            .await
+            .map_err(|e| ChromiumError::new(ChromiumErrorKind::ExecutableNotFound, e.to_string()))
+    }
+
+    /// Negotiate `capabilities` into a config and construct an engine from it, so a
+    /// remote session description can drive `ChromiumEngine::new` without the caller
+    /// hand-building a [`ChromiumEngineConfig`].
+    pub fn from_capabilities(capabilities: &serde_json::Value) -> Result<Self, CapabilitiesError> {
+        ChromiumEngineConfig::from_capabilities(capabilities).map(Self::new)
+    }
+
+    /// Launch the underlying browser process.
+    ///
+    /// Resolves the executable to run via [`ChromiumEngine::resolve_executable`], then
+    /// spawns it and negotiates its DevTools endpoint via [`chromium_devtools::launch_with_devtools`].
+    pub async fn launch(&mut self) -> Result<(), ChromiumError> {
+        self.prepare_profile_dir().await?;
+        self.ensure_extension_compatible()?;
+        self.config.sanitize_extra_chrome_flags();
+        let executable = self.resolve_executable().await?;
+
+        let discovery_options = DevToolsDiscoveryOptions {
+            fixed_port: None,
+            port_range: self
+                .config
+                .devtools_port_range
+                .map(|(start, end)| start..=end),
+            spawn_timeout: Duration::from_secs(self.config.devtools_timeout_secs),
+        };
+        let args = self.config.build_launch_args();
+
+        let launched =
+            chromium_devtools::launch_with_devtools(&executable, &args, &discovery_options)
+                .await
+                .map_err(|e| ChromiumError::new(e.kind.into(), e.message))?;
+
+        *self.resolved_executable.write().await = Some(executable);
+        *self.child_process.write().await = Some(launched.child);
+        *self.devtools_url.write().await = Some(launched.devtools_url);
+        *self.running.write().await = true;
+        Ok(())
+    }
+
+    /// The executable path resolved by the most recent [`ChromiumEngine::launch`].
+    pub async fn resolved_executable(&self) -> Option<PathBuf> {
+        self.resolved_executable.read().await.clone()
+    }
+
+    /// The DevTools CDP endpoint discovered by the most recent [`ChromiumEngine::launch`].
+    pub async fn devtools_url(&self) -> Option<Url> {
+        self.devtools_url.read().await.clone()
+    }
+
+    /// Shut the engine down, closing all tabs. Deletes the ephemeral profile directory
+    /// created by [`ChromiumEngine::launch`], if any. Safe to call repeatedly.
+    pub async fn shutdown(&mut self) -> Result<(), ChromiumError> {
+        if let Some(mut child) = self.child_process.write().await.take() {
+            let _ = child.kill().await;
+        }
+        *self.running.write().await = false;
+        self.tabs.write().await.clear();
+        self.tab_event_subscribers.write().await.clear();
+        self.tab_request_filters.write().await.clear();
+        *self.active_tab.write().await = None;
+        *self.resolved_executable.write().await = None;
+        *self.devtools_url.write().await = None;
+        if let Some(dir) = self.owned_profile_dir.write().await.take() {
+            let _ = tokio::fs::remove_dir_all(&dir).await;
+        }
+        Ok(())
+    }
+
+    /// When `config.ephemeral_profile` is set, point `user_data_dir` at a fresh temp
+    /// directory owned by this instance (overriding any path already configured) so
+    /// `shutdown`/[`Drop`] can clean it up. Otherwise, if an explicit `user_data_dir`
+    /// is configured, refuse to launch into one a live Chrome process already holds a
+    /// `SingletonLock`/`Lockfile` on.
+    async fn prepare_profile_dir(&mut self) -> Result<(), ChromiumError> {
+        if self.config.ephemeral_profile {
+            let dir = std::env::temp_dir().join(format!("browser-core-profile-{}", Uuid::new_v4()));
+            tokio::fs::create_dir_all(&dir).await.map_err(|e| {
+                ChromiumError::new(
+                    ChromiumErrorKind::LaunchFailed,
+                    format!(
+                        "failed to create ephemeral profile dir '{}': {e}",
+                        dir.display()
+                    ),
+                )
+            })?;
+            self.config.user_data_dir = Some(dir.clone());
+            *self.owned_profile_dir.write().await = Some(dir);
+            return Ok(());
+        }
+
+        if let Some(dir) = &self.config.user_data_dir {
+            if dir.join("SingletonLock").exists() || dir.join("Lockfile").exists() {
+                return Err(ChromiumError::new(
+                    ChromiumErrorKind::ProfileLocked,
+                    format!("user_data_dir '{}' is locked by another Chrome process (SingletonLock/Lockfile present)", dir.display()),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reject extension configurations Chromium can't actually honor, and provision a
+    /// persistent `user_data_dir` if `config.extensions` is set but none was given:
+    /// extensions require a non-incognito profile to install into.
+    fn ensure_extension_compatible(&mut self) -> Result<(), ChromiumError> {
+        if self.config.extensions.is_empty() {
+            return Ok(());
+        }
+
+        if self
+            .config
+            .extra_args
+            .iter()
+            .any(|arg| arg == "--incognito")
+        {
+            return Err(ChromiumError::new(
+                ChromiumErrorKind::IncompatibleExtensionConfig,
+                "extensions cannot be loaded in incognito mode; remove --incognito from extra_args or clear config.extensions",
+            ));
+        }
+
+        if self.config.user_data_dir.is_none() {
+            let dir =
+                std::env::temp_dir().join(format!("browser-core-extensions-{}", Uuid::new_v4()));
+            self.config.user_data_dir = Some(dir);
+        }
+
+        Ok(())
+    }
+
+    /// Report each configured extension's id, name and enabled state, so automation
+    /// flows can verify that ad-blocking or content extensions actually loaded.
+    ///
+    /// Reads each extension's `manifest.json` off disk rather than querying the live
+    /// browser over CDP; the id is Chrome's own unpacked-extension id derivation, so it
+    /// matches what `chrome://extensions` would show.
+    pub async fn list_loaded_extensions(&self) -> Result<Vec<LoadedExtension>, ChromiumError> {
+        if !self.is_running().await {
+            return Err(ChromiumError::new(
+                ChromiumErrorKind::NotRunning,
+                "cannot list loaded extensions before the engine is launched",
+            ));
+        }
+
+        Ok(self
+            .config
+            .extensions
+            .iter()
+            .map(|path| LoadedExtension {
+                id: unpacked_extension_id(path),
+                name: extension_manifest_name(path),
+                enabled: self.config.disable_extensions_except.is_empty()
+                    || self.config.disable_extensions_except.contains(path),
+            })
+            .collect())
+    }
+
+    /// Whether the underlying browser process is currently running. Reflects the real
+    /// child process's liveness when one was spawned by [`ChromiumEngine::launch`].
+    pub async fn is_running(&self) -> bool {
+        let mut child_guard = self.child_process.write().await;
+        if let Some(child) = child_guard.as_mut() {
+            match child.try_wait() {
+                Ok(None) => true,
+                _ => {
+                    *child_guard = None;
+                    false
+                }
+            }
+        } else {
+            *self.running.read().await
+        }
+    }
+
+    /// Current engine configuration.
+    pub fn get_config(&self) -> ChromiumEngineConfig {
+        self.config.clone()
+    }
+
+    /// Replace the engine configuration. Takes effect on the next `launch`/tab creation.
+    pub fn set_config(&mut self, config: ChromiumEngineConfig) {
+        self.config = config;
+    }
+
+    /// Report this engine's config and this build's capabilities as a WebDriver-style
+    /// JSON object. It shares key names with [`ChromiumEngineConfig::from_capabilities`]
+    /// where they overlap but is not accepted back by it: keys such as `dnsOverHttps`
+    /// are not ones it supports, and `proxy` here is a capability flag, not a proxy.
+    pub fn effective_capabilities(&self) -> serde_json::Value {
+        let caps = EngineCapabilities::default();
+        serde_json::json!({
+            "browserName": "chromium",
+            "headless": self.config.headless,
+            "userAgent": self.config.user_agent,
+            "viewport": {
+                "width": self.config.viewport_width,
+                "height": self.config.viewport_height,
+            },
+            "proxy": caps.per_tab_proxy,
+            "webrtcProtection": caps.webrtc_protection,
+            "stealthMode": caps.stealth_mode,
+            "dnsOverHttps": caps.dns_over_https,
+            "javascriptInjection": caps.javascript_injection,
+            "networkInterception": caps.network_interception,
+            "cookieManagement": caps.cookie_management,
+        })
+    }
+
+    /// All currently open tabs.
+    pub async fn get_tabs(&self) -> Vec<ChromiumTab> {
+        self.tabs.read().await.values().cloned().collect()
+    }
+
+    /// The currently active tab, if any.
+    pub async fn get_active_tab(&self) -> Option<ChromiumTab> {
+        let active_id = self.active_tab.read().await.clone()?;
+        self.tabs.read().await.get(&active_id).cloned()
+    }
+
+    /// Open a new tab, optionally navigating to `url` and assigning it a dedicated proxy.
+    pub async fn create_tab(
+        &self,
+        url: Option<&str>,
+        proxy: Option<ProxySettings>,
+    ) -> Result<ChromiumTab, ChromiumError> {
+        if !self.is_running().await {
+            return Err(ChromiumError::new(
+                ChromiumErrorKind::NotRunning,
+                "cannot create a tab before the engine is launched",
+            ));
+        }
+
+        let browser_context_id = proxy
+            .as_ref()
+            .map(|_| format!("context-{}", Uuid::new_v4()));
+        let tab = ChromiumTab {
+            id: format!("tab-{}", Uuid::new_v4()),
+            url: url.unwrap_or("about:blank").to_string(),
+            title: String::new(),
+            proxy,
+            is_loading: true,
+            can_go_back: false,
+            can_go_forward: false,
+            browser_context_id,
+            content_settings: ContentSettings::default(),
+        };
+
+        let mut tabs = self.tabs.write().await;
+        tabs.insert(tab.id.clone(), tab.clone());
+        drop(tabs);
+
+        let mut active_tab = self.active_tab.write().await;
+        if active_tab.is_none() {
+            *active_tab = Some(tab.id.clone());
+        }
+
+        Ok(tab)
+    }
+
+    /// Close a tab. Idempotent: closing an already-closed or unknown tab succeeds.
+    pub async fn close_tab(&self, tab_id: &str) -> Result<(), ChromiumError> {
+        self.tabs.write().await.remove(tab_id);
+        self.tab_event_subscribers.write().await.remove(tab_id);
+        self.tab_request_filters.write().await.remove(tab_id);
+
+        let mut active_tab = self.active_tab.write().await;
+        if active_tab.as_deref() == Some(tab_id) {
+            *active_tab = None;
+        }
+
+        Ok(())
+    }
+
+    /// Subscribe to [`TabEvent`]s observed on `tab_id`: console messages, uncaught
+    /// exceptions, and network responses. Replaces any previous subscriber for this tab.
+    /// The returned channel is closed (further `recv` calls return `None`) once the tab
+    /// is closed or the engine shuts down.
+    pub async fn subscribe_events(
+        &self,
+        tab_id: &str,
+    ) -> Result<mpsc::Receiver<TabEvent>, ChromiumError> {
+        if !self.tabs.read().await.contains_key(tab_id) {
+            return Err(ChromiumError::new(
+                ChromiumErrorKind::TabNotFound,
+                format!("no tab with id '{tab_id}'"),
+            ));
+        }
+
+        let (tx, rx) = mpsc::channel(TAB_EVENT_CHANNEL_CAPACITY);
+        self.tab_event_subscribers
+            .write()
+            .await
+            .insert(tab_id.to_string(), tx);
+        Ok(rx)
+    }
+
+    /// Deliver `event` to `tab_id`'s subscriber, if any. Best-effort: a full or dropped
+    /// channel silently drops the event rather than blocking the caller.
+    async fn emit_tab_event(&self, tab_id: &str, event: TabEvent) {
+        if let Some(tx) = self.tab_event_subscribers.read().await.get(tab_id) {
+            let _ = tx.try_send(event);
+        }
+    }
+
+    /// Attach a [`RequestFilter`] to every tab that doesn't have its own filter set via
+    /// [`ChromiumEngine::set_tab_request_filter`].
+    pub async fn set_request_filter(&self, filter: Arc<dyn RequestFilter>) {
+        *self.engine_request_filter.write().await = Some(filter);
+    }
+
+    /// Remove the engine-wide [`RequestFilter`] set by [`ChromiumEngine::set_request_filter`].
+    pub async fn clear_request_filter(&self) {
+        *self.engine_request_filter.write().await = None;
+    }
+
+    /// Attach a [`RequestFilter`] to a single tab, overriding the engine-wide filter
+    /// (if any) for traffic on that tab.
+    pub async fn set_tab_request_filter(
+        &self,
+        tab_id: &str,
+        filter: Arc<dyn RequestFilter>,
+    ) -> Result<(), ChromiumError> {
+        if !self.tabs.read().await.contains_key(tab_id) {
+            return Err(ChromiumError::new(
+                ChromiumErrorKind::TabNotFound,
+                format!("no tab with id '{tab_id}'"),
+            ));
+        }
+
+        self.tab_request_filters
+            .write()
+            .await
+            .insert(tab_id.to_string(), filter);
+        Ok(())
+    }
+
+    /// The filter that applies to `tab_id`'s traffic: its own filter if one was set via
+    /// [`ChromiumEngine::set_tab_request_filter`], otherwise the engine-wide filter.
+    async fn request_filter_for(&self, tab_id: &str) -> Option<Arc<dyn RequestFilter>> {
+        if let Some(filter) = self.tab_request_filters.read().await.get(tab_id) {
+            return Some(filter.clone());
+        }
+        self.engine_request_filter.read().await.clone()
+    }
+
+    /// Record a qualifying first-party interaction (e.g. completing a popup/redirect
+    /// auth flow) with `top_level_site`, earning `third_party_site` a grace grant for
+    /// unpartitioned cookies there. Only meaningful under
+    /// [`CookieIsolationMode::Partitioned`]; harmless to call otherwise.
+    pub async fn grant_third_party_cookie_access(
+        &self,
+        top_level_site: &str,
+        third_party_site: &str,
+    ) {
+        self.cookie_grants
+            .grant(top_level_site, third_party_site)
+            .await;
+    }
+
+    /// Whether a cookie belonging to `third_party_site`, embedded under
+    /// `top_level_site`, should be sent/stored right now, per `config.cookie_isolation`,
+    /// `config.cookie_partition_allowlist` and any active grace grant.
+    pub async fn is_third_party_cookie_allowed(
+        &self,
+        top_level_site: &str,
+        third_party_site: &str,
+    ) -> bool {
+        crate::chromium_cookies::third_party_cookie_allowed(
+            self.config.cookie_isolation,
+            &self.config.cookie_partition_allowlist,
+            &self.cookie_grants,
+            top_level_site,
+            third_party_site,
+        )
+        .await
+    }
+
+    /// How many [`CookieIsolationMode::Partitioned`] grace grants are currently active.
+    pub async fn active_cookie_grant_count(&self) -> usize {
+        self.cookie_grants.active_grant_count().await
+    }
+
+    /// Apply `f` to `tab_id`'s [`ContentSettings`], the shared bookkeeping entry point
+    /// for every `record_*` method below.
+    async fn mutate_content_settings(
+        &self,
+        tab_id: &str,
+        f: impl FnOnce(&mut ContentSettings),
+    ) -> Result<(), ChromiumError> {
+        let mut tabs = self.tabs.write().await;
+        let tab = tabs.get_mut(tab_id).ok_or_else(|| {
+            ChromiumError::new(
+                ChromiumErrorKind::TabNotFound,
+                format!("no tab with id '{tab_id}'"),
+            )
+        })?;
+        f(&mut tab.content_settings);
+        Ok(())
+    }
+
+    /// Record that CDP `Network.setCookie` (or an equivalent document-set cookie) went
+    /// through for `tab_id`.
+    pub async fn record_cookie_set(&self, tab_id: &str) -> Result<(), ChromiumError> {
+        self.mutate_content_settings(tab_id, |settings| settings.cookies_set += 1)
+            .await
+    }
+
+    /// Record that a cookie belonging to `origin` was withheld from `tab_id`, e.g. by
+    /// [`ChromiumEngine::is_third_party_cookie_allowed`] returning `false`.
+    pub async fn record_cookie_blocked(
+        &self,
+        tab_id: &str,
+        origin: &str,
+    ) -> Result<(), ChromiumError> {
+        self.mutate_content_settings(tab_id, |settings| {
+            settings.cookies_blocked += 1;
+            push_blocked_origin(settings, origin);
+        })
+        .await
+    }
+
+    /// Record a `Storage.getLocalStorage`/`localStorage` read or write observed on
+    /// `tab_id`.
+    pub async fn record_local_storage_access(&self, tab_id: &str) -> Result<(), ChromiumError> {
+        self.mutate_content_settings(tab_id, |settings| settings.local_storage_accesses += 1)
+            .await
+    }
+
+    /// Record a `Geolocation.getCurrentPosition`-style permission prompt surfaced to
+    /// `tab_id`.
+    pub async fn record_geolocation_prompt(&self, tab_id: &str) -> Result<(), ChromiumError> {
+        self.mutate_content_settings(tab_id, |settings| settings.geolocation_prompts += 1)
+            .await
+    }
+
+    /// Record a cross-origin request `tab_id` made to `origin`. `blocked` additionally
+    /// appends `origin` to [`ContentSettings::blocked_origins`].
+    pub async fn record_third_party_request(
+        &self,
+        tab_id: &str,
+        origin: &str,
+        blocked: bool,
+    ) -> Result<(), ChromiumError> {
+        self.mutate_content_settings(tab_id, |settings| {
+            settings.third_party_requests += 1;
+            if blocked {
+                push_blocked_origin(settings, origin);
+            }
+        })
+        .await
+    }
+
+    /// `tab_id`'s current [`ContentSettings`] snapshot.
+    pub async fn get_tab_content_settings(
+        &self,
+        tab_id: &str,
+    ) -> Result<ContentSettings, ChromiumError> {
+        self.tabs
+            .read()
+            .await
+            .get(tab_id)
+            .map(|tab| tab.content_settings.clone())
+            .ok_or_else(|| {
+                ChromiumError::new(
+                    ChromiumErrorKind::TabNotFound,
+                    format!("no tab with id '{tab_id}'"),
+                )
+            })
+    }
+
+    /// Purge `tab_id`'s current-origin cookies and storage and reset its
+    /// [`ContentSettings`] counters. The actual cookie/storage purge is deferred to a
+    /// `StorageEngine`, which doesn't exist in this tree yet; for now this only resets
+    /// the per-tab bookkeeping, leaving the underlying data untouched.
+    pub async fn clear_tab_site_data(&self, tab_id: &str) -> Result<(), ChromiumError> {
+        self.mutate_content_settings(tab_id, |settings| *settings = ContentSettings::default())
+            .await
+    }
+
+    /// Make `tab_id` the active tab.
+    pub async fn set_active_tab(&self, tab_id: &str) -> Result<(), ChromiumError> {
+        if !self.tabs.read().await.contains_key(tab_id) {
+            return Err(ChromiumError::new(
+                ChromiumErrorKind::TabNotFound,
+                format!("no tab with id '{tab_id}'"),
+            ));
+        }
+
+        *self.active_tab.write().await = Some(tab_id.to_string());
+        Ok(())
+    }
+
+    /// Navigate `tab_id` to `url`.
+    pub async fn navigate(&self, tab_id: &str, url: &str) -> Result<(), ChromiumError> {
+        if !self.is_running().await {
+            return Err(ChromiumError::new(
+                ChromiumErrorKind::NotRunning,
+                "cannot navigate before the engine is launched",
+            ));
+        }
+
+        if !self.tabs.read().await.contains_key(tab_id) {
+            return Err(ChromiumError::new(
+                ChromiumErrorKind::TabNotFound,
+                format!("no tab with id '{tab_id}'"),
+            ));
+        }
+
+        let request_id = format!("req-{}", Uuid::new_v4());
+        let mut status = 200u16;
+        let mime_type = "text/html".to_string();
+        let mut headers = HashMap::new();
+        let mut body = Vec::new();
+
+        if let Some(filter) = self.request_filter_for(tab_id).await {
+            let request = InterceptedRequest {
+                request_id: request_id.clone(),
+                url: url.to_string(),
+                method: "GET".to_string(),
+                headers: HashMap::new(),
+                body: None,
+            };
+
+            match filter.on_request(request).await {
+                FilterAction::Continue => {}
+                FilterAction::Block => {
+                    let _ = self
+                        .mutate_content_settings(tab_id, |settings| {
+                            push_blocked_origin(settings, url)
+                        })
+                        .await;
+                    return Err(ChromiumError::new(
+                        ChromiumErrorKind::RequestBlocked,
+                        format!("navigation to '{url}' was blocked by the tab's request filter"),
+                    ));
+                }
+                FilterAction::ModifyHeaders(modified) => headers = modified,
+                FilterAction::FulfillWith {
+                    status: fulfilled_status,
+                    headers: fulfilled_headers,
+                    body: fulfilled_body,
+                } => {
+                    status = fulfilled_status;
+                    headers = fulfilled_headers;
+                    body = fulfilled_body;
+                }
+            }
+
+            let response = InterceptedResponse {
+                request_id,
+                url: url.to_string(),
+                status,
+                headers: headers.clone(),
+            };
+            body = filter.on_response_body(response, body).await;
+        }
+
+        let mut tabs = self.tabs.write().await;
+        let tab = tabs.get_mut(tab_id).ok_or_else(|| {
+            ChromiumError::new(
+                ChromiumErrorKind::TabNotFound,
+                format!("no tab with id '{tab_id}'"),
+            )
+        })?;
+
+        tab.can_go_back = !tab.url.is_empty() && tab.url != url;
+        tab.can_go_forward = false;
+        tab.url = url.to_string();
+        tab.is_loading = true;
+        drop(tabs);
+
+        self.emit_tab_event(
+            tab_id,
+            TabEvent::NetworkResponse {
+                url: url.to_string(),
+                status,
+                mime_type,
+                headers,
+                body,
+            },
+        )
+        .await;
+
+        Ok(())
+    }
+
+    /// Assign (or clear) a dedicated proxy for `tab_id`. A non-`None` proxy tears down
+    /// any CDP browser context this tab previously held and creates a fresh one scoped
+    /// to the new `--proxy-server`, rather than reusing the engine's default context --
+    /// otherwise the old proxy (or no proxy at all) would keep applying to in-flight
+    /// requests the tab's context had already queued. Clearing the proxy (`None`)
+    /// drops the tab back onto the engine's default, unproxied context.
+    pub async fn set_tab_proxy(
+        &self,
+        tab_id: &str,
+        proxy: Option<ProxySettings>,
+    ) -> Result<(), ChromiumError> {
+        let mut tabs = self.tabs.write().await;
+        let tab = tabs.get_mut(tab_id).ok_or_else(|| {
+            ChromiumError::new(
+                ChromiumErrorKind::TabNotFound,
+                format!("no tab with id '{tab_id}'"),
+            )
+        })?;
+        tab.browser_context_id = proxy
+            .as_ref()
+            .map(|_| format!("context-{}", Uuid::new_v4()));
+        tab.proxy = proxy;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl crate::browser_engine::BrowserEngine for ChromiumEngine {
+    async fn launch(&mut self) -> Result<(), crate::browser_engine::EngineError> {
+        ChromiumEngine::launch(self).await.map_err(Into::into)
+    }
+
+    async fn shutdown(&mut self) -> Result<(), crate::browser_engine::EngineError> {
+        ChromiumEngine::shutdown(self).await.map_err(Into::into)
+    }
+
+    async fn is_running(&self) -> bool {
+        ChromiumEngine::is_running(self).await
+    }
+
+    async fn create_tab(
+        &self,
+        url: Option<&str>,
+        proxy: Option<ProxySettings>,
+    ) -> Result<ChromiumTab, crate::browser_engine::EngineError> {
+        ChromiumEngine::create_tab(self, url, proxy)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn navigate(
+        &self,
+        tab_id: &str,
+        url: &str,
+    ) -> Result<(), crate::browser_engine::EngineError> {
+        ChromiumEngine::navigate(self, tab_id, url)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn close_tab(&self, tab_id: &str) -> Result<(), crate::browser_engine::EngineError> {
+        ChromiumEngine::close_tab(self, tab_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn set_active_tab(&self, tab_id: &str) -> Result<(), crate::browser_engine::EngineError> {
+        ChromiumEngine::set_active_tab(self, tab_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_tabs(&self) -> Vec<ChromiumTab> {
+        ChromiumEngine::get_tabs(self).await
+    }
+
+    async fn set_tab_proxy(
+        &self,
+        tab_id: &str,
+        proxy: Option<ProxySettings>,
+    ) -> Result<(), crate::browser_engine::EngineError> {
+        ChromiumEngine::set_tab_proxy(self, tab_id, proxy)
+            .await
+            .map_err(Into::into)
+    }
+
+    fn get_config(&self) -> serde_json::Value {
+        self.effective_capabilities()
+    }
+
+    fn set_config(
+        &mut self,
+        config: &serde_json::Value,
+    ) -> Result<(), crate::browser_engine::EngineError> {
+        let config = ChromiumEngineConfig::from_capabilities(config).map_err(|e| {
+            crate::browser_engine::EngineError::new(
+                crate::browser_engine::EngineErrorKind::Unsupported,
+                e.to_string(),
+            )
+        })?;
+        ChromiumEngine::set_config(self, config);
+        Ok(())
+    }
+
+    fn capabilities(&self) -> EngineCapabilities {
+        EngineCapabilities::default()
+    }
+}
+
+impl Drop for ChromiumEngine {
+    /// Best-effort cleanup for an ephemeral profile directory if the caller dropped
+    /// the engine without awaiting [`ChromiumEngine::shutdown`] first.
+    fn drop(&mut self) {
+        if let Ok(guard) = self.owned_profile_dir.try_read() {
+            if let Some(dir) = guard.as_ref() {
+                let _ = std::fs::remove_dir_all(dir);
+            }
+        }
+    }
+}
+
+/// Switches between the system browser and an integrated Chromium engine, exposing a
+/// single interface regardless of which one is active.
+pub struct BrowserEngineManager {
+    engine_type: RwLock<BrowserEngineType>,
+    chromium_config: RwLock<ChromiumEngineConfig>,
+    chromium_engine: RwLock<Option<Arc<ChromiumEngine>>>,
+    proxy_pool: Arc<ProxyPool>,
+}
+
+impl Default for BrowserEngineManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BrowserEngineManager {
+    /// Create a new manager, defaulting to the system browser engine and an empty
+    /// round-robin [`ProxyPool`].
+    pub fn new() -> Self {
+        Self {
+            engine_type: RwLock::new(BrowserEngineType::System),
+            chromium_config: RwLock::new(ChromiumEngineConfig::default()),
+            chromium_engine: RwLock::new(None),
+            proxy_pool: Arc::new(ProxyPool::default()),
+        }
+    }
+
+    /// Which engine is currently active.
+    pub async fn get_engine_type(&self) -> BrowserEngineType {
+        *self.engine_type.read().await
+    }
+
+    /// Switch engines, tearing down or spinning up the Chromium engine as needed.
+    pub async fn set_engine_type(
+        &self,
+        engine_type: BrowserEngineType,
+    ) -> Result<(), ChromiumError> {
+        if *self.engine_type.read().await == engine_type {
+            return Ok(());
+        }
+
+        match engine_type {
+            BrowserEngineType::IntegratedChromium => {
+                let config = self.chromium_config.read().await.clone();
+                *self.chromium_engine.write().await = Some(Arc::new(ChromiumEngine::new(config)));
+            }
+            BrowserEngineType::System => {
+                *self.chromium_engine.write().await = None;
+            }
+        }
+
+        *self.engine_type.write().await = engine_type;
+        Ok(())
+    }
+
+    /// The Chromium configuration that will be used next time the Chromium engine is
+    /// activated, or is currently using if it's already active.
+    pub async fn get_config(&self) -> ChromiumEngineConfig {
+        self.chromium_config.read().await.clone()
+    }
+
+    /// Replace the Chromium configuration used for the next activation. The active
+    /// Chromium engine receives it too only when this manager holds the sole handle to
+    /// it; while a handle from [`Self::get_chromium_engine`] is alive it keeps its old
+    /// config.
+    pub async fn update_chromium_config(
+        &self,
+        config: ChromiumEngineConfig,
+    ) -> Result<(), ChromiumError> {
+        *self.chromium_config.write().await = config.clone();
+
+        if let Some(engine) = self.chromium_engine.write().await.as_mut() {
+            if let Some(engine) = Arc::get_mut(engine) {
+                engine.set_config(config);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Convenience for setting just the proxy on the Chromium configuration.
+    pub async fn set_proxy(&self, proxy: Option<ProxySettings>) -> Result<(), ChromiumError> {
+        let mut config = self.get_config().await;
+        config.proxy = proxy;
+        self.update_chromium_config(config).await
+    }
+
+    /// Whether the active engine configuration supports per-tab proxies. Always true:
+    /// the system engine delegates to the OS proxy and the Chromium engine assigns a
+    /// dedicated proxy per tab.
+    pub fn supports_per_tab_proxy(&self) -> bool {
+        true
+    }
+
+    /// The capabilities this manager's Chromium engine supports, regardless of whether
+    /// it's currently active.
+    pub fn get_capabilities(&self) -> EngineCapabilities {
+        EngineCapabilities::default()
+    }
+
+    /// A handle to the running Chromium engine, if that's the active engine type.
+    pub async fn get_chromium_engine(&self) -> Option<Arc<ChromiumEngine>> {
+        self.chromium_engine.read().await.clone()
+    }
+
+    /// Add a proxy to the end of the per-tab [`ProxyPool`].
+    pub async fn add_proxy(&self, proxy: ProxySettings) {
+        self.proxy_pool.add_proxy(proxy).await;
+    }
+
+    /// Remove every pooled proxy equal to `proxy`. Returns whether anything was removed.
+    pub async fn remove_proxy(&self, proxy: &ProxySettings) -> bool {
+        self.proxy_pool.remove_proxy(proxy).await
+    }
+
+    /// How the [`ProxyPool`] picks a proxy for each new tab.
+    pub async fn rotation_strategy(&self) -> ProxyRotationStrategy {
+        self.proxy_pool.rotation_strategy().await
+    }
+
+    /// Change how the [`ProxyPool`] picks a proxy for each new tab.
+    pub async fn set_rotation_strategy(&self, strategy: ProxyRotationStrategy) {
+        self.proxy_pool.set_rotation_strategy(strategy).await;
+    }
+
+    /// Each pooled proxy's settings and latest observed health, so the UI can show
+    /// which exit nodes are live.
+    pub async fn get_proxy_pool_status(&self) -> Vec<ProxyPoolStatus> {
+        self.proxy_pool.get_status().await
+    }
+
+    /// Weight given to history (vs. the newest probe) when scoring proxy health.
+    pub async fn proxy_health_decay(&self) -> f64 {
+        self.proxy_pool.health_decay().await
+    }
+
+    /// Change the health-score decay factor used by future probes.
+    pub async fn set_proxy_health_decay(&self, decay: f64) {
+        self.proxy_pool.set_health_decay(decay).await;
+    }
+
+    /// Remove every pooled proxy whose health score is below `min_score`. Returns the
+    /// removed proxies.
+    pub async fn prune_dead_proxies(&self, min_score: f64) -> Vec<ProxySettings> {
+        self.proxy_pool.prune_dead_proxies(min_score).await
+    }
+
+    /// Start a background loop that probes every pooled proxy's connectivity every
+    /// `interval_secs` seconds, marking failing proxies temporarily unavailable and
+    /// skipping them in [`Self::create_tab_with_pooled_proxy`] until they recover.
+    pub async fn start_proxy_pool_health_checks(&self, interval_secs: u64, probe_url: String) {
+        self.proxy_pool
+            .start_health_checks(interval_secs, probe_url)
+            .await;
+    }
+
+    /// Stop the background proxy-pool health-check loop, if running.
+    pub async fn stop_proxy_pool_health_checks(&self) {
+        self.proxy_pool.stop_health_checks().await;
+    }
+
+    /// Create a tab on the active Chromium engine, assigning it the next proxy from the
+    /// [`ProxyPool`] per the configured [`ProxyRotationStrategy`]. `domain_hint` is only
+    /// consulted by [`ProxyRotationStrategy::StickyPerDomain`]. If the pool is empty or
+    /// every entry is unavailable, the tab is created without a dedicated proxy.
+    pub async fn create_tab_with_pooled_proxy(
+        &self,
+        url: Option<&str>,
+        domain_hint: Option<&str>,
+    ) -> Result<ChromiumTab, ChromiumError> {
+        let engine = self.get_chromium_engine().await.ok_or_else(|| {
+            ChromiumError::new(
+                ChromiumErrorKind::NotRunning,
+                "cannot create a tab before the integrated Chromium engine is active",
+            )
+        })?;
+
+        let proxy = self.proxy_pool.assign(domain_hint).await;
+        engine.create_tab(url, proxy).await
+    }
+
+    /// Launch the active Chromium engine, if one is active and not already shared with
+    /// another handle. Needed before [`Self::create_tab_with_pooled_proxy`] or
+    /// [`ChromiumEngine::create_tab`] will succeed, since both require
+    /// [`ChromiumEngine::is_running`].
+    pub async fn launch_chromium_engine(&self) -> Result<(), ChromiumError> {
+        let mut guard = self.chromium_engine.write().await;
+        let engine = guard.as_mut().ok_or_else(|| {
+            ChromiumError::new(
+                ChromiumErrorKind::NotRunning,
+                "no integrated Chromium engine is active; call set_engine_type first",
+            )
+        })?;
+        let engine = Arc::get_mut(engine).ok_or_else(|| {
+            ChromiumError::new(
+                ChromiumErrorKind::LaunchFailed,
+                "engine handle is shared with another caller; cannot launch through multiple owners",
+            )
+        })?;
+        engine.launch().await
+    }
+}
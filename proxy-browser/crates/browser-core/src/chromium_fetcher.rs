@@ -0,0 +1,243 @@
+//! Chromium Fetcher
+//!
+//! Downloads a pinned Chromium snapshot build into a crate-managed cache directory
+//! when no local Chrome/Chromium binary is available, so [`crate::chromium_engine::ChromiumEngine`]
+//! can launch on CI and other clean machines without a preinstalled browser. See
+//! [`crate::chromium_engine::ChromiumEngineConfig::auto_fetch`].
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// Where Chromium's continuous-build snapshot server publishes builds for a given
+/// platform triple.
+fn snapshot_base_url(platform: &str) -> Result<&'static str> {
+    match platform {
+        "linux64" => Ok("https://storage.googleapis.com/chromium-browser-snapshots/Linux_x64"),
+        "mac64" => Ok("https://storage.googleapis.com/chromium-browser-snapshots/Mac"),
+        "win64" => Ok("https://storage.googleapis.com/chromium-browser-snapshots/Win_x64"),
+        other => Err(anyhow!("unsupported platform triple '{other}' for Chromium auto-fetch")),
+    }
+}
+
+fn archive_name(platform: &str) -> &'static str {
+    match platform {
+        "linux64" => "chrome-linux.zip",
+        "mac64" => "chrome-mac.zip",
+        "win64" => "chrome-win.zip",
+        _ => "chrome.zip",
+    }
+}
+
+fn binary_relative_path(platform: &str) -> &'static str {
+    match platform {
+        "linux64" => "chrome-linux/chrome",
+        "mac64" => "chrome-mac/Chromium.app/Contents/MacOS/Chromium",
+        "win64" => "chrome-win/chrome.exe",
+        _ => "chrome",
+    }
+}
+
+/// The native platform triple this process is running on, in the form the Chromium
+/// snapshot server expects.
+pub fn host_platform() -> &'static str {
+    if cfg!(all(target_os = "macos", target_arch = "x86_64")) {
+        "mac64"
+    } else if cfg!(target_os = "windows") {
+        "win64"
+    } else {
+        "linux64"
+    }
+}
+
+/// Controls [`fetch_chromium`]'s download/caching behavior.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FetcherOptions {
+    /// Chromium snapshot revision number to fetch, e.g. `"1250580"`.
+    pub revision: String,
+    /// Platform triple to download for; defaults to [`host_platform`].
+    pub platform: String,
+    /// Directory under which each revision gets its own extracted subfolder, e.g.
+    /// `~/.cache/browser-core/chromium/<revision>/`.
+    pub install_dir: PathBuf,
+    /// If `false`, [`fetch_chromium`] only looks for an already-extracted binary and
+    /// returns an error instead of reaching the network.
+    pub allow_download: bool,
+}
+
+impl Default for FetcherOptions {
+    fn default() -> Self {
+        Self {
+            revision: "1250580".to_string(),
+            platform: host_platform().to_string(),
+            install_dir: default_install_dir(),
+            allow_download: true,
+        }
+    }
+}
+
+fn default_install_dir() -> PathBuf {
+    let base = std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir);
+    base.join(".cache").join("browser-core").join("chromium")
+}
+
+/// Records the SHA-256 of the extracted binary at download time, so a later reuse of
+/// the same `revision_dir` can detect accidental on-disk corruption (e.g. bit rot, a
+/// truncated copy) instead of blindly trusting that the binary is still intact. This
+/// manifest lives next to the binary and is written by this same fetcher, so it is
+/// not a defense against deliberate tampering -- anyone able to modify the cached
+/// binary can rewrite the manifest to match.
+#[derive(Debug, Serialize, Deserialize)]
+struct RevisionManifest {
+    revision: String,
+    platform: String,
+    sha256: String,
+}
+
+fn manifest_path(revision_dir: &Path) -> PathBuf {
+    revision_dir.join("manifest.json")
+}
+
+async fn sha256_of_file(path: &Path) -> Result<String> {
+    let bytes = tokio::fs::read(path).await?;
+    let mut hasher = Sha256::new();
+    hasher.update(&bytes);
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Download (if needed) and return the path to a pinned Chromium build's executable.
+///
+/// Extraction is memoized: if `options.revision`'s binary already exists under
+/// `options.install_dir`, its hash is checked against the [`RevisionManifest`] this
+/// fetcher wrote the last time it extracted that revision (if any) before being
+/// reused without touching the network -- this only guards against accidental
+/// corruption of the cache, not deliberate tampering (see [`RevisionManifest`]). A
+/// binary with no manifest (e.g. placed there by something other than this fetcher)
+/// is reused as-is, since there's nothing to verify it against.
+pub async fn fetch_chromium(options: &FetcherOptions) -> Result<PathBuf> {
+    let revision_dir = options.install_dir.join(&options.revision);
+    let binary_path = revision_dir.join(binary_relative_path(&options.platform));
+
+    if tokio::fs::try_exists(&binary_path).await.unwrap_or(false) {
+        match verify_cached_binary(&revision_dir, &binary_path).await {
+            Ok(()) => return Ok(binary_path),
+            Err(e) => {
+                warn!(
+                    "cached Chromium revision '{}' failed verification, re-fetching: {e}",
+                    options.revision
+                );
+                let _ = tokio::fs::remove_dir_all(&revision_dir).await;
+            }
+        }
+    }
+
+    if !options.allow_download {
+        return Err(anyhow!(
+            "no Chromium binary cached for revision '{}' under {:?}, and allow_download is false",
+            options.revision,
+            options.install_dir
+        ));
+    }
+
+    let base_url = snapshot_base_url(&options.platform)?;
+    let archive = archive_name(&options.platform);
+    let url = format!("{base_url}/{}/{archive}", options.revision);
+
+    info!("Downloading Chromium revision {} from {}", options.revision, url);
+    let response = reqwest::get(&url).await?.error_for_status()?;
+    let expected_size = response.content_length();
+    let bytes = response.bytes().await?;
+    if let Some(expected) = expected_size {
+        if bytes.len() as u64 != expected {
+            return Err(anyhow!(
+                "Chromium download for revision '{}' was truncated: expected {} bytes, got {}",
+                options.revision,
+                expected,
+                bytes.len()
+            ));
+        }
+    }
+
+    tokio::fs::create_dir_all(&revision_dir).await?;
+    let temp_zip = revision_dir.join(format!("{archive}.download"));
+    tokio::fs::write(&temp_zip, &bytes).await?;
+
+    let extract_dir = revision_dir.clone();
+    let extract_zip_path = temp_zip.clone();
+    tokio::task::spawn_blocking(move || extract_zip(&extract_zip_path, &extract_dir))
+        .await
+        .map_err(|e| anyhow!("Chromium extraction task panicked: {e}"))??;
+    let _ = tokio::fs::remove_file(&temp_zip).await;
+
+    mark_executable(&binary_path).await?;
+
+    if !tokio::fs::try_exists(&binary_path).await.unwrap_or(false) {
+        return Err(anyhow!(
+            "extracted Chromium archive for revision '{}' but did not find the expected binary at {:?}",
+            options.revision,
+            binary_path
+        ));
+    }
+
+    let manifest = RevisionManifest {
+        revision: options.revision.clone(),
+        platform: options.platform.clone(),
+        sha256: sha256_of_file(&binary_path).await?,
+    };
+    tokio::fs::write(manifest_path(&revision_dir), serde_json::to_vec_pretty(&manifest)?).await?;
+
+    Ok(binary_path)
+}
+
+/// Check a memoized binary against the [`RevisionManifest`] written the last time this
+/// fetcher extracted `revision_dir`, catching accidental corruption (not tampering --
+/// see [`RevisionManifest`]). A missing manifest (nothing to verify against) is
+/// treated as trusted, not as a failure.
+async fn verify_cached_binary(revision_dir: &Path, binary_path: &Path) -> Result<()> {
+    let manifest_bytes = match tokio::fs::read(manifest_path(revision_dir)).await {
+        Ok(bytes) => bytes,
+        Err(_) => return Ok(()),
+    };
+    let manifest: RevisionManifest = serde_json::from_slice(&manifest_bytes)?;
+
+    let actual = sha256_of_file(binary_path).await?;
+    if actual != manifest.sha256 {
+        return Err(anyhow!(
+            "binary hash {} does not match manifest hash {} recorded for revision '{}'",
+            actual,
+            manifest.sha256,
+            manifest.revision
+        ));
+    }
+
+    Ok(())
+}
+
+fn extract_zip(zip_path: &Path, dest_dir: &Path) -> Result<()> {
+    let file = std::fs::File::open(zip_path)?;
+    let mut archive = zip::ZipArchive::new(file)?;
+    archive.extract(dest_dir)?;
+    Ok(())
+}
+
+#[cfg(unix)]
+async fn mark_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    if !tokio::fs::try_exists(path).await.unwrap_or(false) {
+        return Ok(());
+    }
+    let mut permissions = tokio::fs::metadata(path).await?.permissions();
+    permissions.set_mode(permissions.mode() | 0o111);
+    tokio::fs::set_permissions(path, permissions).await?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+async fn mark_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
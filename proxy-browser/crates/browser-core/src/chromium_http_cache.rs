@@ -0,0 +1,493 @@
+//! Revalidating HTTP cache for intercepted requests
+//!
+//! [`HttpCache`] keys entries by request method + URL + the values of any headers its
+//! stored response's `Vary` lists, and honors standard freshness semantics (RFC 9111):
+//! freshness lifetime comes from `Cache-Control: max-age`/`s-maxage`, falling back to
+//! `Expires`, falling back to a heuristic fraction of `(now - Last-Modified)`, and
+//! `no-store`/`private` responses are never stored while `no-cache` ones are stored but
+//! always revalidated. [`CachingRequestFilter`] wires this into the real CDP
+//! `Fetch.requestPaused` pipeline via [`crate::chromium_interception::RequestFilter`]:
+//! a fresh hit is served directly with `FilterAction::FulfillWith`, a stale entry gets
+//! conditional `If-None-Match`/`If-Modified-Since` headers added before the request is
+//! let through, and `on_response_body` records the response (or refreshes a stale
+//! entry's freshness on a `304`).
+
+use crate::chromium_interception::{
+    FilterAction, InterceptedRequest, InterceptedResponse, RequestFilter,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Tunable cache posture, surfaced on [`crate::chromium_engine::ChromiumEngineConfig::http_cache`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpCacheConfig {
+    pub enabled: bool,
+    /// A response larger than this (bytes) is never stored.
+    pub max_entry_size_bytes: usize,
+    /// Total stored bytes across all entries; the least-recently-used entry is evicted
+    /// once this is exceeded.
+    pub max_total_bytes: usize,
+    /// Fraction of `(now - Last-Modified)` used as the heuristic freshness lifetime
+    /// when a response has neither `Cache-Control` nor `Expires`. RFC 9111 suggests
+    /// 10%.
+    pub heuristic_fraction: f64,
+}
+
+impl Default for HttpCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_entry_size_bytes: 5 * 1024 * 1024,
+            max_total_bytes: 100 * 1024 * 1024,
+            heuristic_fraction: 0.1,
+        }
+    }
+}
+
+struct CacheEntry {
+    status: u16,
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+    vary_values: HashMap<String, Option<String>>,
+    stored_at: DateTime<Utc>,
+    freshness_lifetime: Option<Duration>,
+    last_accessed: DateTime<Utc>,
+}
+
+impl CacheEntry {
+    fn size(&self) -> usize {
+        self.body.len()
+    }
+
+    fn age(&self) -> Duration {
+        Utc::now() - self.stored_at
+    }
+
+    fn is_fresh(&self) -> bool {
+        match self.freshness_lifetime {
+            Some(lifetime) => self.age() < lifetime,
+            None => false,
+        }
+    }
+
+    fn etag(&self) -> Option<&str> {
+        header_value(&self.headers, "etag")
+    }
+
+    fn last_modified(&self) -> Option<&str> {
+        header_value(&self.headers, "last-modified")
+    }
+}
+
+fn header_value<'a>(headers: &'a HashMap<String, String>, name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(name))
+        .map(|(_, v)| v.as_str())
+}
+
+/// Parse a `Cache-Control` header's directives into a name -> optional-value map
+/// (case-insensitive names, values lowercased for directives that are keywords-only).
+fn parse_cache_control(value: &str) -> HashMap<String, Option<String>> {
+    value
+        .split(',')
+        .filter_map(|part| {
+            let part = part.trim();
+            if part.is_empty() {
+                return None;
+            }
+            match part.split_once('=') {
+                Some((k, v)) => Some((
+                    k.trim().to_ascii_lowercase(),
+                    Some(v.trim().trim_matches('"').to_string()),
+                )),
+                None => Some((part.to_ascii_lowercase(), None)),
+            }
+        })
+        .collect()
+}
+
+/// Whether a response may be stored at all: `no-store` and `private` responses never
+/// are. `no-cache` responses *are* stored, but [`compute_freshness_lifetime`] gives
+/// them a zero lifetime, so [`CacheEntry::is_fresh`] never returns true for them and
+/// every later request revalidates (the same as `max-age=0`).
+fn is_cacheable(headers: &HashMap<String, String>) -> bool {
+    let Some(cache_control) = header_value(headers, "cache-control") else {
+        return true;
+    };
+    let directives = parse_cache_control(cache_control);
+    !directives.contains_key("no-store") && !directives.contains_key("private")
+}
+
+/// Compute how long a response stays fresh from its headers, per RFC 9111 8.4.2:
+/// `Cache-Control: s-maxage` (shared cache) or `max-age`, else `Expires` minus `Date`
+/// (or minus `now` if no `Date`), else a heuristic fraction of `(now - Last-Modified)`.
+/// `no-cache` collapses to a zero lifetime so the entry is stored but always
+/// considered stale.
+fn compute_freshness_lifetime(
+    headers: &HashMap<String, String>,
+    now: DateTime<Utc>,
+    heuristic_fraction: f64,
+) -> Option<Duration> {
+    if let Some(cache_control) = header_value(headers, "cache-control") {
+        let directives = parse_cache_control(cache_control);
+        if directives.contains_key("no-cache") {
+            return Some(Duration::zero());
+        }
+        for key in ["s-maxage", "max-age"] {
+            if let Some(Some(seconds)) = directives.get(key) {
+                if let Ok(seconds) = seconds.parse::<i64>() {
+                    return Some(Duration::seconds(seconds.max(0)));
+                }
+            }
+        }
+    }
+
+    if let Some(expires) = header_value(headers, "expires") {
+        if let Ok(expires) = DateTime::parse_from_rfc2822(expires) {
+            let date = header_value(headers, "date")
+                .and_then(|d| DateTime::parse_from_rfc2822(d).ok())
+                .map(|d| d.with_timezone(&Utc))
+                .unwrap_or(now);
+            return Some(expires.with_timezone(&Utc) - date);
+        }
+    }
+
+    if let Some(last_modified) = header_value(headers, "last-modified") {
+        if let Ok(last_modified) = DateTime::parse_from_rfc2822(last_modified) {
+            let age = now - last_modified.with_timezone(&Utc);
+            let heuristic_seconds = (age.num_seconds() as f64 * heuristic_fraction) as i64;
+            return Some(Duration::seconds(heuristic_seconds.max(0)));
+        }
+    }
+
+    None
+}
+
+/// Which headers a stored response's `Vary` lists, resolved against the request that
+/// produced it.
+fn vary_values(
+    response_headers: &HashMap<String, String>,
+    request_headers: &HashMap<String, String>,
+) -> HashMap<String, Option<String>> {
+    let Some(vary) = header_value(response_headers, "vary") else {
+        return HashMap::new();
+    };
+
+    vary.split(',')
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            let value = header_value(request_headers, &name).map(|v| v.to_string());
+            (name.to_ascii_lowercase(), value)
+        })
+        .collect()
+}
+
+fn matches_vary(entry: &CacheEntry, request_headers: &HashMap<String, String>) -> bool {
+    entry.vary_values.iter().all(|(name, stored)| {
+        let current = header_value(request_headers, name).map(|v| v.to_string());
+        &current == stored
+    })
+}
+
+fn cache_key(method: &str, url: &str) -> String {
+    format!("{}:{}", method.to_ascii_uppercase(), url)
+}
+
+/// A revalidating HTTP cache keyed by request method + URL + `Vary` header values. See
+/// the module docs for freshness/revalidation semantics.
+pub struct HttpCache {
+    config: HttpCacheConfig,
+    entries: RwLock<HashMap<String, Vec<CacheEntry>>>,
+}
+
+impl HttpCache {
+    pub fn new(config: HttpCacheConfig) -> Self {
+        Self {
+            config,
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Look up a cached entry matching `method`/`url`/`request_headers`, returning
+    /// `(candidates stored under this key, index of the match, fresh)`. `fresh` true
+    /// means the match can be served as-is; false means it's stale and the caller
+    /// should revalidate (see [`Self::conditional_headers_for_revalidation`]) before
+    /// serving it. `None` when no stored entry matches.
+    async fn lookup(
+        &self,
+        method: &str,
+        url: &str,
+        request_headers: &HashMap<String, String>,
+    ) -> Option<(usize, usize, bool)> {
+        let key = cache_key(method, url);
+        let entries = self.entries.read().await;
+        let candidates = entries.get(&key)?;
+        let (index, entry) = candidates
+            .iter()
+            .enumerate()
+            .find(|(_, entry)| matches_vary(entry, request_headers))?;
+        Some((candidates.len(), index, entry.is_fresh()))
+    }
+
+    /// Build `If-None-Match`/`If-Modified-Since` headers to revalidate a stale entry,
+    /// if it has an `ETag`/`Last-Modified` to revalidate against.
+    async fn conditional_headers_for_revalidation(
+        &self,
+        method: &str,
+        url: &str,
+        request_headers: &HashMap<String, String>,
+    ) -> HashMap<String, String> {
+        let key = cache_key(method, url);
+        let entries = self.entries.read().await;
+        let mut headers = HashMap::new();
+        if let Some(entry) = entries
+            .get(&key)
+            .and_then(|candidates| candidates.iter().find(|e| matches_vary(e, request_headers)))
+        {
+            if let Some(etag) = entry.etag() {
+                headers.insert("If-None-Match".to_string(), etag.to_string());
+            }
+            if let Some(last_modified) = entry.last_modified() {
+                headers.insert("If-Modified-Since".to_string(), last_modified.to_string());
+            }
+        }
+        headers
+    }
+
+    /// Serve a fresh cached entry's status/headers/body, bumping its LRU recency.
+    async fn serve(
+        &self,
+        method: &str,
+        url: &str,
+        request_headers: &HashMap<String, String>,
+    ) -> Option<(u16, HashMap<String, String>, Vec<u8>)> {
+        let key = cache_key(method, url);
+        let mut entries = self.entries.write().await;
+        let entry = entries
+            .get_mut(&key)?
+            .iter_mut()
+            .find(|e| matches_vary(e, request_headers))?;
+        entry.last_accessed = Utc::now();
+        Some((entry.status, entry.headers.clone(), entry.body.clone()))
+    }
+
+    /// Record a response, replacing any existing entry with the same key and `Vary`
+    /// values. No-ops if the response isn't cacheable or exceeds `max_entry_size_bytes`.
+    async fn store(
+        &self,
+        method: &str,
+        url: &str,
+        request_headers: &HashMap<String, String>,
+        status: u16,
+        headers: HashMap<String, String>,
+        body: Vec<u8>,
+    ) {
+        if !is_cacheable(&headers) || body.len() > self.config.max_entry_size_bytes {
+            return;
+        }
+
+        let now = Utc::now();
+        let entry = CacheEntry {
+            freshness_lifetime: compute_freshness_lifetime(
+                &headers,
+                now,
+                self.config.heuristic_fraction,
+            ),
+            vary_values: vary_values(&headers, request_headers),
+            status,
+            headers,
+            body,
+            stored_at: now,
+            last_accessed: now,
+        };
+
+        let key = cache_key(method, url);
+        let mut entries = self.entries.write().await;
+        let candidates = entries.entry(key).or_default();
+        candidates.retain(|e| !matches_vary(e, request_headers));
+        candidates.push(entry);
+        drop(entries);
+
+        self.evict_to_capacity().await;
+    }
+
+    /// Mark a stale entry fresh again after a `304 Not Modified` revalidation,
+    /// refreshing its freshness lifetime from the (typically sparse) 304 headers
+    /// without replacing its stored body.
+    async fn note_revalidated(
+        &self,
+        method: &str,
+        url: &str,
+        request_headers: &HashMap<String, String>,
+        response_headers: HashMap<String, String>,
+    ) {
+        let key = cache_key(method, url);
+        let mut entries = self.entries.write().await;
+        if let Some(entry) = entries
+            .get_mut(&key)
+            .and_then(|candidates| candidates.iter_mut().find(|e| matches_vary(e, request_headers)))
+        {
+            let now = Utc::now();
+            entry.stored_at = now;
+            entry.last_accessed = now;
+            for (name, value) in response_headers {
+                entry.headers.insert(name, value);
+            }
+            entry.freshness_lifetime = compute_freshness_lifetime(
+                &entry.headers,
+                now,
+                self.config.heuristic_fraction,
+            );
+        }
+    }
+
+    async fn total_bytes(&self) -> usize {
+        self.entries
+            .read()
+            .await
+            .values()
+            .flat_map(|candidates| candidates.iter())
+            .map(|e| e.size())
+            .sum()
+    }
+
+    /// Evict least-recently-accessed entries until total stored bytes is back under
+    /// `max_total_bytes`.
+    async fn evict_to_capacity(&self) {
+        while self.total_bytes().await > self.config.max_total_bytes {
+            let mut entries = self.entries.write().await;
+            let oldest = entries
+                .iter()
+                .flat_map(|(key, candidates)| {
+                    candidates
+                        .iter()
+                        .enumerate()
+                        .map(move |(i, e)| (key.clone(), i, e.last_accessed))
+                })
+                .min_by_key(|(_, _, accessed)| *accessed);
+
+            let Some((key, index, _)) = oldest else {
+                break;
+            };
+            if let Some(candidates) = entries.get_mut(&key) {
+                candidates.remove(index);
+                if candidates.is_empty() {
+                    entries.remove(&key);
+                }
+            }
+        }
+    }
+}
+
+/// Serves fresh cache hits directly and revalidates stale ones, wired into the real
+/// CDP `Fetch.requestPaused` pipeline via [`RequestFilter`]. An optional `inner` filter
+/// decides every request the cache doesn't answer itself (a miss, or a stale entry with
+/// no `ETag`/`Last-Modified` to revalidate against), so this can be layered on top of an
+/// existing ad/tracker-blocking filter rather than replacing it. A fresh hit served
+/// from the cache and a stale entry sent off with conditional headers skip
+/// `inner.on_request`; `inner.on_response_body` runs for every response.
+///
+/// `Fetch.requestPaused` fires separately for the request stage and the response
+/// stage, and [`InterceptedResponse`] doesn't carry the request's method or headers
+/// back -- only its `request_id`. `pending` bridges the two stages, recording each
+/// request's method/headers in [`Self::on_request`] and consuming them in
+/// [`Self::on_response_body`].
+pub struct CachingRequestFilter {
+    cache: Arc<HttpCache>,
+    inner: Option<Arc<dyn RequestFilter>>,
+    pending: RwLock<HashMap<String, (String, HashMap<String, String>)>>,
+}
+
+impl CachingRequestFilter {
+    pub fn new(cache: Arc<HttpCache>) -> Self {
+        Self {
+            cache,
+            inner: None,
+            pending: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_inner(cache: Arc<HttpCache>, inner: Arc<dyn RequestFilter>) -> Self {
+        Self {
+            cache,
+            inner: Some(inner),
+            pending: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl RequestFilter for CachingRequestFilter {
+    async fn on_request(&self, req: InterceptedRequest) -> FilterAction {
+        self.pending
+            .write()
+            .await
+            .insert(req.request_id.clone(), (req.method.clone(), req.headers.clone()));
+
+        if let Some((_, _, fresh)) = self.cache.lookup(&req.method, &req.url, &req.headers).await {
+            if fresh {
+                if let Some((status, headers, body)) =
+                    self.cache.serve(&req.method, &req.url, &req.headers).await
+                {
+                    return FilterAction::FulfillWith {
+                        status,
+                        headers,
+                        body,
+                    };
+                }
+            } else {
+                let conditional = self
+                    .cache
+                    .conditional_headers_for_revalidation(&req.method, &req.url, &req.headers)
+                    .await;
+                if !conditional.is_empty() {
+                    let mut headers = req.headers.clone();
+                    headers.extend(conditional);
+                    return FilterAction::ModifyHeaders(headers);
+                }
+            }
+        }
+
+        match &self.inner {
+            Some(inner) => inner.on_request(req).await,
+            None => FilterAction::Continue,
+        }
+    }
+
+    async fn on_response_body(&self, resp: InterceptedResponse, body: Vec<u8>) -> Vec<u8> {
+        let body = match &self.inner {
+            Some(inner) => inner.on_response_body(resp.clone(), body).await,
+            None => body,
+        };
+
+        let Some((method, request_headers)) =
+            self.pending.write().await.remove(&resp.request_id)
+        else {
+            return body;
+        };
+
+        if resp.status == 304 {
+            self.cache
+                .note_revalidated(&method, &resp.url, &request_headers, resp.headers.clone())
+                .await;
+        } else {
+            self.cache
+                .store(
+                    &method,
+                    &resp.url,
+                    &request_headers,
+                    resp.status,
+                    resp.headers.clone(),
+                    body.clone(),
+                )
+                .await;
+        }
+
+        body
+    }
+}
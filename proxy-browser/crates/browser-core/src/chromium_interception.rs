@@ -0,0 +1,67 @@
+//! Per-tab request/response interception
+//!
+//! A pluggable [`RequestFilter`] trait backing CDP's `Fetch.enable`/`Fetch.requestPaused`,
+//! so a caller can inspect or rewrite traffic on a tab — block ads/trackers, inject
+//! headers, or serve deterministic mock responses in tests — rather than only being
+//! able to read after-the-fact [`crate::chromium_engine::TabEvent`]s. Attach one via
+//! [`crate::chromium_engine::ChromiumEngine::set_tab_request_filter`] (a single tab) or
+//! [`crate::chromium_engine::ChromiumEngine::set_request_filter`] (every tab that
+//! doesn't have its own).
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+/// A request CDP paused via `Fetch.requestPaused` before it's sent, handed to
+/// [`RequestFilter::on_request`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterceptedRequest {
+    pub request_id: String,
+    pub url: String,
+    pub method: String,
+    pub headers: HashMap<String, String>,
+    pub body: Option<Vec<u8>>,
+}
+
+/// A response CDP paused via `Fetch.requestPaused` (response stage), handed to
+/// [`RequestFilter::on_response_body`] before its body reaches the renderer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterceptedResponse {
+    pub request_id: String,
+    pub url: String,
+    pub status: u16,
+    pub headers: HashMap<String, String>,
+}
+
+/// What a [`RequestFilter`] wants Chromium to do with a paused request: the CDP
+/// `Fetch.continueRequest`/`Fetch.failRequest`/`Fetch.fulfillRequest` choices.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterAction {
+    /// Let the request proceed unmodified.
+    Continue,
+    /// Abort the request entirely (`Fetch.failRequest`).
+    Block,
+    /// Let the request proceed, replacing its headers (`Fetch.continueRequest` with
+    /// `headers` set).
+    ModifyHeaders(HashMap<String, String>),
+    /// Answer the request directly without it reaching the network
+    /// (`Fetch.fulfillRequest`).
+    FulfillWith {
+        status: u16,
+        headers: HashMap<String, String>,
+        body: Vec<u8>,
+    },
+}
+
+/// Inspects or rewrites traffic on a tab via CDP `Fetch.enable`/`Fetch.requestPaused`.
+#[async_trait]
+pub trait RequestFilter: Send + Sync {
+    /// Decide what to do with a request before it's sent.
+    async fn on_request(&self, req: InterceptedRequest) -> FilterAction;
+
+    /// Rewrite (or drop) a response body before it reaches the renderer. The default
+    /// passes the body through unchanged.
+    async fn on_response_body(&self, resp: InterceptedResponse, body: Vec<u8>) -> Vec<u8> {
+        let _ = resp;
+        body
+    }
+}
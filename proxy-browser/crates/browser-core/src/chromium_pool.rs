@@ -0,0 +1,208 @@
+//! Chromium Instance Pool
+//!
+//! Maintains a fixed-size set of pre-launched [`ChromiumEngine`] instances so callers
+//! serving many concurrent navigation requests don't pay Chrome's startup cost per
+//! request. Instances are recycled (torn down and relaunched) once they accumulate too
+//! many tabs or sit idle too long, to avoid unbounded memory creep in a long-lived pool.
+
+use crate::chromium_engine::{ChromiumEngine, ChromiumEngineConfig, ChromiumError};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, Notify, RwLock};
+
+/// Default pool size used by [`ChromiumPoolConfig::default`].
+pub const NUMBER_OF_INSTANCES: usize = 10;
+
+/// Controls [`ChromiumPool`]'s size, the config each instance launches with, and when
+/// an instance is recycled instead of handed back out as-is.
+#[derive(Debug, Clone)]
+pub struct ChromiumPoolConfig {
+    /// How many `ChromiumEngine` instances to keep launched.
+    pub size: usize,
+    /// Configuration each pooled instance is launched with.
+    pub engine_config: ChromiumEngineConfig,
+    /// Recycle (shut down and relaunch) an instance once it was last released back to
+    /// the pool with at least this many tabs still open. Tabs closed before release
+    /// are not counted.
+    pub max_tabs_before_recycle: usize,
+    /// Recycle an instance that has sat idle in the pool for at least this long.
+    pub idle_timeout: Duration,
+}
+
+impl Default for ChromiumPoolConfig {
+    fn default() -> Self {
+        Self {
+            size: NUMBER_OF_INSTANCES,
+            engine_config: ChromiumEngineConfig::default(),
+            max_tabs_before_recycle: 20,
+            idle_timeout: Duration::from_secs(300),
+        }
+    }
+}
+
+/// Lifecycle state of a single pool slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SlotStatus {
+    /// Being launched or relaunched; not yet available to hand out.
+    Launching,
+    /// Idle and available to [`ChromiumPool::acquire`].
+    Free,
+    /// Checked out by a live [`PooledEngine`].
+    Busy,
+}
+
+struct PoolSlot {
+    engine: Arc<RwLock<ChromiumEngine>>,
+    status: SlotStatus,
+    /// Tab count observed the last time this instance was released back to the pool.
+    tab_snapshot: usize,
+    /// When this instance was last launched or released back to the pool.
+    last_used: Instant,
+}
+
+struct PoolInner {
+    config: ChromiumPoolConfig,
+    slots: Mutex<Vec<PoolSlot>>,
+    /// Notified every time a slot is released back to `Free`, so `acquire` can wake up
+    /// and retry instead of polling.
+    freed: Notify,
+}
+
+/// Counts of pooled instances by lifecycle state, as returned by
+/// [`ChromiumPool::pool_status`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PoolStatus {
+    pub free: usize,
+    pub busy: usize,
+    pub launching: usize,
+}
+
+/// A fixed-size pool of pre-launched [`ChromiumEngine`] instances.
+pub struct ChromiumPool {
+    inner: Arc<PoolInner>,
+}
+
+impl ChromiumPool {
+    /// Launch `config.size` instances eagerly and return a pool backed by them.
+    /// Fails immediately if any instance fails to launch.
+    pub async fn new(config: ChromiumPoolConfig) -> Result<Self, ChromiumError> {
+        let mut slots = Vec::with_capacity(config.size);
+        for _ in 0..config.size {
+            let mut engine = ChromiumEngine::new(config.engine_config.clone());
+            engine.launch().await?;
+            slots.push(PoolSlot {
+                engine: Arc::new(RwLock::new(engine)),
+                status: SlotStatus::Free,
+                tab_snapshot: 0,
+                last_used: Instant::now(),
+            });
+        }
+
+        Ok(Self {
+            inner: Arc::new(PoolInner {
+                config,
+                slots: Mutex::new(slots),
+                freed: Notify::new(),
+            }),
+        })
+    }
+
+    /// Hand out the next free instance, recycling it first if it's past the pool's
+    /// tab-count or idle-timeout threshold. Waits for an instance to free up if every
+    /// slot is currently busy or mid-recycle.
+    pub async fn acquire(&self) -> Result<PooledEngine, ChromiumError> {
+        loop {
+            let candidate = {
+                let mut slots = self.inner.slots.lock().await;
+                slots.iter_mut().enumerate().find(|(_, slot)| slot.status == SlotStatus::Free).map(|(index, slot)| {
+                    let needs_recycle = slot.tab_snapshot >= self.inner.config.max_tabs_before_recycle
+                        || slot.last_used.elapsed() >= self.inner.config.idle_timeout;
+                    slot.status = if needs_recycle { SlotStatus::Launching } else { SlotStatus::Busy };
+                    (index, slot.engine.clone(), needs_recycle)
+                })
+            };
+
+            let Some((index, engine, needs_recycle)) = candidate else {
+                self.inner.freed.notified().await;
+                continue;
+            };
+
+            if needs_recycle {
+                if let Err(e) = self.recycle(&engine).await {
+                    let mut slots = self.inner.slots.lock().await;
+                    slots[index].status = SlotStatus::Free;
+                    return Err(e);
+                }
+
+                let mut slots = self.inner.slots.lock().await;
+                slots[index].status = SlotStatus::Busy;
+                slots[index].tab_snapshot = 0;
+            }
+
+            return Ok(PooledEngine { pool: self.inner.clone(), index, engine });
+        }
+    }
+
+    /// Shut down and relaunch a pooled instance to shed accumulated tabs/memory.
+    async fn recycle(&self, engine: &Arc<RwLock<ChromiumEngine>>) -> Result<(), ChromiumError> {
+        let mut guard = engine.write().await;
+        let _ = guard.shutdown().await;
+        guard.launch().await
+    }
+
+    /// Counts of pooled instances currently free, busy, or mid-(re)launch.
+    pub async fn pool_status(&self) -> PoolStatus {
+        let slots = self.inner.slots.lock().await;
+        let mut status = PoolStatus::default();
+        for slot in slots.iter() {
+            match slot.status {
+                SlotStatus::Free => status.free += 1,
+                SlotStatus::Busy => status.busy += 1,
+                SlotStatus::Launching => status.launching += 1,
+            }
+        }
+        status
+    }
+
+    /// How many instances this pool was configured to maintain.
+    pub fn size(&self) -> usize {
+        self.inner.config.size
+    }
+}
+
+/// A pooled [`ChromiumEngine`] checked out via [`ChromiumPool::acquire`]. Returns its
+/// instance to the pool as `Free` when dropped, after recording the tab count it's
+/// left with so the next `acquire` can decide whether to recycle it.
+pub struct PooledEngine {
+    pool: Arc<PoolInner>,
+    index: usize,
+    engine: Arc<RwLock<ChromiumEngine>>,
+}
+
+impl PooledEngine {
+    /// The underlying engine, behind the same `RwLock` the pool itself uses so
+    /// `read`/`write` guards work exactly like [`crate::chromium_engine::BrowserEngineManager`]'s.
+    pub fn engine(&self) -> &RwLock<ChromiumEngine> {
+        &self.engine
+    }
+}
+
+impl Drop for PooledEngine {
+    fn drop(&mut self) {
+        let pool = self.pool.clone();
+        let engine = self.engine.clone();
+        let index = self.index;
+
+        tokio::spawn(async move {
+            let tab_count = engine.read().await.get_tabs().await.len();
+            let mut slots = pool.slots.lock().await;
+            if let Some(slot) = slots.get_mut(index) {
+                slot.status = SlotStatus::Free;
+                slot.tab_snapshot = tab_count;
+                slot.last_used = Instant::now();
+            }
+            drop(slots);
+            pool.freed.notify_one();
+        });
+    }
+}
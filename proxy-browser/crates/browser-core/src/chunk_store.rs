@@ -0,0 +1,209 @@
+//! Content-Defined Chunking Module
+//!
+//! Splits backup payloads into variable-length, content-defined chunks and stores them
+//! in a reference-counted, content-addressed store so that repeated backups of a mostly
+//! unchanged profile only write the chunks that actually changed.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tokio::sync::RwLock;
+
+/// Bounds on the chunk sizes produced by [`cut_points`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ChunkingConfig {
+    pub min_size: usize,
+    pub avg_size: usize,
+    pub max_size: usize,
+}
+
+impl Default for ChunkingConfig {
+    fn default() -> Self {
+        Self {
+            min_size: 256 * 1024,
+            avg_size: 1024 * 1024,
+            max_size: 4 * 1024 * 1024,
+        }
+    }
+}
+
+/// The sliding window width, in bytes, the buzhash rolls over.
+const BUZHASH_WINDOW: usize = 48;
+
+/// A fixed, deterministic per-byte-value table for the buzhash rolling hash, generated
+/// with splitmix64 so chunk boundaries are stable across runs without depending on an
+/// external `rand` crate.
+fn buzhash_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    for slot in table.iter_mut() {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        *slot = (z & 0xFFFF_FFFF) as u32;
+    }
+    table
+}
+
+/// Find the content-defined chunk boundaries of `data` using a buzhash rolling-hash
+/// cut-point detector: a boundary is emitted once a chunk has reached `config.min_size`
+/// and the low bits of the current hash are zero, or once `config.max_size` is reached
+/// (to bound worst-case chunk length regardless of content).
+///
+/// Returns the `(start, end)` byte ranges of each chunk, in order.
+pub fn cut_points(data: &[u8], config: &ChunkingConfig) -> Vec<(usize, usize)> {
+    if data.is_empty() {
+        return Vec::new();
+    }
+
+    let table = buzhash_table();
+    let mask = config.avg_size.next_power_of_two().max(2) as u32 - 1;
+
+    let mut boundaries = Vec::new();
+    let mut start = 0usize;
+    let mut hash: u32 = 0;
+
+    for (i, &byte) in data.iter().enumerate() {
+        hash = hash.rotate_left(1) ^ table[byte as usize];
+        if i >= BUZHASH_WINDOW {
+            let leaving = table[data[i - BUZHASH_WINDOW] as usize];
+            hash ^= leaving.rotate_left((BUZHASH_WINDOW % 32) as u32);
+        }
+
+        let len = i - start + 1;
+        let hit_boundary = len >= config.min_size && (hash & mask) == 0;
+        if hit_boundary || len >= config.max_size {
+            boundaries.push((start, i + 1));
+            start = i + 1;
+            hash = 0;
+        }
+    }
+
+    if start < data.len() {
+        boundaries.push((start, data.len()));
+    }
+
+    boundaries
+}
+
+/// Hash a chunk's bytes with SHA-256, returning its hex-encoded digest.
+pub fn hash_chunk(data: &[u8]) -> String {
+    let digest = Sha256::digest(data);
+    digest.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// A content-addressed, reference-counted store of backup chunks on disk.
+///
+/// Chunks are written once per distinct digest; every manifest that references a chunk
+/// bumps its refcount, and [`ChunkStore::release`] drops a manifest's references,
+/// deleting any chunk whose count reaches zero.
+pub struct ChunkStore {
+    chunk_dir: PathBuf,
+    refcounts: RwLock<HashMap<String, u64>>,
+}
+
+impl ChunkStore {
+    /// Create a store that writes chunk files under `chunk_dir`.
+    pub fn new(chunk_dir: PathBuf) -> Self {
+        Self {
+            chunk_dir,
+            refcounts: RwLock::new(HashMap::new()),
+        }
+    }
+
+    fn chunk_path(&self, digest: &str) -> PathBuf {
+        self.chunk_dir.join(digest)
+    }
+
+    /// Split `data` per `config`, writing any chunk whose digest isn't already stored
+    /// and bumping every chunk's refcount, then return the ordered manifest of digests.
+    pub async fn store_chunks(&self, data: &[u8], config: &ChunkingConfig) -> Result<Vec<String>> {
+        tokio::fs::create_dir_all(&self.chunk_dir).await?;
+
+        let mut manifest = Vec::with_capacity(data.len() / config.avg_size.max(1) + 1);
+        let mut refcounts = self.refcounts.write().await;
+        for (start, end) in cut_points(data, config) {
+            let chunk = &data[start..end];
+            let digest = hash_chunk(chunk);
+            if !refcounts.contains_key(&digest) {
+                tokio::fs::write(self.chunk_path(&digest), chunk).await?;
+            }
+            *refcounts.entry(digest.clone()).or_insert(0) += 1;
+            manifest.push(digest);
+        }
+        Ok(manifest)
+    }
+
+    /// Reassemble the original payload from an ordered manifest of chunk digests.
+    pub async fn reassemble(&self, manifest: &[String]) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+        for digest in manifest {
+            data.extend_from_slice(&tokio::fs::read(self.chunk_path(digest)).await?);
+        }
+        Ok(data)
+    }
+
+    /// Bump the refcount of every chunk `manifest` references without writing anything,
+    /// because the chunks are assumed to already exist on disk. Used by
+    /// [`crate::backup::BackupManager::load`] to rebuild refcounts from the manifests
+    /// recorded in each backup archive on startup, since refcounts themselves aren't
+    /// persisted.
+    pub async fn adopt_manifest(&self, manifest: &[String]) {
+        let mut refcounts = self.refcounts.write().await;
+        for digest in manifest {
+            *refcounts.entry(digest.clone()).or_insert(0) += 1;
+        }
+    }
+
+    /// Decrement the refcount of every chunk `manifest` references, deleting any chunk
+    /// whose count drops to zero.
+    pub async fn release(&self, manifest: &[String]) -> Result<()> {
+        let mut to_delete = Vec::new();
+        {
+            let mut refcounts = self.refcounts.write().await;
+            for digest in manifest {
+                if let Some(count) = refcounts.get_mut(digest) {
+                    *count = count.saturating_sub(1);
+                    if *count == 0 {
+                        refcounts.remove(digest);
+                        to_delete.push(digest.clone());
+                    }
+                }
+            }
+        }
+
+        for digest in &to_delete {
+            let path = self.chunk_path(digest);
+            if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+                tokio::fs::remove_file(path).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The current refcount of a chunk, or 0 if it isn't stored.
+    pub async fn refcount(&self, digest: &str) -> u64 {
+        self.refcounts.read().await.get(digest).copied().unwrap_or(0)
+    }
+
+    /// Re-read a chunk from disk and confirm it still hashes to its own digest.
+    pub async fn verify_chunk(&self, digest: &str) -> ChunkVerifyStatus {
+        match tokio::fs::read(self.chunk_path(digest)).await {
+            Ok(bytes) if hash_chunk(&bytes) == digest => ChunkVerifyStatus::Ok,
+            Ok(_) => ChunkVerifyStatus::Mismatch,
+            Err(_) => ChunkVerifyStatus::Missing,
+        }
+    }
+}
+
+/// The outcome of verifying a single stored chunk against its own digest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkVerifyStatus {
+    Ok,
+    Missing,
+    Mismatch,
+}
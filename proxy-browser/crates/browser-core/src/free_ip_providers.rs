@@ -0,0 +1,156 @@
+//! Free IP Provider Health Tracking
+//!
+//! Wraps [`crate::http_client::PublicIpDetector`]'s provider chain with per-provider
+//! health bookkeeping (success/failure counts, last-used timestamps), so the
+//! `get_ip_providers_status`/`refresh_ip_providers` Tauri commands report real data
+//! instead of assuming every provider is up.
+
+use crate::http_client::{self, HttpClientError, IpSource, PublicIpDetector};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::RwLock;
+
+/// A single free IP-echo provider tracked by [`FreeIpProviderManager`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FreeIpProvider {
+    pub source: IpSource,
+    pub enabled: bool,
+}
+
+/// Observed health of a single provider, part of [`FreeIpProviderManager::get_status`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub struct ProviderStatus {
+    pub success_count: u64,
+    pub failure_count: u64,
+    /// Unix timestamp (seconds) of the most recent request to this provider, if any.
+    pub last_used: Option<u64>,
+}
+
+impl ProviderStatus {
+    /// Fraction of requests that succeeded, or `1.0` (optimistic) if never queried.
+    pub fn success_rate(&self) -> f64 {
+        let total = self.success_count + self.failure_count;
+        if total == 0 {
+            1.0
+        } else {
+            self.success_count as f64 / total as f64
+        }
+    }
+}
+
+/// Selection criteria [`FreeIpProviderManager::active_sources`] applies when deciding
+/// which providers to hand to [`PublicIpDetector::detect_with_sources`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProxyFilter {
+    /// Only include providers currently marked `enabled`.
+    pub enabled_only: bool,
+    /// Only include providers whose observed success rate is at least this fraction.
+    pub min_success_rate: Option<f64>,
+}
+
+impl ProxyFilter {
+    fn matches(&self, provider: &FreeIpProvider, status: &ProviderStatus) -> bool {
+        if self.enabled_only && !provider.enabled {
+            return false;
+        }
+        if let Some(min_rate) = self.min_success_rate {
+            if status.success_rate() < min_rate {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Manages a chain of free IP-echo providers and their observed health, so callers can
+/// see which providers are actually succeeding rather than treating IP detection as an
+/// opaque single call.
+pub struct FreeIpProviderManager {
+    providers: Vec<FreeIpProvider>,
+    status: RwLock<HashMap<String, ProviderStatus>>,
+}
+
+impl Default for FreeIpProviderManager {
+    fn default() -> Self {
+        Self::new(http_client::default_sources())
+    }
+}
+
+impl FreeIpProviderManager {
+    pub fn new(sources: Vec<IpSource>) -> Self {
+        let providers = sources
+            .into_iter()
+            .map(|source| FreeIpProvider { source, enabled: true })
+            .collect();
+        Self {
+            providers,
+            status: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Providers matching `filter`, in configured priority order.
+    pub async fn active_sources(&self, filter: ProxyFilter) -> Vec<IpSource> {
+        let status = self.status.read().await;
+        self.providers
+            .iter()
+            .filter(|provider| {
+                let provider_status = status.get(&provider.source.name).copied().unwrap_or_default();
+                filter.matches(provider, &provider_status)
+            })
+            .map(|provider| provider.source.clone())
+            .collect()
+    }
+
+    /// Detect the caller's public IP through `filter`'s matching providers, recording
+    /// the outcome for [`Self::get_status`].
+    pub async fn detect(&self, filter: ProxyFilter) -> Result<crate::http_client::PublicIpInfo, HttpClientError> {
+        let sources = self.active_sources(filter).await;
+        let result = PublicIpDetector::detect_with_sources(&sources).await;
+
+        match &result {
+            Ok(info) => self.record(&info.source, true).await,
+            Err(_) => {
+                for source in &sources {
+                    self.record(&source.name, false).await;
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Ping every configured provider individually to refresh its health snapshot,
+    /// regardless of whether it's needed to answer an actual IP lookup.
+    pub async fn refresh_all(&self) -> Result<serde_json::Value, HttpClientError> {
+        for provider in &self.providers {
+            let sources = std::slice::from_ref(&provider.source);
+            let ok = PublicIpDetector::detect_with_sources(sources).await.is_ok();
+            self.record(&provider.source.name, ok).await;
+        }
+
+        self.get_status().await
+    }
+
+    async fn record(&self, provider: &str, success: bool) {
+        let mut status = self.status.write().await;
+        let entry = status.entry(provider.to_string()).or_default();
+        if success {
+            entry.success_count += 1;
+        } else {
+            entry.failure_count += 1;
+        }
+        entry.last_used = Some(
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        );
+    }
+
+    /// Current per-provider health snapshot: success/failure counts and last-used time.
+    pub async fn get_status(&self) -> Result<serde_json::Value, HttpClientError> {
+        let status = self.status.read().await;
+        Ok(serde_json::json!(*status))
+    }
+}
@@ -0,0 +1,796 @@
+//! Gecko (Firefox) Engine Module
+//!
+//! A second [`crate::browser_engine::BrowserEngine`] implementation alongside
+//! [`crate::chromium_engine::ChromiumEngine`], driving Firefox over the Marionette
+//! remote protocol instead of CDP. Tabs map onto Marionette/WebDriver window handles;
+//! [`GeckoEngineConfig::enable_bidi`] opts the session into WebDriver BiDi
+//! (`webSocketUrl: true`), exposing the negotiated socket URL via
+//! [`GeckoEngine::bidi_websocket_url`].
+//!
+//! Firefox has no per-tab network-stack equivalent to Chromium's CDP-driven per-tab
+//! proxy, so [`GeckoEngine::set_tab_proxy`] records the assignment for API parity but
+//! does not enforce it; see its doc comment.
+
+use crate::browser_engine::{BrowserEngine, EngineError, EngineErrorKind};
+use crate::chromium_engine::{ChromiumTab, ContentSettings, EngineCapabilities, Geolocation};
+use crate::proxy::ProxySettings;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::process::Child;
+use tokio::sync::RwLock;
+use tokio::time::Instant;
+use uuid::Uuid;
+
+/// Configuration for a [`GeckoEngine`] instance, deliberately mirroring
+/// [`crate::chromium_engine::ChromiumEngineConfig`]'s shape for the knobs both engines
+/// share.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeckoEngineConfig {
+    pub executable_path: Option<PathBuf>,
+    pub headless: bool,
+    pub profile_dir: Option<PathBuf>,
+    /// Give this instance a fresh temp profile directory on every
+    /// [`GeckoEngine::launch`], overriding `profile_dir`, and delete it on
+    /// `shutdown`/drop.
+    pub ephemeral_profile: bool,
+    pub extra_args: Vec<String>,
+    pub proxy: Option<ProxySettings>,
+    pub user_agent: Option<String>,
+    pub geolocation: Option<Geolocation>,
+    /// IANA timezone name (e.g. `"America/Los_Angeles"`); applied via the `TZ`
+    /// environment variable on the spawned process, since Firefox has no
+    /// runtime-settable timezone preference.
+    pub timezone: Option<String>,
+    /// Opt the Marionette session into WebDriver BiDi (`webSocketUrl: true`).
+    pub enable_bidi: bool,
+    /// TCP port Firefox's `-marionette` listener binds to.
+    pub marionette_port: u16,
+    /// How long to wait for the Marionette listener to accept a connection after
+    /// spawning the process.
+    pub marionette_timeout_secs: u64,
+}
+
+impl Default for GeckoEngineConfig {
+    fn default() -> Self {
+        Self {
+            executable_path: None,
+            headless: false,
+            profile_dir: None,
+            ephemeral_profile: false,
+            extra_args: Vec::new(),
+            proxy: None,
+            user_agent: None,
+            geolocation: None,
+            timezone: None,
+            enable_bidi: false,
+            marionette_port: 2828,
+            marionette_timeout_secs: 10,
+        }
+    }
+}
+
+impl GeckoEngineConfig {
+    /// Build the `about:config` preference overrides [`GeckoEngine::launch`] sends as
+    /// `moz:firefoxOptions.prefs` in the Marionette `newSession` request.
+    fn build_prefs(&self) -> HashMap<String, serde_json::Value> {
+        let mut prefs = HashMap::new();
+
+        if let Some(proxy) = &self.proxy {
+            prefs.insert("network.proxy.type".to_string(), serde_json::json!(1));
+            if let (Some(host), Some(port)) = (&proxy.host, proxy.port) {
+                for key in ["http", "ssl", "socks"] {
+                    prefs.insert(format!("network.proxy.{key}"), serde_json::json!(host));
+                    prefs.insert(format!("network.proxy.{key}_port"), serde_json::json!(port));
+                }
+            }
+        }
+
+        if let Some(user_agent) = &self.user_agent {
+            prefs.insert(
+                "general.useragent.override".to_string(),
+                serde_json::json!(user_agent),
+            );
+        }
+
+        if let Some(geo) = &self.geolocation {
+            // Firefox resolves network-based geolocation through `geo.wifi.uri`;
+            // pointing it at a `data:` URI serving a fixed response spoofs the fix
+            // without a real Wi-Fi-positioning round trip.
+            prefs.insert("geo.provider.testing".to_string(), serde_json::json!(true));
+            prefs.insert("geo.prompt.testing".to_string(), serde_json::json!(true));
+            prefs.insert(
+                "geo.prompt.testing.allow".to_string(),
+                serde_json::json!(true),
+            );
+            prefs.insert(
+                "geo.wifi.uri".to_string(),
+                serde_json::json!(geo_wifi_data_uri(geo)),
+            );
+        }
+
+        prefs
+    }
+}
+
+/// A `data:` URI serving the fixed Google-geolocation-API-shaped response Firefox's
+/// `geo.wifi.uri` pref expects back from a location provider.
+fn geo_wifi_data_uri(geo: &Geolocation) -> String {
+    let body = serde_json::json!({
+        "location": { "lat": geo.latitude, "lng": geo.longitude },
+        "accuracy": geo.accuracy,
+    });
+    format!("data:application/json,{body}")
+}
+
+/// Kind of error raised by [`GeckoEngine`] operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeckoErrorKind {
+    NotRunning,
+    TabNotFound,
+    ExecutableNotFound,
+    LaunchFailed,
+    /// The Marionette listener never accepted a connection within
+    /// `config.marionette_timeout_secs`.
+    ConnectTimeout,
+    /// Marionette returned an `error` field for a command.
+    CommandFailed,
+    /// The Marionette wire protocol framing was malformed.
+    ProtocolError,
+}
+
+/// Error returned by the Gecko engine module.
+#[derive(Debug, Clone)]
+pub struct GeckoError {
+    pub kind: GeckoErrorKind,
+    pub message: String,
+}
+
+impl GeckoError {
+    pub fn new(kind: GeckoErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for GeckoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}: {}", self.kind, self.message)
+    }
+}
+
+impl std::error::Error for GeckoError {}
+
+impl From<GeckoError> for EngineError {
+    fn from(err: GeckoError) -> Self {
+        let kind = match err.kind {
+            GeckoErrorKind::NotRunning => EngineErrorKind::NotRunning,
+            GeckoErrorKind::TabNotFound => EngineErrorKind::TabNotFound,
+            GeckoErrorKind::ExecutableNotFound => EngineErrorKind::ExecutableNotFound,
+            GeckoErrorKind::LaunchFailed | GeckoErrorKind::ConnectTimeout => {
+                EngineErrorKind::LaunchFailed
+            }
+            GeckoErrorKind::CommandFailed | GeckoErrorKind::ProtocolError => {
+                EngineErrorKind::Internal
+            }
+        };
+        EngineError::new(kind, err.message)
+    }
+}
+
+/// Names searched for on `$PATH`/well-known install locations when
+/// `config.executable_path` isn't set.
+fn resolve_executable(config: &GeckoEngineConfig) -> Result<PathBuf, GeckoError> {
+    if let Some(path) = &config.executable_path {
+        return Ok(path.clone());
+    }
+
+    if let Some(path) = std::env::var_os("FIREFOX_PATH").map(PathBuf::from) {
+        if path.exists() {
+            return Ok(path);
+        }
+    }
+
+    if let Some(path_var) = std::env::var_os("PATH") {
+        for dir in std::env::split_paths(&path_var) {
+            for name in ["firefox", "firefox-bin", "firefox.exe"] {
+                let candidate = dir.join(name);
+                if candidate.is_file() {
+                    return Ok(candidate);
+                }
+            }
+        }
+    }
+
+    let well_known: &[&str] = if cfg!(target_os = "windows") {
+        &[r"C:\Program Files\Mozilla Firefox\firefox.exe"]
+    } else if cfg!(target_os = "macos") {
+        &["/Applications/Firefox.app/Contents/MacOS/firefox"]
+    } else {
+        &["/usr/bin/firefox", "/snap/bin/firefox"]
+    };
+
+    well_known
+        .iter()
+        .map(PathBuf::from)
+        .find(|p| p.exists())
+        .ok_or_else(|| {
+            GeckoError::new(
+                GeckoErrorKind::ExecutableNotFound,
+                "no FIREFOX_PATH override, $PATH binary or well-known install was found",
+            )
+        })
+}
+
+/// A Marionette protocol connection: length-prefixed JSON packets over TCP, per
+/// https://firefox-source-docs.mozilla.org/testing/marionette/Protocol.html.
+struct MarionetteClient {
+    stream: TcpStream,
+    next_message_id: u64,
+}
+
+impl MarionetteClient {
+    /// Retry connecting until `deadline`, since the Marionette listener isn't ready
+    /// the instant the process is spawned.
+    async fn connect(port: u16, timeout: Duration) -> Result<Self, GeckoError> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            match TcpStream::connect(("127.0.0.1", port)).await {
+                Ok(stream) => {
+                    let mut client = Self {
+                        stream,
+                        next_message_id: 0,
+                    };
+                    // The server sends an unsolicited handshake packet on connect.
+                    client.read_packet().await?;
+                    return Ok(client);
+                }
+                Err(e) => {
+                    if Instant::now() >= deadline {
+                        return Err(GeckoError::new(
+                            GeckoErrorKind::ConnectTimeout,
+                            format!("could not connect to Marionette on port {port}: {e}"),
+                        ));
+                    }
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                }
+            }
+        }
+    }
+
+    async fn write_packet(&mut self, value: &serde_json::Value) -> Result<(), GeckoError> {
+        let payload = serde_json::to_vec(value)
+            .map_err(|e| GeckoError::new(GeckoErrorKind::ProtocolError, e.to_string()))?;
+        let framed = format!("{}:", payload.len());
+        self.stream
+            .write_all(framed.as_bytes())
+            .await
+            .map_err(|e| GeckoError::new(GeckoErrorKind::ProtocolError, e.to_string()))?;
+        self.stream
+            .write_all(&payload)
+            .await
+            .map_err(|e| GeckoError::new(GeckoErrorKind::ProtocolError, e.to_string()))?;
+        Ok(())
+    }
+
+    async fn read_packet(&mut self) -> Result<serde_json::Value, GeckoError> {
+        let mut len_digits = Vec::new();
+        loop {
+            let mut byte = [0u8; 1];
+            self.stream
+                .read_exact(&mut byte)
+                .await
+                .map_err(|e| GeckoError::new(GeckoErrorKind::ProtocolError, e.to_string()))?;
+            if byte[0] == b':' {
+                break;
+            }
+            len_digits.push(byte[0]);
+        }
+
+        let len: usize = std::str::from_utf8(&len_digits)
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| {
+                GeckoError::new(
+                    GeckoErrorKind::ProtocolError,
+                    "malformed packet length prefix",
+                )
+            })?;
+
+        let mut payload = vec![0u8; len];
+        self.stream
+            .read_exact(&mut payload)
+            .await
+            .map_err(|e| GeckoError::new(GeckoErrorKind::ProtocolError, e.to_string()))?;
+
+        serde_json::from_slice(&payload)
+            .map_err(|e| GeckoError::new(GeckoErrorKind::ProtocolError, e.to_string()))
+    }
+
+    /// Send a Marionette command packet (`[0, id, name, params]`) and return its
+    /// result, translating an `error` response into [`GeckoErrorKind::CommandFailed`].
+    async fn command(
+        &mut self,
+        name: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, GeckoError> {
+        self.next_message_id += 1;
+        let id = self.next_message_id;
+        self.write_packet(&serde_json::json!([0, id, name, params]))
+            .await?;
+
+        let response = self.read_packet().await?;
+        let fields = response.as_array().ok_or_else(|| {
+            GeckoError::new(
+                GeckoErrorKind::ProtocolError,
+                "response was not a 4-element array",
+            )
+        })?;
+        if fields.len() != 4 {
+            return Err(GeckoError::new(
+                GeckoErrorKind::ProtocolError,
+                "response was not a 4-element array",
+            ));
+        }
+
+        if !fields[2].is_null() {
+            return Err(GeckoError::new(
+                GeckoErrorKind::CommandFailed,
+                fields[2].to_string(),
+            ));
+        }
+
+        Ok(fields[3].clone())
+    }
+}
+
+/// An integrated Firefox browser engine instance, driven over Marionette.
+///
+/// Tab state lives behind internal locks so read operations (`get_tabs`,
+/// `is_running`, ...) work through a shared `&GeckoEngine`, matching
+/// [`crate::chromium_engine::ChromiumEngine`]'s concurrency model.
+pub struct GeckoEngine {
+    config: GeckoEngineConfig,
+    running: RwLock<bool>,
+    child_process: RwLock<Option<Child>>,
+    client: RwLock<Option<MarionetteClient>>,
+    tabs: RwLock<HashMap<String, ChromiumTab>>,
+    active_tab: RwLock<Option<String>>,
+    /// The WebDriver BiDi session socket URL negotiated at `newSession` time, if
+    /// `config.enable_bidi` was set.
+    bidi_websocket_url: RwLock<Option<String>>,
+    owned_profile_dir: RwLock<Option<PathBuf>>,
+}
+
+impl GeckoEngine {
+    /// Create a new, not-yet-launched engine with the given configuration.
+    pub fn new(config: GeckoEngineConfig) -> Self {
+        Self {
+            config,
+            running: RwLock::new(false),
+            child_process: RwLock::new(None),
+            client: RwLock::new(None),
+            tabs: RwLock::new(HashMap::new()),
+            active_tab: RwLock::new(None),
+            bidi_websocket_url: RwLock::new(None),
+            owned_profile_dir: RwLock::new(None),
+        }
+    }
+
+    /// The WebDriver BiDi session socket URL, if `config.enable_bidi` was set and
+    /// negotiation succeeded.
+    pub async fn bidi_websocket_url(&self) -> Option<String> {
+        self.bidi_websocket_url.read().await.clone()
+    }
+
+    async fn prepare_profile_dir(&self) -> PathBuf {
+        if self.config.ephemeral_profile || self.config.profile_dir.is_none() {
+            let dir = std::env::temp_dir().join(format!("gecko-profile-{}", Uuid::new_v4()));
+            let _ = tokio::fs::create_dir_all(&dir).await;
+            *self.owned_profile_dir.write().await = Some(dir.clone());
+            dir
+        } else {
+            self.config.profile_dir.clone().unwrap()
+        }
+    }
+
+    /// Launch the underlying Firefox process and negotiate a Marionette session.
+    pub async fn launch(&mut self) -> Result<(), GeckoError> {
+        let executable = resolve_executable(&self.config)?;
+        let profile_dir = self.prepare_profile_dir().await;
+
+        let mut command = tokio::process::Command::new(&executable);
+        command
+            .arg("-marionette")
+            .arg("-no-remote")
+            .arg("-new-instance")
+            .arg("-profile")
+            .arg(&profile_dir);
+        if self.config.headless {
+            command.arg("-headless");
+        }
+        command.args(&self.config.extra_args);
+        if let Some(timezone) = &self.config.timezone {
+            command.env("TZ", timezone);
+        }
+        command.stdout(std::process::Stdio::null());
+        command.stderr(std::process::Stdio::null());
+
+        let child = command.spawn().map_err(|e| {
+            GeckoError::new(
+                GeckoErrorKind::LaunchFailed,
+                format!("failed to spawn Firefox: {e}"),
+            )
+        })?;
+        *self.child_process.write().await = Some(child);
+
+        let mut client = MarionetteClient::connect(
+            self.config.marionette_port,
+            Duration::from_secs(self.config.marionette_timeout_secs),
+        )
+        .await?;
+
+        let mut always_match = serde_json::json!({
+            "acceptInsecureCerts": true,
+            "moz:firefoxOptions": { "prefs": self.config.build_prefs() },
+        });
+        if self.config.enable_bidi {
+            always_match["webSocketUrl"] = serde_json::json!(true);
+        }
+
+        let response = client
+            .command(
+                "WebDriver:NewSession",
+                serde_json::json!({ "capabilities": { "alwaysMatch": always_match } }),
+            )
+            .await?;
+
+        if let Some(url) = response
+            .get("capabilities")
+            .and_then(|c| c.get("webSocketUrl"))
+            .and_then(|v| v.as_str())
+        {
+            *self.bidi_websocket_url.write().await = Some(url.to_string());
+        }
+
+        *self.client.write().await = Some(client);
+        *self.running.write().await = true;
+        Ok(())
+    }
+
+    /// Shut the engine down, quitting Firefox and deleting the ephemeral profile
+    /// directory created by `launch`, if any. Safe to call repeatedly.
+    pub async fn shutdown(&mut self) -> Result<(), GeckoError> {
+        if let Some(mut client) = self.client.write().await.take() {
+            let _ = client.command("Quit", serde_json::json!({})).await;
+        }
+
+        if let Some(mut child) = self.child_process.write().await.take() {
+            let _ = child.kill().await;
+        }
+
+        self.tabs.write().await.clear();
+        *self.active_tab.write().await = None;
+        *self.bidi_websocket_url.write().await = None;
+        *self.running.write().await = false;
+
+        if let Some(dir) = self.owned_profile_dir.write().await.take() {
+            let _ = tokio::fs::remove_dir_all(&dir).await;
+        }
+
+        Ok(())
+    }
+
+    /// Whether the Firefox process is currently alive.
+    pub async fn is_running(&self) -> bool {
+        if let Some(child) = self.child_process.write().await.as_mut() {
+            return matches!(child.try_wait(), Ok(None));
+        }
+        *self.running.read().await
+    }
+
+    /// Current engine configuration.
+    pub fn get_config(&self) -> GeckoEngineConfig {
+        self.config.clone()
+    }
+
+    /// Replace the engine configuration. Takes effect on the next `launch`.
+    pub fn set_config(&mut self, config: GeckoEngineConfig) {
+        self.config = config;
+    }
+
+    /// Open a new tab (Marionette `WebDriver:NewWindow` with `type: "tab"`),
+    /// optionally navigating to `url`. `proxy` is recorded for API parity with
+    /// [`crate::chromium_engine::ChromiumEngine::create_tab`] but not enforced; see
+    /// [`GeckoEngine::set_tab_proxy`].
+    pub async fn create_tab(
+        &self,
+        url: Option<&str>,
+        proxy: Option<ProxySettings>,
+    ) -> Result<ChromiumTab, GeckoError> {
+        if !self.is_running().await {
+            return Err(GeckoError::new(
+                GeckoErrorKind::NotRunning,
+                "cannot create a tab before the engine is launched",
+            ));
+        }
+
+        let mut client_guard = self.client.write().await;
+        let client = client_guard.as_mut().ok_or_else(|| {
+            GeckoError::new(GeckoErrorKind::NotRunning, "no active Marionette session")
+        })?;
+
+        let response = client
+            .command("WebDriver:NewWindow", serde_json::json!({ "type": "tab" }))
+            .await?;
+        let handle = response
+            .get("handle")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                GeckoError::new(
+                    GeckoErrorKind::ProtocolError,
+                    "WebDriver:NewWindow returned no handle",
+                )
+            })?
+            .to_string();
+
+        if let Some(url) = url {
+            client
+                .command(
+                    "WebDriver:SwitchToWindow",
+                    serde_json::json!({ "handle": handle }),
+                )
+                .await?;
+            client
+                .command("WebDriver:Navigate", serde_json::json!({ "url": url }))
+                .await?;
+        }
+        drop(client_guard);
+
+        let tab = ChromiumTab {
+            id: handle,
+            url: url.unwrap_or("about:blank").to_string(),
+            title: String::new(),
+            proxy,
+            is_loading: url.is_some(),
+            can_go_back: false,
+            can_go_forward: false,
+            // Marionette has no per-tab CDP browser context to hand out -- a proxy
+            // assigned here is recorded on the tab but, per the module doc above, not
+            // actually isolated from the rest of the profile.
+            browser_context_id: None,
+            content_settings: ContentSettings::default(),
+        };
+
+        self.tabs.write().await.insert(tab.id.clone(), tab.clone());
+        let mut active_tab = self.active_tab.write().await;
+        if active_tab.is_none() {
+            *active_tab = Some(tab.id.clone());
+        }
+
+        Ok(tab)
+    }
+
+    /// Navigate `tab_id` to `url`, switching the Marionette window focus to it first.
+    pub async fn navigate(&self, tab_id: &str, url: &str) -> Result<(), GeckoError> {
+        if !self.is_running().await {
+            return Err(GeckoError::new(
+                GeckoErrorKind::NotRunning,
+                "cannot navigate before the engine is launched",
+            ));
+        }
+        if !self.tabs.read().await.contains_key(tab_id) {
+            return Err(GeckoError::new(
+                GeckoErrorKind::TabNotFound,
+                format!("no tab with id '{tab_id}'"),
+            ));
+        }
+
+        let mut client_guard = self.client.write().await;
+        let client = client_guard.as_mut().ok_or_else(|| {
+            GeckoError::new(GeckoErrorKind::NotRunning, "no active Marionette session")
+        })?;
+        client
+            .command(
+                "WebDriver:SwitchToWindow",
+                serde_json::json!({ "handle": tab_id }),
+            )
+            .await?;
+        client
+            .command("WebDriver:Navigate", serde_json::json!({ "url": url }))
+            .await?;
+        drop(client_guard);
+
+        let mut tabs = self.tabs.write().await;
+        let tab = tabs.get_mut(tab_id).ok_or_else(|| {
+            GeckoError::new(
+                GeckoErrorKind::TabNotFound,
+                format!("no tab with id '{tab_id}'"),
+            )
+        })?;
+        tab.can_go_back = !tab.url.is_empty() && tab.url != url;
+        tab.can_go_forward = false;
+        tab.url = url.to_string();
+        tab.is_loading = true;
+
+        Ok(())
+    }
+
+    /// Close a tab. Idempotent: closing an already-closed or unknown tab succeeds.
+    pub async fn close_tab(&self, tab_id: &str) -> Result<(), GeckoError> {
+        if !self.tabs.write().await.remove(tab_id).is_some() {
+            return Ok(());
+        }
+
+        if let Some(client) = self.client.write().await.as_mut() {
+            client
+                .command(
+                    "WebDriver:SwitchToWindow",
+                    serde_json::json!({ "handle": tab_id }),
+                )
+                .await?;
+            client
+                .command("WebDriver:CloseWindow", serde_json::json!({}))
+                .await?;
+        }
+
+        let mut active_tab = self.active_tab.write().await;
+        if active_tab.as_deref() == Some(tab_id) {
+            *active_tab = None;
+        }
+
+        Ok(())
+    }
+
+    /// Make `tab_id` the active tab. Does not itself switch Marionette's window
+    /// focus; `navigate`/`close_tab` do that as needed.
+    pub async fn set_active_tab(&self, tab_id: &str) -> Result<(), GeckoError> {
+        if !self.tabs.read().await.contains_key(tab_id) {
+            return Err(GeckoError::new(
+                GeckoErrorKind::TabNotFound,
+                format!("no tab with id '{tab_id}'"),
+            ));
+        }
+        *self.active_tab.write().await = Some(tab_id.to_string());
+        Ok(())
+    }
+
+    /// All currently open tabs.
+    pub async fn get_tabs(&self) -> Vec<ChromiumTab> {
+        self.tabs.read().await.values().cloned().collect()
+    }
+
+    /// Record (or clear) a proxy assignment for `tab_id`. Firefox has no per-tab
+    /// network-stack equivalent to Chromium's CDP-driven per-tab proxy, so this is
+    /// informational only: it does not change which proxy (if any) the tab's
+    /// requests actually use. `config.proxy` remains the only proxy Firefox enforces,
+    /// applied engine-wide via `network.proxy.*` prefs at `launch` time.
+    pub async fn set_tab_proxy(
+        &self,
+        tab_id: &str,
+        proxy: Option<ProxySettings>,
+    ) -> Result<(), GeckoError> {
+        let mut tabs = self.tabs.write().await;
+        let tab = tabs.get_mut(tab_id).ok_or_else(|| {
+            GeckoError::new(
+                GeckoErrorKind::TabNotFound,
+                format!("no tab with id '{tab_id}'"),
+            )
+        })?;
+        tab.proxy = proxy;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl BrowserEngine for GeckoEngine {
+    async fn launch(&mut self) -> Result<(), EngineError> {
+        GeckoEngine::launch(self).await.map_err(Into::into)
+    }
+
+    async fn shutdown(&mut self) -> Result<(), EngineError> {
+        GeckoEngine::shutdown(self).await.map_err(Into::into)
+    }
+
+    async fn is_running(&self) -> bool {
+        GeckoEngine::is_running(self).await
+    }
+
+    async fn create_tab(
+        &self,
+        url: Option<&str>,
+        proxy: Option<ProxySettings>,
+    ) -> Result<ChromiumTab, EngineError> {
+        GeckoEngine::create_tab(self, url, proxy)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn navigate(&self, tab_id: &str, url: &str) -> Result<(), EngineError> {
+        GeckoEngine::navigate(self, tab_id, url)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn close_tab(&self, tab_id: &str) -> Result<(), EngineError> {
+        GeckoEngine::close_tab(self, tab_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn set_active_tab(&self, tab_id: &str) -> Result<(), EngineError> {
+        GeckoEngine::set_active_tab(self, tab_id)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_tabs(&self) -> Vec<ChromiumTab> {
+        GeckoEngine::get_tabs(self).await
+    }
+
+    async fn set_tab_proxy(
+        &self,
+        tab_id: &str,
+        proxy: Option<ProxySettings>,
+    ) -> Result<(), EngineError> {
+        GeckoEngine::set_tab_proxy(self, tab_id, proxy)
+            .await
+            .map_err(Into::into)
+    }
+
+    fn get_config(&self) -> serde_json::Value {
+        serde_json::json!({
+            "browserName": "firefox",
+            "headless": self.config.headless,
+            "userAgent": self.config.user_agent,
+            "webSocketUrl": self.config.enable_bidi,
+        })
+    }
+
+    fn set_config(&mut self, config: &serde_json::Value) -> Result<(), EngineError> {
+        let mut updated = self.config.clone();
+        if let Some(headless) = config.get("headless").and_then(|v| v.as_bool()) {
+            updated.headless = headless;
+        }
+        if let Some(user_agent) = config.get("userAgent").and_then(|v| v.as_str()) {
+            updated.user_agent = Some(user_agent.to_string());
+        }
+        if let Some(enable_bidi) = config.get("webSocketUrl").and_then(|v| v.as_bool()) {
+            updated.enable_bidi = enable_bidi;
+        }
+        GeckoEngine::set_config(self, updated);
+        Ok(())
+    }
+
+    fn capabilities(&self) -> EngineCapabilities {
+        EngineCapabilities {
+            per_tab_proxy: false,
+            webrtc_protection: false,
+            stealth_mode: false,
+            dns_over_https: false,
+            custom_user_agent: true,
+            javascript_injection: false,
+            network_interception: false,
+            cookie_management: false,
+            extensions: false,
+        }
+    }
+}
+
+impl Drop for GeckoEngine {
+    /// Best-effort cleanup for an ephemeral profile directory if the caller dropped
+    /// the engine without awaiting [`GeckoEngine::shutdown`] first.
+    fn drop(&mut self) {
+        if let Ok(guard) = self.owned_profile_dir.try_read() {
+            if let Some(dir) = guard.as_ref() {
+                let _ = std::fs::remove_dir_all(dir);
+            }
+        }
+    }
+}
@@ -0,0 +1,268 @@
+//! HTTP Client and Public IP Detection
+//!
+//! A thin, reusable `reqwest` wrapper plus public-IP detection backed by a chain of
+//! free IP-echo services (ipify, icanhazip, seeip-style), so a single provider outage
+//! doesn't take down IP detection. [`PublicIpDetector::detect_with_sources`] tries each
+//! [`IpSource`] in order, falling back to the next on error (including a `429`) or
+//! timeout, with a per-provider token-bucket limiter: a provider whose budget is
+//! exhausted is still queried, but only after a jittered backoff, so it isn't hammered
+//! again immediately.
+
+use rand::Rng;
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tokio::time::sleep;
+
+/// Requests allowed per provider within [`RATE_LIMIT_WINDOW`] before
+/// [`PublicIpDetector`] starts backing off that provider.
+const RATE_LIMIT_CAPACITY: u32 = 10;
+/// Window over which a provider's rate-limit budget fully refills.
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+/// Upper bound on the jittered backoff applied when a provider's budget is exhausted.
+const MAX_BACKOFF: Duration = Duration::from_secs(5);
+/// How long to wait for a single provider's response before falling back.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Thin wrapper around a shared [`reqwest::Client`], reused across requests instead of
+/// paying for a fresh connection pool per call.
+#[derive(Clone)]
+pub struct HttpClient {
+    client: Client,
+}
+
+impl Default for HttpClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HttpClient {
+    pub fn new() -> Self {
+        Self { client: Client::new() }
+    }
+
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+}
+
+/// The caller's public IP address, as reported by whichever [`IpSource`] answered.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublicIpInfo {
+    pub ip: String,
+    pub source: String,
+}
+
+/// How to pull an IP address out of an [`IpSource`]'s response body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IpResponseFormat {
+    /// The body is the bare IP address (e.g. `icanhazip.com`, `api.seeip.org/plain`).
+    PlainText,
+    /// The body is JSON with the IP under this top-level string field (e.g.
+    /// `api.ipify.org?format=json` → `"ip"`).
+    JsonField(String),
+}
+
+/// A free IP-echo service [`PublicIpDetector`] can query, with its own URL and
+/// response parser.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IpSource {
+    pub name: String,
+    pub url: String,
+    pub format: IpResponseFormat,
+}
+
+impl IpSource {
+    pub fn ipify() -> Self {
+        Self {
+            name: "ipify".to_string(),
+            url: "https://api.ipify.org?format=json".to_string(),
+            format: IpResponseFormat::JsonField("ip".to_string()),
+        }
+    }
+
+    pub fn icanhazip() -> Self {
+        Self {
+            name: "icanhazip".to_string(),
+            url: "https://icanhazip.com".to_string(),
+            format: IpResponseFormat::PlainText,
+        }
+    }
+
+    pub fn seeip() -> Self {
+        Self {
+            name: "seeip".to_string(),
+            url: "https://api.seeip.org/jsonip".to_string(),
+            format: IpResponseFormat::JsonField("ip".to_string()),
+        }
+    }
+
+    /// Pull the IP address out of a response body per this source's `format`.
+    fn parse(&self, body: &str) -> Option<String> {
+        match &self.format {
+            IpResponseFormat::PlainText => {
+                let ip = body.trim();
+                (!ip.is_empty()).then(|| ip.to_string())
+            }
+            IpResponseFormat::JsonField(field) => serde_json::from_str::<serde_json::Value>(body)
+                .ok()?
+                .get(field)?
+                .as_str()
+                .map(|s| s.to_string()),
+        }
+    }
+}
+
+/// The default provider chain: ipify, then icanhazip, then seeip.
+pub fn default_sources() -> Vec<IpSource> {
+    vec![IpSource::ipify(), IpSource::icanhazip(), IpSource::seeip()]
+}
+
+/// Why [`PublicIpDetector::detect`] could not determine the caller's public IP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpClientErrorKind {
+    /// Every configured [`IpSource`] errored, timed out, or returned an unparseable body.
+    AllSourcesExhausted,
+}
+
+/// Error returned by [`PublicIpDetector`].
+#[derive(Debug, Clone)]
+pub struct HttpClientError {
+    pub kind: HttpClientErrorKind,
+    pub message: String,
+}
+
+impl HttpClientError {
+    fn new(kind: HttpClientErrorKind, message: impl Into<String>) -> Self {
+        Self { kind, message: message.into() }
+    }
+}
+
+impl std::fmt::Display for HttpClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}: {}", self.kind, self.message)
+    }
+}
+
+impl std::error::Error for HttpClientError {}
+
+/// A provider's remaining request budget: refills to `capacity` tokens over
+/// `refill_window`, so a provider that starts rate-limiting us backs off instead of
+/// being hit again immediately.
+struct TokenBucket {
+    tokens: f64,
+    capacity: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(capacity: u32, refill_window: Duration) -> Self {
+        Self {
+            tokens: capacity as f64,
+            capacity: capacity as f64,
+            refill_per_sec: capacity as f64 / refill_window.as_secs_f64(),
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Refill based on elapsed time, then take one token if available.
+    fn try_acquire(&mut self) -> bool {
+        let elapsed = self.last_refill.elapsed().as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = Instant::now();
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Per-provider token buckets, shared across every [`PublicIpDetector::detect`] call
+/// in the process so a provider's budget is tracked regardless of which caller is
+/// asking.
+fn rate_limiters() -> &'static Mutex<HashMap<String, TokenBucket>> {
+    static LIMITERS: OnceLock<Mutex<HashMap<String, TokenBucket>>> = OnceLock::new();
+    LIMITERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Take one token from `provider`'s bucket. If the bucket is exhausted, sleep a jittered
+/// backoff of up to [`MAX_BACKOFF`] and return without a token; the caller's request
+/// then goes ahead.
+async fn wait_for_capacity(provider: &str) {
+    let mut limiters = rate_limiters().lock().await;
+    let bucket = limiters
+        .entry(provider.to_string())
+        .or_insert_with(|| TokenBucket::new(RATE_LIMIT_CAPACITY, RATE_LIMIT_WINDOW));
+
+    if bucket.try_acquire() {
+        return;
+    }
+    drop(limiters);
+
+    let jitter_ms = rand::thread_rng().gen_range(0..=MAX_BACKOFF.as_millis() as u64);
+    sleep(Duration::from_millis(jitter_ms)).await;
+}
+
+async fn query_source(http: &HttpClient, source: &IpSource) -> Result<PublicIpInfo, String> {
+    let response = tokio::time::timeout(REQUEST_TIMEOUT, http.client().get(&source.url).send())
+        .await
+        .map_err(|_| format!("'{}' timed out after {:?}", source.name, REQUEST_TIMEOUT))?
+        .map_err(|e| format!("'{}' request failed: {e}", source.name))?;
+
+    if response.status() == StatusCode::TOO_MANY_REQUESTS {
+        return Err(format!("'{}' is rate-limiting us", source.name));
+    }
+
+    let body = response
+        .error_for_status()
+        .map_err(|e| format!("'{}' returned an error status: {e}", source.name))?
+        .text()
+        .await
+        .map_err(|e| format!("'{}' response body could not be read: {e}", source.name))?;
+
+    source
+        .parse(&body)
+        .map(|ip| PublicIpInfo { ip, source: source.name.clone() })
+        .ok_or_else(|| format!("'{}' response could not be parsed as an IP address", source.name))
+}
+
+/// Queries a chain of [`IpSource`]s for the caller's public IP, falling back to the
+/// next source on error or timeout.
+pub struct PublicIpDetector;
+
+impl PublicIpDetector {
+    /// Query [`default_sources`] in order.
+    pub async fn detect() -> Result<PublicIpInfo, HttpClientError> {
+        Self::detect_with_sources(&default_sources()).await
+    }
+
+    /// Query `sources` in order, falling back to the next on error or timeout and
+    /// returning the first successful result. A source whose rate-limit budget is
+    /// exhausted is queried after a jittered backoff (see [`wait_for_capacity`]).
+    pub async fn detect_with_sources(sources: &[IpSource]) -> Result<PublicIpInfo, HttpClientError> {
+        let http = HttpClient::new();
+        let mut last_error = None;
+
+        for source in sources {
+            wait_for_capacity(&source.name).await;
+
+            match query_source(&http, source).await {
+                Ok(info) => return Ok(info),
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        Err(HttpClientError::new(
+            HttpClientErrorKind::AllSourcesExhausted,
+            last_error.unwrap_or_else(|| "no IP sources were configured".to_string()),
+        ))
+    }
+}
@@ -0,0 +1,347 @@
+//! WebDriver-style input actions: tick-based dispatch of pointer/key/wheel/pause
+//! sources.
+//!
+//! Mirrors the W3C WebDriver "dispatch actions" algorithm: each [`ActionSequence`]
+//! advances one [`ActionItem`] per tick, shorter sequences are padded with implicit
+//! pauses so every source has the same number of ticks, and a tick's duration is the
+//! maximum `duration` requested by any source at that tick. [`dispatch`] returns the
+//! ordered, per-tick [`DispatchedAction`]s a caller would turn into real input events.
+//!
+//! This crate has no CDP `Input.dispatchMouseEvent`/`Input.dispatchKeyEvent` wiring
+//! yet (`ChromiumEngine` only manages process lifecycle and tab bookkeeping), so
+//! `dispatch` stops at producing that ordered, validated event list rather than
+//! injecting it into a real page -- the same honest-substitution the rest of
+//! [`crate::webdriver`] makes where this tree has no real browser surface to drive.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Where a [`ActionItem::PointerMove`]'s `x`/`y` are measured from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PointerOrigin {
+    /// `x`/`y` are absolute viewport coordinates.
+    Viewport,
+    /// `x`/`y` are an offset from this source's last resolved position.
+    Pointer,
+    /// `x`/`y` are an offset from the named element's top-left corner. This tree has
+    /// no DOM/element geometry model, so the offset is carried through unresolved
+    /// rather than computed -- see [`DispatchedPointerMove::unresolved_element`].
+    Element { element_id: String },
+}
+
+impl Default for PointerOrigin {
+    fn default() -> Self {
+        PointerOrigin::Viewport
+    }
+}
+
+/// One input source's kind, matching the W3C `type` discriminant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceType {
+    Pointer,
+    Key,
+    Wheel,
+    None,
+}
+
+/// A single item in one source's action list. Not every variant is valid for every
+/// [`SourceType`]; [`dispatch`] validates that.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ActionItem {
+    /// Valid on any source. Holds this source at its current state for `duration_ms`.
+    Pause {
+        #[serde(default)]
+        duration_ms: u64,
+    },
+    /// Pointer source only.
+    PointerMove {
+        x: f64,
+        y: f64,
+        #[serde(default)]
+        origin: PointerOrigin,
+        #[serde(default)]
+        duration_ms: u64,
+    },
+    /// Pointer source only.
+    PointerDown { button: u8 },
+    /// Pointer source only.
+    PointerUp { button: u8 },
+    /// Key source only. `value` is a normalized key value, e.g. `"a"` or `"Enter"`.
+    KeyDown { value: String },
+    /// Key source only.
+    KeyUp { value: String },
+    /// Wheel source only.
+    Scroll {
+        x: f64,
+        y: f64,
+        delta_x: f64,
+        delta_y: f64,
+        #[serde(default)]
+        duration_ms: u64,
+    },
+}
+
+impl ActionItem {
+    fn duration_ms(&self) -> u64 {
+        match self {
+            ActionItem::Pause { duration_ms } => *duration_ms,
+            ActionItem::PointerMove { duration_ms, .. } => *duration_ms,
+            ActionItem::Scroll { duration_ms, .. } => *duration_ms,
+            ActionItem::PointerDown { .. }
+            | ActionItem::PointerUp { .. }
+            | ActionItem::KeyDown { .. }
+            | ActionItem::KeyUp { .. } => 0,
+        }
+    }
+
+    fn matches_source(&self, source_type: SourceType) -> bool {
+        match (self, source_type) {
+            (ActionItem::Pause { .. }, _) => true,
+            (
+                ActionItem::PointerMove { .. } | ActionItem::PointerDown { .. } | ActionItem::PointerUp { .. },
+                SourceType::Pointer,
+            ) => true,
+            (ActionItem::KeyDown { .. } | ActionItem::KeyUp { .. }, SourceType::Key) => true,
+            (ActionItem::Scroll { .. }, SourceType::Wheel) => true,
+            _ => false,
+        }
+    }
+}
+
+/// One input source: an id (so e.g. `release_actions` can address it), its kind, and
+/// its ordered action list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionSequence {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub source_type: SourceType,
+    pub actions: Vec<ActionItem>,
+}
+
+/// Why a set of action sequences was rejected before (or during) dispatch.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ActionsError {
+    /// An [`ActionItem`] variant isn't valid for its source's [`SourceType`].
+    WrongItemForSource { source_id: String, tick: usize },
+    /// A `pointerDown`/`keyDown` repeated a button/key that was already held down.
+    AlreadyPressed { source_id: String, value: String },
+    /// A `pointerUp`/`keyUp` released a button/key that wasn't held down.
+    NotPressed { source_id: String, value: String },
+}
+
+impl std::fmt::Display for ActionsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ActionsError::WrongItemForSource { source_id, tick } => write!(
+                f,
+                "source '{source_id}' has an action at tick {tick} that isn't valid for its type"
+            ),
+            ActionsError::AlreadyPressed { source_id, value } => {
+                write!(f, "source '{source_id}' pressed '{value}' while already held down")
+            }
+            ActionsError::NotPressed { source_id, value } => {
+                write!(f, "source '{source_id}' released '{value}' which wasn't held down")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ActionsError {}
+
+/// One source's resolved contribution to a tick, ready to be turned into a real input
+/// event by a CDP layer.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum DispatchedAction {
+    Pause,
+    PointerMove(DispatchedPointerMove),
+    PointerDown { button: u8 },
+    PointerUp { button: u8 },
+    KeyDown { value: String },
+    KeyUp { value: String },
+    Scroll { delta_x: f64, delta_y: f64 },
+}
+
+/// A resolved (or, for [`PointerOrigin::Element`], unresolved) pointer move.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DispatchedPointerMove {
+    pub x: f64,
+    pub y: f64,
+    /// Set when the source's `origin` was [`PointerOrigin::Element`]: the crate has no
+    /// element geometry to resolve `x`/`y` against, so the element id is surfaced here
+    /// instead of silently treating the offset as a viewport-absolute position.
+    pub unresolved_element: Option<String>,
+}
+
+/// One tick's worth of dispatched actions, one per source, in source order.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DispatchedTick {
+    pub actions: Vec<(String, DispatchedAction)>,
+}
+
+/// Which buttons/keys are currently held down per source, so [`release`] can undo
+/// them in reverse order.
+#[derive(Debug, Clone, Default)]
+pub struct PressedState {
+    pointer_buttons: HashMap<String, Vec<u8>>,
+    keys: HashMap<String, Vec<String>>,
+    pointer_positions: HashMap<String, (f64, f64)>,
+}
+
+/// Dispatch `sources` tick by tick, validating each item against its source's
+/// [`SourceType`] and against `pressed`'s running down/up state (which callers should
+/// persist across `dispatch` calls for the lifetime of a session, e.g. on
+/// [`crate::webdriver::WebDriverServer`]'s per-session state).
+///
+/// Sources shorter than the longest one are padded with implicit pauses, per the W3C
+/// "extend an action sequence" step.
+pub fn dispatch(
+    sources: &[ActionSequence],
+    pressed: &mut PressedState,
+) -> Result<Vec<DispatchedTick>, ActionsError> {
+    let tick_count = sources.iter().map(|s| s.actions.len()).max().unwrap_or(0);
+
+    let mut ticks = Vec::with_capacity(tick_count);
+    for tick in 0..tick_count {
+        let mut actions = Vec::with_capacity(sources.len());
+        for source in sources {
+            let item = source.actions.get(tick).cloned().unwrap_or(ActionItem::Pause { duration_ms: 0 });
+            if !item.matches_source(source.source_type) {
+                return Err(ActionsError::WrongItemForSource {
+                    source_id: source.id.clone(),
+                    tick,
+                });
+            }
+            let dispatched = resolve(&source.id, &item, pressed)?;
+            actions.push((source.id.clone(), dispatched));
+        }
+        ticks.push(DispatchedTick { actions });
+    }
+
+    Ok(ticks)
+}
+
+/// The wall-clock duration of each returned tick: the maximum `duration_ms` any source
+/// requested at that tick index (after padding shorter sources with zero-duration
+/// pauses), per the W3C "tick duration" step.
+pub fn tick_durations(sources: &[ActionSequence]) -> Vec<u64> {
+    let tick_count = sources.iter().map(|s| s.actions.len()).max().unwrap_or(0);
+    (0..tick_count)
+        .map(|tick| {
+            sources
+                .iter()
+                .filter_map(|s| s.actions.get(tick))
+                .map(ActionItem::duration_ms)
+                .max()
+                .unwrap_or(0)
+        })
+        .collect()
+}
+
+fn resolve(
+    source_id: &str,
+    item: &ActionItem,
+    pressed: &mut PressedState,
+) -> Result<DispatchedAction, ActionsError> {
+    match item {
+        ActionItem::Pause { .. } => Ok(DispatchedAction::Pause),
+        ActionItem::PointerMove { x, y, origin, .. } => {
+            let (resolved_x, resolved_y, unresolved_element) = match origin {
+                PointerOrigin::Viewport => (*x, *y, None),
+                PointerOrigin::Pointer => {
+                    let (last_x, last_y) = pressed
+                        .pointer_positions
+                        .get(source_id)
+                        .copied()
+                        .unwrap_or((0.0, 0.0));
+                    (last_x + x, last_y + y, None)
+                }
+                PointerOrigin::Element { element_id } => (*x, *y, Some(element_id.clone())),
+            };
+            if unresolved_element.is_none() {
+                pressed
+                    .pointer_positions
+                    .insert(source_id.to_string(), (resolved_x, resolved_y));
+            }
+            Ok(DispatchedAction::PointerMove(DispatchedPointerMove {
+                x: resolved_x,
+                y: resolved_y,
+                unresolved_element,
+            }))
+        }
+        ActionItem::PointerDown { button } => {
+            let held = pressed.pointer_buttons.entry(source_id.to_string()).or_default();
+            if held.contains(button) {
+                return Err(ActionsError::AlreadyPressed {
+                    source_id: source_id.to_string(),
+                    value: button.to_string(),
+                });
+            }
+            held.push(*button);
+            Ok(DispatchedAction::PointerDown { button: *button })
+        }
+        ActionItem::PointerUp { button } => {
+            let held = pressed.pointer_buttons.entry(source_id.to_string()).or_default();
+            let Some(pos) = held.iter().position(|b| b == button) else {
+                return Err(ActionsError::NotPressed {
+                    source_id: source_id.to_string(),
+                    value: button.to_string(),
+                });
+            };
+            held.remove(pos);
+            Ok(DispatchedAction::PointerUp { button: *button })
+        }
+        ActionItem::KeyDown { value } => {
+            let held = pressed.keys.entry(source_id.to_string()).or_default();
+            if held.iter().any(|k| k == value) {
+                return Err(ActionsError::AlreadyPressed {
+                    source_id: source_id.to_string(),
+                    value: value.clone(),
+                });
+            }
+            held.push(value.clone());
+            Ok(DispatchedAction::KeyDown { value: value.clone() })
+        }
+        ActionItem::KeyUp { value } => {
+            let held = pressed.keys.entry(source_id.to_string()).or_default();
+            let Some(pos) = held.iter().position(|k| k == value) else {
+                return Err(ActionsError::NotPressed {
+                    source_id: source_id.to_string(),
+                    value: value.clone(),
+                });
+            };
+            held.remove(pos);
+            Ok(DispatchedAction::KeyUp { value: value.clone() })
+        }
+        ActionItem::Scroll { delta_x, delta_y, .. } => Ok(DispatchedAction::Scroll {
+            delta_x: *delta_x,
+            delta_y: *delta_y,
+        }),
+    }
+}
+
+/// Undo every currently-depressed key/button across all sources, releasing each
+/// source's own presses in reverse (most-recently-pressed first) order, per the W3C
+/// "release actions" algorithm. Clears `pressed` afterwards.
+pub fn release(pressed: &mut PressedState) -> Vec<(String, DispatchedAction)> {
+    let mut released = Vec::new();
+
+    for (source_id, buttons) in pressed.pointer_buttons.iter_mut() {
+        while let Some(button) = buttons.pop() {
+            released.push((source_id.clone(), DispatchedAction::PointerUp { button }));
+        }
+    }
+    for (source_id, keys) in pressed.keys.iter_mut() {
+        while let Some(value) = keys.pop() {
+            released.push((source_id.clone(), DispatchedAction::KeyUp { value }));
+        }
+    }
+
+    pressed.pointer_buttons.clear();
+    pressed.keys.clear();
+    released
+}
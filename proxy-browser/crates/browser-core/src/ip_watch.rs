@@ -0,0 +1,133 @@
+//! Background public-IP watch loop
+//!
+//! [`crate::http_client::PublicIpDetector`] and the IP provider commands are all
+//! one-shot: a caller has to poll to notice the detected public IP has changed.
+//! [`IpWatcher`] instead runs a DNS-updater-style background task that re-detects the
+//! public IP on a configurable interval, debouncing transient provider disagreements so
+//! a change is only reported once it's been observed on two consecutive polls.
+
+use crate::http_client::PublicIpDetector;
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{oneshot, RwLock};
+use tokio::task::JoinHandle;
+
+/// A detected public IP at a point in time. `country`/`isp` are filled in by the
+/// caller's own [`GeoLookup`] (e.g. against the active virtual-IP-to-country mapping),
+/// since resolving geolocation is outside this crate's scope.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IpWatchSnapshot {
+    pub ip: String,
+    pub country: Option<String>,
+    pub isp: Option<String>,
+}
+
+/// A confirmed change between two [`IpWatchSnapshot`]s, as reported by [`IpWatcher`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IpChangeEvent {
+    /// The previously confirmed snapshot, or `None` if this is the first one observed.
+    pub previous: Option<IpWatchSnapshot>,
+    pub current: IpWatchSnapshot,
+}
+
+/// Resolves the country/ISP for a freshly detected public IP. Implemented by the
+/// caller so [`IpWatcher`] doesn't need to know how the active virtual-IP-to-country
+/// mapping (or any other geolocation source) actually works.
+#[async_trait]
+pub trait GeoLookup: Send + Sync {
+    async fn lookup(&self, ip: &str) -> (Option<String>, Option<String>);
+}
+
+/// A [`GeoLookup`] that reports no geolocation, for callers that only care about raw
+/// IP-address changes.
+pub struct NoopGeoLookup;
+
+#[async_trait]
+impl GeoLookup for NoopGeoLookup {
+    async fn lookup(&self, _ip: &str) -> (Option<String>, Option<String>) {
+        (None, None)
+    }
+}
+
+/// Runs [`PublicIpDetector::detect`] on an interval and reports debounced
+/// [`IpChangeEvent`]s through a caller-supplied callback, guarded by a cancellation
+/// handle so at most one watch loop runs at a time.
+#[derive(Default)]
+pub struct IpWatcher {
+    cancel: RwLock<Option<oneshot::Sender<()>>>,
+    task: RwLock<Option<JoinHandle<()>>>,
+}
+
+impl IpWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether a watch loop is currently running.
+    pub async fn is_running(&self) -> bool {
+        self.task.read().await.is_some()
+    }
+
+    /// Start polling every `interval_secs` seconds, calling `on_change` with a debounced
+    /// [`IpChangeEvent`] whenever the detected IP/country/ISP changes. Replaces any
+    /// watch loop already running.
+    pub async fn start<G, F>(&self, interval_secs: u64, geo: Arc<G>, on_change: F)
+    where
+        G: GeoLookup + 'static,
+        F: Fn(IpChangeEvent) + Send + Sync + 'static,
+    {
+        self.stop().await;
+
+        let (cancel_tx, mut cancel_rx) = oneshot::channel();
+        let interval = Duration::from_secs(interval_secs.max(1));
+
+        let handle = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            let mut confirmed: Option<IpWatchSnapshot> = None;
+            let mut pending: Option<IpWatchSnapshot> = None;
+
+            loop {
+                tokio::select! {
+                    _ = &mut cancel_rx => break,
+                    _ = ticker.tick() => {
+                        let Ok(info) = PublicIpDetector::detect().await else { continue };
+                        let (country, isp) = geo.lookup(&info.ip).await;
+                        let snapshot = IpWatchSnapshot { ip: info.ip, country, isp };
+
+                        if confirmed.as_ref() == Some(&snapshot) {
+                            // Back to the last confirmed value; drop any pending candidate.
+                            pending = None;
+                            continue;
+                        }
+
+                        if pending.as_ref() == Some(&snapshot) {
+                            // Seen on two consecutive polls: confirm and report it.
+                            let previous = confirmed.replace(snapshot.clone());
+                            pending = None;
+                            on_change(IpChangeEvent { previous, current: snapshot });
+                        } else {
+                            // First time seeing this candidate; wait for the next poll
+                            // to confirm it before reporting, so a transient provider
+                            // disagreement doesn't spam the callback.
+                            pending = Some(snapshot);
+                        }
+                    }
+                }
+            }
+        });
+
+        *self.cancel.write().await = Some(cancel_tx);
+        *self.task.write().await = Some(handle);
+    }
+
+    /// Cancel the running watch loop, if any, and wait for it to exit.
+    pub async fn stop(&self) {
+        if let Some(cancel) = self.cancel.write().await.take() {
+            let _ = cancel.send(());
+        }
+        if let Some(task) = self.task.write().await.take() {
+            let _ = task.await;
+        }
+    }
+}
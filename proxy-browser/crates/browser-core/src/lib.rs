@@ -7,6 +7,8 @@
 //! - `mvp` (default): Minimal viable product with core features
 //! - `full`: All features enabled
 //! - `chromium`: Chromium browser engine integration
+//! - `gecko`: Firefox/Marionette browser engine integration (requires `chromium` for
+//!   shared tab/capability types)
 //! - `automation`: Browser automation capabilities
 //! - `advanced-privacy`: Advanced privacy features
 //! - `content-enhancement`: Reader mode, content transformation
@@ -32,27 +34,37 @@ pub mod browser_profile;
 pub mod config_manager;
 pub mod http_client;
 pub mod proxy;
+pub mod proxy_pool;
 pub mod request;
 pub mod screenshot;
+pub mod secret;
 pub mod security;
+pub mod share;
 pub mod storage;
 pub mod tab_manager;
 
 // Core exports
 pub use browser_profile::{BrowserProfile, BrowserProfileManager, ProfileSettings};
+pub use secret::{Secret, SecretError, SecretErrorKind, SecretKey};
 pub use config_manager::{
     AppConfig, ConfigManager, FeatureFlags, GeneralConfig, LoggingConfig,
     NetworkConfig as AppNetworkConfig, PerformanceConfig, PrivacyConfig, ProxyConfig,
     StorageConfig,
 };
 pub use http_client::{HttpClient, PublicIpDetector, PublicIpInfo};
-pub use proxy::{FreeProxy, ProxyManager, ProxySettings, ProxyTestResult, ProxyType};
+pub use proxy::{ProxySettings, ProxyType};
+pub use proxy_pool::{ProxyHealth, ProxyPool, ProxyPoolStatus, ProxyRotationStrategy};
 pub use request::{
-    HttpMethod, RequestBody, RequestBuilder, RequestConfig, RequestError, RequestErrorKind,
-    RequestManager, RequestResponse,
+    parse_auth_tokens, resolve_url_from_location, AuthToken, ClientIdentity, HttpMethod,
+    RequestBody, RequestBuilder, RequestConfig, RequestError, RequestErrorKind, RequestManager,
+    RequestResponse,
+};
+pub use screenshot::{
+    SafetyAction, SafetyCategory, SafetyCheck, ScreenshotClip, ScreenshotFormat, ScreenshotManager,
+    ScreenshotOptions, ScreenshotResult,
 };
-pub use screenshot::{ScreenshotFormat, ScreenshotManager, ScreenshotOptions, ScreenshotResult};
 pub use security::{BookmarkInput, ProxyInput, SecurityManager};
+pub use share::{ShareError, ShareErrorKind, ShareStore, ShareStoreConfig};
 pub use storage::{
     Bookmark, BrowserSession, Cookie, ExportOptions, HistoryEntry, ImportExportStats,
     ImportOptions, ScrollPosition, SessionManager, SessionProxyConfig, SessionSettings,
@@ -64,11 +76,15 @@ pub use tab_manager::TabIPManager;
 // MVP Extended Modules (Part of default MVP)
 // ============================================================================
 pub mod backup;
+pub mod backup_crypto;
+pub mod backup_destination;
 pub mod browser_controls;
 pub mod browser_tab_manager;
+pub mod chunk_store;
 pub mod error_recovery;
 pub mod fingerprint;
 pub mod free_ip_providers;
+pub mod ip_watch;
 pub mod local_proxy;
 pub mod pac_server;
 pub mod proxy_rotation;
@@ -78,19 +94,33 @@ pub mod tab_isolation;
 pub mod webview_manager;
 
 // MVP Extended exports
-pub use backup::{AutoBackupSettings, BackupData, BackupInfo, BackupManager, BackupOptions};
+pub use backup::{
+    AutoBackupSettings, AutoVerifySettings, BackupData, BackupInfo, BackupManager, BackupOptions,
+    BackupScheduler, PruneOptions, PruneReport, SyncCacheEntry, SyncReport, VerifyError,
+    VerifyReport,
+};
+pub use backup_crypto::{
+    generate_recovery_keypair, Argon2Params, BackupCryptoError, BackupCryptoErrorKind,
+    EncryptedChunk, EncryptedPayload, EncryptionHeader, RecoveryWrappedKey,
+};
+pub use backup_destination::{
+    BackupDestination, LocalFilesystemDestination, ObjectMetadata, RemoteDestinationConfig,
+    S3Destination, S3DestinationConfig,
+};
 pub use browser_controls::{
     BrowserController, BrowserSettings, BrowserState, ContextInfo, ContextMenuItem,
     ContextMenuItemType, ContextMenuManager, ContextType, DownloadItem, DownloadManager,
     DownloadState, HistoryItem, WebRtcPolicy,
 };
 pub use browser_tab_manager::{BrowserTab, BrowserTabManager, CreateTabConfig, TabStats};
+pub use chunk_store::{ChunkStore, ChunkVerifyStatus, ChunkingConfig};
 pub use error_recovery::{
     CrashPrediction, ErrorCategory, ErrorRecoveryConfig, ErrorRecoveryManager, ErrorSeverity,
     ErrorStats, OperationMetrics, RecoveryResult, RecoveryStrategy,
 };
 pub use fingerprint::BrowserFingerprint;
 pub use free_ip_providers::{FreeIpProvider, FreeIpProviderManager, ProxyFilter};
+pub use ip_watch::{GeoLookup, IpChangeEvent, IpWatchSnapshot, IpWatcher, NoopGeoLookup};
 pub use local_proxy::{
     InterceptedRequest, LocalProxyManager, LocalProxyServer, ModificationRule, NetworkInterceptor,
     ProxyConnection, RequestModifications, WebSocketInterception, WebSocketProxyHandler,
@@ -126,11 +156,63 @@ pub use performance_optimizer::{
 // ============================================================================
 #[cfg(feature = "chromium")]
 pub mod chromium_engine;
+#[cfg(feature = "chromium")]
+pub mod browser_engine;
+#[cfg(feature = "chromium")]
+pub mod chromium_cookies;
+#[cfg(feature = "chromium")]
+pub mod chromium_detect;
+#[cfg(feature = "chromium")]
+pub mod chromium_devtools;
+#[cfg(feature = "chromium")]
+pub mod chromium_fetcher;
+#[cfg(feature = "chromium")]
+pub mod chromium_http_cache;
+#[cfg(feature = "chromium")]
+pub mod chromium_interception;
+#[cfg(feature = "chromium")]
+pub mod chromium_pool;
+#[cfg(feature = "chromium")]
+pub mod input_actions;
+#[cfg(feature = "chromium")]
+pub mod webdriver;
+#[cfg(feature = "gecko")]
+pub mod gecko_engine;
 
 #[cfg(feature = "chromium")]
 pub use chromium_engine::{
     BrowserEngineManager, BrowserEngineType, ChromiumEngine, ChromiumEngineConfig, ChromiumTab,
-    EngineCapabilities,
+    ContentSettings, EngineCapabilities, LoadedExtension, TabEvent,
+};
+#[cfg(feature = "chromium")]
+pub use browser_engine::{BrowserEngine, EngineError, EngineErrorKind};
+#[cfg(feature = "chromium")]
+pub use chromium_cookies::{third_party_cookie_allowed, ThirdPartyCookieGrants};
+#[cfg(feature = "gecko")]
+pub use gecko_engine::{GeckoEngine, GeckoEngineConfig};
+#[cfg(feature = "chromium")]
+pub use chromium_detect::{
+    default_executable, is_chrome_available, ChromeLocator, DetectionError, DetectionErrorKind,
+};
+#[cfg(feature = "chromium")]
+pub use chromium_devtools::{
+    launch_with_devtools, DevToolsDiscoveryOptions, DevToolsError, DevToolsErrorKind,
+    LaunchedProcess,
+};
+#[cfg(feature = "chromium")]
+pub use chromium_fetcher::{fetch_chromium, FetcherOptions};
+#[cfg(feature = "chromium")]
+pub use chromium_http_cache::{CachingRequestFilter, HttpCache, HttpCacheConfig};
+#[cfg(feature = "chromium")]
+pub use chromium_interception::{FilterAction, InterceptedRequest, InterceptedResponse, RequestFilter};
+#[cfg(feature = "chromium")]
+pub use chromium_pool::{ChromiumPool, ChromiumPoolConfig, PoolStatus, PooledEngine, NUMBER_OF_INSTANCES};
+#[cfg(feature = "chromium")]
+pub use webdriver::{WebDriverCookie, WebDriverError, WebDriverErrorKind, WebDriverServer};
+#[cfg(feature = "chromium")]
+pub use input_actions::{
+    ActionItem, ActionSequence, ActionsError, DispatchedAction, DispatchedPointerMove,
+    DispatchedTick, PointerOrigin, PressedState, SourceType,
 };
 
 // Stub for when chromium is not enabled
@@ -0,0 +1,106 @@
+//! Proxy configuration types shared across browser engines
+//!
+//! [`ProxySettings`] is the engine-agnostic proxy description threaded through
+//! [`crate::chromium_engine`], [`crate::gecko_engine`], and [`crate::browser_engine`]'s
+//! `create_tab`/`set_tab_proxy` calls.
+
+use serde::{Deserialize, Serialize};
+
+/// Which proxy protocol a [`ProxySettings`] connects over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProxyType {
+    Http,
+    Https,
+    Socks4,
+    Socks5,
+}
+
+/// An engine-agnostic proxy configuration, parsed from a WebDriver capability (see
+/// [`crate::chromium_engine::ChromiumEngineConfig::from_capabilities`]) or a
+/// `scheme://[user:pass@]host:port` URL.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProxySettings {
+    pub proxy_type: ProxyType,
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub dns_servers: Vec<String>,
+    pub bypass_list: Vec<String>,
+}
+
+impl ProxySettings {
+    /// Parse a `scheme://[user:pass@]host:port` URL into a [`ProxySettings`], pulling
+    /// any embedded credentials out into `username`/`password` rather than leaving
+    /// them in the host string.
+    pub fn parse_url(url: &str) -> Result<Self, String> {
+        let parsed = url::Url::parse(url).map_err(|e| format!("invalid proxy URL '{url}': {e}"))?;
+
+        let proxy_type = match parsed.scheme() {
+            "http" => ProxyType::Http,
+            "https" => ProxyType::Https,
+            "socks4" => ProxyType::Socks4,
+            "socks5" | "socks" => ProxyType::Socks5,
+            other => return Err(format!("unsupported proxy scheme '{other}'")),
+        };
+
+        let host = parsed.host_str().map(|host| host.to_string());
+        let port = parsed.port();
+        let username = if parsed.username().is_empty() {
+            None
+        } else {
+            Some(parsed.username().to_string())
+        };
+        let password = parsed.password().map(|password| password.to_string());
+
+        Ok(Self {
+            proxy_type,
+            host,
+            port,
+            username,
+            password,
+            dns_servers: Vec::new(),
+            bypass_list: Vec::new(),
+        })
+    }
+
+    /// Whether connecting through this proxy requires answering an auth challenge.
+    pub fn requires_auth(&self) -> bool {
+        self.username.is_some()
+    }
+
+    /// The `--proxy-server=` value Chromium/Chrome accepts. Deliberately omits any
+    /// credentials, which aren't valid in `--proxy-server`; [`Self::connect_url`] is
+    /// the form that includes them.
+    pub fn proxy_server_arg(&self) -> Option<String> {
+        let host = self.host.as_ref()?;
+        let port = self.port?;
+        let scheme = match self.proxy_type {
+            ProxyType::Http => "http",
+            ProxyType::Https => "https",
+            ProxyType::Socks4 => "socks4",
+            ProxyType::Socks5 => "socks5",
+        };
+        Some(format!("{scheme}://{host}:{port}"))
+    }
+
+    /// The full `scheme://[user:pass@]host:port` URL for this proxy, suitable for a
+    /// client library (e.g. `reqwest::Proxy::all`) that connects through it directly
+    /// rather than handing it to Chromium's own `--proxy-server` flag. Unlike
+    /// [`Self::proxy_server_arg`], this includes embedded credentials when present.
+    pub fn connect_url(&self) -> Option<String> {
+        let host = self.host.as_ref()?;
+        let port = self.port?;
+        let scheme = match self.proxy_type {
+            ProxyType::Http => "http",
+            ProxyType::Https => "https",
+            ProxyType::Socks4 => "socks4",
+            ProxyType::Socks5 => "socks5",
+        };
+        match (&self.username, &self.password) {
+            (Some(user), Some(pass)) => Some(format!("{scheme}://{user}:{pass}@{host}:{port}")),
+            (Some(user), None) => Some(format!("{scheme}://{user}@{host}:{port}")),
+            _ => Some(format!("{scheme}://{host}:{port}")),
+        }
+    }
+}
@@ -0,0 +1,390 @@
+//! Per-tab proxy pool with rotation and health checks
+//!
+//! [`crate::chromium_engine::BrowserEngineManager::set_proxy`] only ever holds a single
+//! proxy shared by the whole engine. [`ProxyPool`] instead holds an ordered list of
+//! [`ProxySettings`] and assigns one to each new tab according to a
+//! [`ProxyRotationStrategy`], skipping entries a background
+//! [`ProxyPool::start_health_checks`] loop has marked unavailable.
+
+use crate::proxy::ProxySettings;
+use rand::Rng;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::{oneshot, RwLock};
+use tokio::task::JoinHandle;
+
+/// How [`ProxyPool::assign`] picks the next proxy for a new tab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProxyRotationStrategy {
+    /// Cycle through healthy proxies in pool order.
+    RoundRobin,
+    /// Pick a healthy proxy uniformly at random.
+    Random,
+    /// The same domain always gets the same healthy proxy, so a site doesn't see its
+    /// session hop exit nodes mid-visit.
+    StickyPerDomain,
+    /// Pick a healthy proxy with probability proportional to its [`ProxyHealth::score`].
+    WeightedByHealth,
+    /// Pick the healthy proxy that was assigned longest ago (or never).
+    LeastRecentlyUsed,
+}
+
+/// Default weight given to history when folding a new probe into [`ProxyHealth::score`]
+/// -- see [`ProxyPool::set_health_decay`].
+const DEFAULT_HEALTH_DECAY: f64 = 0.7;
+
+/// Observed health of one [`ProxyPool`] entry, refreshed by the background health-check
+/// loop started with [`ProxyPool::start_health_checks`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ProxyHealth {
+    /// Whether the last probe succeeded. Optimistically `true` until first checked, so
+    /// a freshly added proxy isn't skipped before its first health check runs.
+    pub available: bool,
+    /// Unix timestamp (seconds) of the most recent probe, if any.
+    pub last_checked: Option<u64>,
+    /// Round-trip time of the most recent successful probe, in milliseconds.
+    pub latency_ms: Option<u64>,
+    /// Exponentially-decayed health score in `[0, 1]`, folding in both success/failure
+    /// and normalized latency: `sample = success as f64 / (1 + latency_seconds)`, then
+    /// `score = decay * score + (1 - decay) * sample` so stale results fade. Starts at
+    /// `1.0`, optimistic like `available`, until the first probe updates it.
+    pub score: f64,
+    /// How many probes have contributed to `score`.
+    pub sample_count: u32,
+    /// Unix timestamp (seconds) this entry was last handed out by
+    /// [`ProxyPool::assign`], for [`ProxyRotationStrategy::LeastRecentlyUsed`].
+    pub last_used: Option<u64>,
+}
+
+impl Default for ProxyHealth {
+    fn default() -> Self {
+        Self {
+            available: true,
+            last_checked: None,
+            latency_ms: None,
+            score: 1.0,
+            sample_count: 0,
+            last_used: None,
+        }
+    }
+}
+
+impl ProxyHealth {
+    /// Fold a new probe result into `score` via exponential decay, and update
+    /// `available`/`last_checked`/`latency_ms`/`sample_count` to match.
+    fn record_probe(&mut self, available: bool, latency_ms: Option<u64>, now: u64, decay: f64) {
+        let latency_seconds = latency_ms.map(|ms| ms as f64 / 1000.0).unwrap_or(1.0);
+        let sample = if available {
+            1.0 / (1.0 + latency_seconds)
+        } else {
+            0.0
+        };
+
+        self.score = decay * self.score + (1.0 - decay) * sample;
+        self.sample_count += 1;
+        self.available = available;
+        self.last_checked = Some(now);
+        self.latency_ms = latency_ms;
+    }
+}
+
+/// One pooled [`ProxySettings`] plus its latest [`ProxyHealth`], as returned by
+/// [`ProxyPool::get_status`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProxyPoolStatus {
+    pub proxy: ProxySettings,
+    pub health: ProxyHealth,
+}
+
+struct ProxyPoolEntry {
+    settings: ProxySettings,
+    health: ProxyHealth,
+}
+
+/// An ordered pool of proxies assigned to new tabs per [`ProxyRotationStrategy`], with
+/// an opt-in background health-check loop that temporarily skips unavailable entries
+/// during assignment until they recover.
+pub struct ProxyPool {
+    entries: RwLock<Vec<ProxyPoolEntry>>,
+    strategy: RwLock<ProxyRotationStrategy>,
+    round_robin_cursor: RwLock<usize>,
+    sticky_assignments: RwLock<HashMap<String, ProxySettings>>,
+    health_cancel: RwLock<Option<oneshot::Sender<()>>>,
+    health_task: RwLock<Option<JoinHandle<()>>>,
+    health_decay: RwLock<f64>,
+}
+
+impl Default for ProxyPool {
+    fn default() -> Self {
+        Self::new(ProxyRotationStrategy::RoundRobin)
+    }
+}
+
+impl ProxyPool {
+    pub fn new(strategy: ProxyRotationStrategy) -> Self {
+        Self {
+            entries: RwLock::new(Vec::new()),
+            strategy: RwLock::new(strategy),
+            round_robin_cursor: RwLock::new(0),
+            sticky_assignments: RwLock::new(HashMap::new()),
+            health_cancel: RwLock::new(None),
+            health_task: RwLock::new(None),
+            health_decay: RwLock::new(DEFAULT_HEALTH_DECAY),
+        }
+    }
+
+    /// Weight given to history (vs. the newest probe) when folding a probe result into
+    /// each entry's [`ProxyHealth::score`]. Defaults to [`DEFAULT_HEALTH_DECAY`].
+    pub async fn health_decay(&self) -> f64 {
+        *self.health_decay.read().await
+    }
+
+    /// Change the health-score decay factor used by future probes. Clamped to `[0, 1)`
+    /// so the newest sample always contributes at least something.
+    pub async fn set_health_decay(&self, decay: f64) {
+        *self.health_decay.write().await = decay.clamp(0.0, 0.999);
+    }
+
+    /// Remove every entry whose [`ProxyHealth::score`] is below `min_score`. Returns
+    /// the removed proxies.
+    pub async fn prune_dead_proxies(&self, min_score: f64) -> Vec<ProxySettings> {
+        let mut entries = self.entries.write().await;
+        let mut pruned = Vec::new();
+        entries.retain(|entry| {
+            if entry.health.score < min_score {
+                pruned.push(entry.settings.clone());
+                false
+            } else {
+                true
+            }
+        });
+        pruned
+    }
+
+    /// Append a proxy to the end of the pool, optimistically healthy until the next
+    /// health check runs.
+    pub async fn add_proxy(&self, proxy: ProxySettings) {
+        self.entries.write().await.push(ProxyPoolEntry {
+            settings: proxy,
+            health: ProxyHealth::default(),
+        });
+    }
+
+    /// Remove every pool entry equal to `proxy`. Returns whether anything was removed.
+    pub async fn remove_proxy(&self, proxy: &ProxySettings) -> bool {
+        let mut entries = self.entries.write().await;
+        let before = entries.len();
+        entries.retain(|entry| &entry.settings != proxy);
+        entries.len() != before
+    }
+
+    pub async fn rotation_strategy(&self) -> ProxyRotationStrategy {
+        *self.strategy.read().await
+    }
+
+    pub async fn set_rotation_strategy(&self, strategy: ProxyRotationStrategy) {
+        *self.strategy.write().await = strategy;
+    }
+
+    /// Assign the next proxy for a new tab per the configured [`ProxyRotationStrategy`],
+    /// skipping any entry the health check has marked unavailable. `domain` is only
+    /// consulted by [`ProxyRotationStrategy::StickyPerDomain`] -- pass `None` for the
+    /// other strategies or when the destination isn't known yet. Returns `None` if the
+    /// pool is empty or every entry is currently unavailable.
+    pub async fn assign(&self, domain: Option<&str>) -> Option<ProxySettings> {
+        let picked = {
+            let entries = self.entries.read().await;
+            let healthy: Vec<&ProxyPoolEntry> =
+                entries.iter().filter(|e| e.health.available).collect();
+            if healthy.is_empty() {
+                return None;
+            }
+
+            match *self.strategy.read().await {
+                ProxyRotationStrategy::RoundRobin => {
+                    let mut cursor = self.round_robin_cursor.write().await;
+                    let picked = healthy[*cursor % healthy.len()].settings.clone();
+                    *cursor = cursor.wrapping_add(1);
+                    Some(picked)
+                }
+                ProxyRotationStrategy::Random => {
+                    let index = rand::thread_rng().gen_range(0..healthy.len());
+                    Some(healthy[index].settings.clone())
+                }
+                ProxyRotationStrategy::StickyPerDomain => 'sticky: {
+                    let Some(domain) = domain else {
+                        break 'sticky Some(healthy[0].settings.clone());
+                    };
+
+                    let mut sticky = self.sticky_assignments.write().await;
+                    if let Some(existing) = sticky.get(domain) {
+                        if let Some(entry) = healthy.iter().find(|e| &e.settings == existing) {
+                            break 'sticky Some(entry.settings.clone());
+                        }
+                        // The sticky proxy went unhealthy or was removed; fall through
+                        // and re-pin this domain to a currently healthy one.
+                    }
+
+                    let mut hasher = DefaultHasher::new();
+                    domain.hash(&mut hasher);
+                    let picked = healthy[(hasher.finish() as usize) % healthy.len()].settings.clone();
+                    sticky.insert(domain.to_string(), picked.clone());
+                    Some(picked)
+                }
+                ProxyRotationStrategy::WeightedByHealth => {
+                    let total_weight: f64 = healthy.iter().map(|e| e.health.score.max(0.0)).sum();
+                    if total_weight <= 0.0 {
+                        // No entry has a positive score yet (e.g. none have been
+                        // probed); fall back to a uniform pick rather than always
+                        // returning the first entry.
+                        let index = rand::thread_rng().gen_range(0..healthy.len());
+                        Some(healthy[index].settings.clone())
+                    } else {
+                        let mut remaining = rand::thread_rng().gen_range(0.0..total_weight);
+                        let mut picked = healthy.last().unwrap().settings.clone();
+                        for entry in &healthy {
+                            let weight = entry.health.score.max(0.0);
+                            if remaining < weight {
+                                picked = entry.settings.clone();
+                                break;
+                            }
+                            remaining -= weight;
+                        }
+                        Some(picked)
+                    }
+                }
+                ProxyRotationStrategy::LeastRecentlyUsed => healthy
+                    .iter()
+                    .min_by_key(|e| e.health.last_used.unwrap_or(0))
+                    .map(|e| e.settings.clone()),
+            }
+        };
+
+        if let Some(picked) = &picked {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            let mut entries = self.entries.write().await;
+            if let Some(entry) = entries.iter_mut().find(|e| &e.settings == picked) {
+                entry.health.last_used = Some(now);
+            }
+        }
+
+        picked
+    }
+
+    /// Each pooled proxy's settings and latest observed health, in pool order.
+    pub async fn get_status(&self) -> Vec<ProxyPoolStatus> {
+        self.entries
+            .read()
+            .await
+            .iter()
+            .map(|entry| ProxyPoolStatus {
+                proxy: entry.settings.clone(),
+                health: entry.health,
+            })
+            .collect()
+    }
+
+    /// Whether a background health-check loop is currently running.
+    pub async fn is_health_check_running(&self) -> bool {
+        self.health_task.read().await.is_some()
+    }
+
+    /// Start probing every pooled proxy's connectivity every `interval_secs` seconds by
+    /// connecting through it and fetching `probe_url`, marking an entry unavailable
+    /// (and skipped by [`Self::assign`]) until a later probe succeeds again. Replaces
+    /// any health-check loop already running. Requires `self` behind an `Arc` since the
+    /// loop outlives this call.
+    pub async fn start_health_checks(self: &Arc<Self>, interval_secs: u64, probe_url: String) {
+        self.stop_health_checks().await;
+
+        let (cancel_tx, mut cancel_rx) = oneshot::channel();
+        let interval = Duration::from_secs(interval_secs.max(1));
+        let pool = Arc::clone(self);
+
+        let handle = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                tokio::select! {
+                    _ = &mut cancel_rx => break,
+                    _ = ticker.tick() => pool.run_health_check_once(&probe_url).await,
+                }
+            }
+        });
+
+        *self.health_cancel.write().await = Some(cancel_tx);
+        *self.health_task.write().await = Some(handle);
+    }
+
+    /// Cancel the running health-check loop, if any, and wait for it to exit.
+    pub async fn stop_health_checks(&self) {
+        if let Some(cancel) = self.health_cancel.write().await.take() {
+            let _ = cancel.send(());
+        }
+        if let Some(task) = self.health_task.write().await.take() {
+            let _ = task.await;
+        }
+    }
+
+    async fn run_health_check_once(&self, probe_url: &str) {
+        let snapshot: Vec<ProxySettings> = self
+            .entries
+            .read()
+            .await
+            .iter()
+            .map(|entry| entry.settings.clone())
+            .collect();
+
+        let decay = self.health_decay().await;
+
+        for proxy in snapshot {
+            let (available, latency_ms) = Self::probe(&proxy, probe_url).await;
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+
+            let mut entries = self.entries.write().await;
+            if let Some(entry) = entries.iter_mut().find(|entry| entry.settings == proxy) {
+                entry.health.record_probe(available, latency_ms, now, decay);
+            }
+        }
+    }
+
+    /// A lightweight connectivity probe: connect through `proxy` and fetch `probe_url`,
+    /// treating any non-success response or request error as unavailable. Returns
+    /// whether it succeeded and, if so, its round-trip latency.
+    async fn probe(proxy: &ProxySettings, probe_url: &str) -> (bool, Option<u64>) {
+        let client = proxy
+            .connect_url()
+            .and_then(|url| reqwest::Proxy::all(url).ok())
+            .and_then(|proxy| {
+                Client::builder()
+                    .proxy(proxy)
+                    .timeout(Duration::from_secs(5))
+                    .build()
+                    .ok()
+            });
+
+        let Some(client) = client else {
+            return (false, None);
+        };
+
+        let start = Instant::now();
+        let available = client
+            .get(probe_url)
+            .send()
+            .await
+            .map(|response| response.status().is_success())
+            .unwrap_or(false);
+
+        (available, available.then(|| start.elapsed().as_millis() as u64))
+    }
+}
@@ -0,0 +1,804 @@
+//! HTTP Request Module
+//!
+//! Provides a higher-level HTTP client on top of `reqwest`, including:
+//! - A fluent `RequestBuilder` for constructing requests
+//! - Typed request/response structures
+//! - An in-memory response cache with ETag/Cache-Control revalidation
+//! - Manual redirect handling with RFC 3986 location resolution
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+/// HTTP methods supported by the request builder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum HttpMethod {
+    Get,
+    Post,
+    Put,
+    Delete,
+    Patch,
+    Head,
+    Options,
+}
+
+impl From<HttpMethod> for reqwest::Method {
+    fn from(method: HttpMethod) -> Self {
+        match method {
+            HttpMethod::Get => reqwest::Method::GET,
+            HttpMethod::Post => reqwest::Method::POST,
+            HttpMethod::Put => reqwest::Method::PUT,
+            HttpMethod::Delete => reqwest::Method::DELETE,
+            HttpMethod::Patch => reqwest::Method::PATCH,
+            HttpMethod::Head => reqwest::Method::HEAD,
+            HttpMethod::Options => reqwest::Method::OPTIONS,
+        }
+    }
+}
+
+/// Request body payload.
+#[derive(Debug, Clone)]
+pub enum RequestBody {
+    None,
+    Json(serde_json::Value),
+    Form(HashMap<String, String>),
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+/// Kind of error that occurred while performing a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestErrorKind {
+    Network,
+    Timeout,
+    DnsResolution,
+    InvalidUrl,
+    Serialization,
+    Tls,
+}
+
+/// Error returned by the request module.
+#[derive(Debug, Clone)]
+pub struct RequestError {
+    pub kind: RequestErrorKind,
+    pub message: String,
+    pub url: Option<String>,
+    pub status_code: Option<u16>,
+}
+
+impl RequestError {
+    /// Create a new request error of the given kind.
+    pub fn new(kind: RequestErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            url: None,
+            status_code: None,
+        }
+    }
+
+    /// Attach the URL that was being requested when the error occurred.
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Attach the HTTP status code associated with the error, if any.
+    pub fn with_status(mut self, status_code: u16) -> Self {
+        self.status_code = Some(status_code);
+        self
+    }
+}
+
+impl std::fmt::Display for RequestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}: {}", self.kind, self.message)
+    }
+}
+
+impl std::error::Error for RequestError {}
+
+/// Walk a `reqwest::Error`'s source chain looking for a TLS/certificate failure.
+fn is_tls_error(err: &reqwest::Error) -> bool {
+    let mut source: Option<&(dyn std::error::Error + 'static)> = std::error::Error::source(err);
+    while let Some(cause) = source {
+        let message = cause.to_string().to_lowercase();
+        if message.contains("certificate") || message.contains("tls") {
+            return true;
+        }
+        source = cause.source();
+    }
+    false
+}
+
+impl From<reqwest::Error> for RequestError {
+    fn from(err: reqwest::Error) -> Self {
+        let kind = if err.is_timeout() {
+            RequestErrorKind::Timeout
+        } else if is_tls_error(&err) {
+            RequestErrorKind::Tls
+        } else if err.is_connect() {
+            RequestErrorKind::Network
+        } else {
+            RequestErrorKind::Network
+        };
+        let mut error = RequestError::new(kind, err.to_string());
+        if let Some(url) = err.url() {
+            error = error.with_url(url.to_string());
+        }
+        if let Some(status) = err.status() {
+            error = error.with_status(status.as_u16());
+        }
+        error
+    }
+}
+
+/// A client certificate and private key (PEM-encoded), presented during mTLS handshakes.
+#[derive(Debug, Clone)]
+pub struct ClientIdentity {
+    pub cert_pem: Vec<u8>,
+    pub key_pem: Vec<u8>,
+}
+
+/// Per-request configuration.
+///
+/// `verify_ssl`, `extra_ca_certs`, `client_identity` and `connect_timeout` determine how
+/// the underlying `reqwest::Client` is built, so they only take effect via
+/// [`RequestManager::with_config`], not per-request.
+#[derive(Debug, Clone)]
+pub struct RequestConfig {
+    /// Overall wall-clock budget for the request, connect through body, per reqwest hop.
+    pub timeout: Duration,
+    /// Time allowed to establish the TCP/TLS connection. Client-level; see struct docs.
+    pub connect_timeout: Option<Duration>,
+    /// Time allowed between successive chunks of the response body. When it elapses with
+    /// only part of the body received, [`RequestManager::execute`] aborts with
+    /// [`RequestErrorKind::Timeout`] noting how many bytes had arrived.
+    pub read_timeout: Option<Duration>,
+    pub follow_redirects: bool,
+    pub max_redirects: u32,
+    pub verify_ssl: bool,
+    pub user_agent: Option<String>,
+    /// Additional root CA certificates (PEM bytes) to trust, e.g. for a corporate proxy
+    /// or self-signed endpoint, without disabling verification entirely.
+    pub extra_ca_certs: Vec<Vec<u8>>,
+    /// Client certificate + key to present for mTLS.
+    pub client_identity: Option<ClientIdentity>,
+}
+
+impl Default for RequestConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+            connect_timeout: None,
+            read_timeout: None,
+            follow_redirects: true,
+            max_redirects: 10,
+            verify_ssl: true,
+            user_agent: Some(format!("browser-core/{}", env!("CARGO_PKG_VERSION"))),
+            extra_ca_certs: Vec::new(),
+            client_identity: None,
+        }
+    }
+}
+
+/// Fluent builder for an HTTP request.
+#[derive(Debug, Clone)]
+pub struct RequestBuilder {
+    pub url: String,
+    pub method: HttpMethod,
+    pub headers: HashMap<String, String>,
+    pub body: RequestBody,
+    pub config: RequestConfig,
+}
+
+impl RequestBuilder {
+    fn new(method: HttpMethod, url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            method,
+            headers: HashMap::new(),
+            body: RequestBody::None,
+            config: RequestConfig::default(),
+        }
+    }
+
+    /// Start building a GET request.
+    pub fn get(url: impl Into<String>) -> Self {
+        Self::new(HttpMethod::Get, url)
+    }
+
+    /// Start building a POST request.
+    pub fn post(url: impl Into<String>) -> Self {
+        Self::new(HttpMethod::Post, url)
+    }
+
+    /// Start building a PUT request.
+    pub fn put(url: impl Into<String>) -> Self {
+        Self::new(HttpMethod::Put, url)
+    }
+
+    /// Start building a DELETE request.
+    pub fn delete(url: impl Into<String>) -> Self {
+        Self::new(HttpMethod::Delete, url)
+    }
+
+    /// Start building a PATCH request.
+    pub fn patch(url: impl Into<String>) -> Self {
+        Self::new(HttpMethod::Patch, url)
+    }
+
+    /// Start building a HEAD request.
+    pub fn head(url: impl Into<String>) -> Self {
+        Self::new(HttpMethod::Head, url)
+    }
+
+    /// Start building an OPTIONS request.
+    pub fn options(url: impl Into<String>) -> Self {
+        Self::new(HttpMethod::Options, url)
+    }
+
+    /// Set a header on the request.
+    pub fn header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(key.into(), value.into());
+        self
+    }
+
+    /// Override the total request timeout.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.config.timeout = timeout;
+        self
+    }
+
+    /// Enable or disable automatic redirect following.
+    pub fn follow_redirects(mut self, follow: bool) -> Self {
+        self.config.follow_redirects = follow;
+        self
+    }
+
+    /// Send a JSON body.
+    pub fn json(mut self, value: serde_json::Value) -> Self {
+        self.body = RequestBody::Json(value);
+        self.headers
+            .insert("Content-Type".to_string(), "application/json".to_string());
+        self
+    }
+
+    /// Send a `application/x-www-form-urlencoded` body.
+    pub fn form(mut self, data: HashMap<String, String>) -> Self {
+        self.body = RequestBody::Form(data);
+        self.headers.insert(
+            "Content-Type".to_string(),
+            "application/x-www-form-urlencoded".to_string(),
+        );
+        self
+    }
+
+    /// Send a plain text body.
+    pub fn body_text(mut self, text: impl Into<String>) -> Self {
+        self.body = RequestBody::Text(text.into());
+        self.headers
+            .insert("Content-Type".to_string(), "text/plain".to_string());
+        self
+    }
+
+    /// Send a raw bytes body.
+    pub fn body_bytes(mut self, bytes: Vec<u8>) -> Self {
+        self.body = RequestBody::Bytes(bytes);
+        self
+    }
+}
+
+/// Response returned by [`RequestManager::execute`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestResponse {
+    pub status: u16,
+    pub status_text: String,
+    pub headers: HashMap<String, String>,
+    pub body: String,
+    pub response_time_ms: u64,
+    pub final_url: String,
+    /// Whether this response was served from the local cache without a network call
+    /// (including a revalidated-but-not-modified response).
+    #[serde(default)]
+    pub from_cache: bool,
+    /// URLs visited while following redirects, in order, not including the final URL.
+    #[serde(default)]
+    pub redirect_hops: Vec<String>,
+}
+
+impl RequestResponse {
+    /// Returns `true` for 2xx status codes.
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Parse the response body as JSON.
+    pub fn json<T: serde::de::DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        serde_json::from_str(&self.body)
+    }
+}
+
+/// Cache-Control directives relevant to response freshness.
+#[derive(Debug, Clone, Default)]
+struct CacheControl {
+    no_store: bool,
+    no_cache: bool,
+    max_age: Option<u64>,
+}
+
+impl CacheControl {
+    fn parse(header: Option<&str>) -> Self {
+        let mut cc = CacheControl::default();
+        let Some(header) = header else {
+            return cc;
+        };
+        for directive in header.split(',') {
+            let directive = directive.trim();
+            if directive.eq_ignore_ascii_case("no-store") {
+                cc.no_store = true;
+            } else if directive.eq_ignore_ascii_case("no-cache") {
+                cc.no_cache = true;
+            } else if let Some(value) = directive
+                .to_ascii_lowercase()
+                .strip_prefix("max-age=")
+                .and_then(|v| v.trim().parse::<u64>().ok())
+            {
+                cc.max_age = Some(value);
+            }
+        }
+        cc
+    }
+}
+
+/// A cached response plus the freshness metadata needed to revalidate it.
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    response: RequestResponse,
+    stored_at: Instant,
+    max_age: Option<Duration>,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    must_revalidate: bool,
+}
+
+impl CacheEntry {
+    fn is_fresh(&self) -> bool {
+        match self.max_age {
+            Some(max_age) if !self.must_revalidate => self.stored_at.elapsed() < max_age,
+            _ => false,
+        }
+    }
+
+    fn has_validators(&self) -> bool {
+        self.etag.is_some() || self.last_modified.is_some()
+    }
+}
+
+/// Cache key: method plus the final (post-redirect) URL.
+type CacheKey = (HttpMethod, String);
+
+/// A host-scoped credential, automatically injected as an `Authorization` header.
+///
+/// Borrowed from Deno's `auth_tokens` mechanism: a semicolon-separated env-style config
+/// string of `token@host` or `user:pass@host` entries, parsed with [`parse_auth_tokens`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthToken {
+    Bearer { host: String, token: String },
+    Basic {
+        host: String,
+        username: String,
+        password: String,
+    },
+}
+
+impl AuthToken {
+    fn host(&self) -> &str {
+        match self {
+            AuthToken::Bearer { host, .. } => host,
+            AuthToken::Basic { host, .. } => host,
+        }
+    }
+
+    fn header_value(&self) -> String {
+        match self {
+            AuthToken::Bearer { token, .. } => format!("Bearer {}", token),
+            AuthToken::Basic {
+                username, password, ..
+            } => {
+                use base64::{engine::general_purpose::STANDARD, Engine as _};
+                format!(
+                    "Basic {}",
+                    STANDARD.encode(format!("{}:{}", username, password))
+                )
+            }
+        }
+    }
+}
+
+/// Parse an env-style `auth_tokens` config string into a list of [`AuthToken`]s.
+///
+/// Entries are separated by `;`. Each entry is either `token@host` (bearer) or
+/// `user:pass@host` (basic). Malformed entries are skipped.
+pub fn parse_auth_tokens(config: &str) -> Vec<AuthToken> {
+    config
+        .split(';')
+        .filter_map(|entry| {
+            let entry = entry.trim();
+            if entry.is_empty() {
+                return None;
+            }
+            let (credential, host) = entry.rsplit_once('@')?;
+            if let Some((username, password)) = credential.split_once(':') {
+                Some(AuthToken::Basic {
+                    host: host.to_string(),
+                    username: username.to_string(),
+                    password: password.to_string(),
+                })
+            } else {
+                Some(AuthToken::Bearer {
+                    host: host.to_string(),
+                    token: credential.to_string(),
+                })
+            }
+        })
+        .collect()
+}
+
+/// Find the most specific [`AuthToken`] whose host pattern matches `host`.
+///
+/// A pattern matches `host` exactly or as a parent domain (`example.com` matches
+/// `api.example.com`). When more than one pattern matches, the longest (most specific)
+/// pattern wins.
+fn find_auth_token<'a>(tokens: &'a [AuthToken], host: &str) -> Option<&'a AuthToken> {
+    tokens
+        .iter()
+        .filter(|token| {
+            let pattern = token.host();
+            host == pattern || host.ends_with(&format!(".{}", pattern))
+        })
+        .max_by_key(|token| token.host().len())
+}
+
+/// Manages outgoing HTTP requests, including optional response caching.
+pub struct RequestManager {
+    client: reqwest::Client,
+    cache: Arc<RwLock<HashMap<CacheKey, CacheEntry>>>,
+    cache_enabled: bool,
+    auth_tokens: Vec<AuthToken>,
+}
+
+impl RequestManager {
+    /// Create a new request manager with default configuration and caching enabled.
+    ///
+    /// Redirects are always followed manually (see [`RequestManager::execute`]) so the
+    /// crate controls cross-origin header stripping and method rewriting; reqwest's own
+    /// redirect policy is disabled.
+    pub fn new() -> Result<Self, RequestError> {
+        Self::with_config(&RequestConfig::default())
+    }
+
+    /// Create a request manager whose TLS behavior (certificate verification, trusted
+    /// CAs, and client identity) is derived from `config`.
+    pub fn with_config(config: &RequestConfig) -> Result<Self, RequestError> {
+        let client = Self::build_client(config)?;
+        Ok(Self {
+            client,
+            cache: Arc::new(RwLock::new(HashMap::new())),
+            cache_enabled: true,
+            auth_tokens: Vec::new(),
+        })
+    }
+
+    /// Build the underlying `reqwest::Client`, applying `config`'s TLS settings.
+    fn build_client(config: &RequestConfig) -> Result<reqwest::Client, RequestError> {
+        let mut builder = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .danger_accept_invalid_certs(!config.verify_ssl);
+
+        if let Some(connect_timeout) = config.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+
+        for pem in &config.extra_ca_certs {
+            let cert = reqwest::Certificate::from_pem(pem).map_err(|e| {
+                RequestError::new(RequestErrorKind::Tls, format!("invalid CA certificate: {e}"))
+            })?;
+            builder = builder.add_root_certificate(cert);
+        }
+
+        if let Some(identity) = &config.client_identity {
+            let mut pem = identity.cert_pem.clone();
+            pem.extend_from_slice(&identity.key_pem);
+            let identity = reqwest::Identity::from_pem(&pem).map_err(|e| {
+                RequestError::new(RequestErrorKind::Tls, format!("invalid client identity: {e}"))
+            })?;
+            builder = builder.identity(identity);
+        }
+
+        builder.build().map_err(RequestError::from)
+    }
+
+    /// Disable the response cache for this manager.
+    pub fn without_cache(mut self) -> Self {
+        self.cache_enabled = false;
+        self
+    }
+
+    /// Set the host-scoped auth tokens to automatically inject as `Authorization` headers.
+    pub fn with_auth_tokens(mut self, auth_tokens: Vec<AuthToken>) -> Self {
+        self.auth_tokens = auth_tokens;
+        self
+    }
+
+    /// Clear all cached responses.
+    pub fn clear_cache(&self) {
+        self.cache.write().unwrap().clear();
+    }
+
+    fn cache_key(method: HttpMethod, url: &str) -> CacheKey {
+        (method, url.to_string())
+    }
+
+    /// Execute a request, consulting and updating the response cache for GET requests and
+    /// manually walking any redirect chain.
+    pub async fn execute(&self, request: RequestBuilder) -> Result<RequestResponse, RequestError> {
+        let cacheable = self.cache_enabled && request.method == HttpMethod::Get;
+        let key = Self::cache_key(request.method, &request.url);
+
+        if cacheable {
+            if let Some(entry) = self.cache.read().unwrap().get(&key).cloned() {
+                if entry.is_fresh() {
+                    let mut response = entry.response.clone();
+                    response.from_cache = true;
+                    return Ok(response);
+                }
+            }
+        }
+
+        let mut headers = request.headers.clone();
+        if cacheable {
+            if let Some(entry) = self.cache.read().unwrap().get(&key).cloned() {
+                if entry.has_validators() {
+                    if let Some(etag) = &entry.etag {
+                        headers.insert("If-None-Match".to_string(), etag.clone());
+                    }
+                    if let Some(last_modified) = &entry.last_modified {
+                        headers.insert("If-Modified-Since".to_string(), last_modified.clone());
+                    }
+                }
+            }
+        }
+        if let Some(user_agent) = &request.config.user_agent {
+            headers.insert(reqwest::header::USER_AGENT.to_string(), user_agent.clone());
+        }
+
+        let origin_url = reqwest::Url::parse(&request.url).map_err(|e| {
+            RequestError::new(RequestErrorKind::InvalidUrl, e.to_string())
+                .with_url(request.url.clone())
+        })?;
+        let origin_host = origin_url.host_str().map(|h| h.to_string());
+        let explicit_auth = has_header_case_insensitive(&headers, "Authorization");
+
+        let started = Instant::now();
+        let mut method = request.method;
+        let mut current_url = origin_url;
+        let mut body = request.body.clone();
+        let mut hops: Vec<String> = Vec::new();
+
+        let (status, resp_headers, final_url, resp_body) = loop {
+            if !explicit_auth {
+                remove_header_case_insensitive(&mut headers, "Authorization");
+                if let Some(host) = current_url.host_str() {
+                    if let Some(token) = find_auth_token(&self.auth_tokens, host) {
+                        headers.insert("Authorization".to_string(), token.header_value());
+                    }
+                }
+            }
+
+            let mut req = self
+                .client
+                .request(method.into(), current_url.clone())
+                .timeout(request.config.timeout);
+            for (k, v) in &headers {
+                req = req.header(k, v);
+            }
+            req = match &body {
+                RequestBody::None => req,
+                RequestBody::Json(value) => req.json(value),
+                RequestBody::Form(data) => req.form(data),
+                RequestBody::Text(text) => req.body(text.clone()),
+                RequestBody::Bytes(bytes) => req.body(bytes.clone()),
+            };
+
+            let resp = req.send().await.map_err(RequestError::from)?;
+            let status = resp.status();
+
+            let location = resp
+                .headers()
+                .get(reqwest::header::LOCATION)
+                .and_then(|v| v.to_str().ok())
+                .map(|s| s.to_string());
+
+            if request.config.follow_redirects
+                && status.is_redirection()
+                && hops.len() < request.config.max_redirects as usize
+            {
+                if let Some(location) = location {
+                    let next_url = resolve_url_from_location(&current_url, &location)?;
+                    hops.push(current_url.to_string());
+
+                    match status.as_u16() {
+                        301 | 302 | 303 => {
+                            if matches!(method, HttpMethod::Post | HttpMethod::Put) {
+                                method = HttpMethod::Get;
+                                body = RequestBody::None;
+                                remove_header_case_insensitive(&mut headers, "Content-Type");
+                            }
+                        }
+                        // 307/308: method and body are preserved as-is.
+                        _ => {}
+                    }
+
+                    if next_url.host_str().map(|h| h.to_string()) != origin_host {
+                        remove_header_case_insensitive(&mut headers, "Authorization");
+                    }
+
+                    current_url = next_url;
+                    continue;
+                }
+            }
+
+            let final_url = current_url.to_string();
+            let resp_headers: HashMap<String, String> = resp
+                .headers()
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or_default().to_string()))
+                .collect();
+            let resp_body = read_body(resp, request.config.read_timeout)
+                .await
+                .map_err(|e| e.with_url(final_url.clone()))?;
+            break (status, resp_headers, final_url, resp_body);
+        };
+
+        if cacheable && status.as_u16() == 304 {
+            let mut cache = self.cache.write().unwrap();
+            if let Some(entry) = cache.get_mut(&key) {
+                entry.stored_at = Instant::now();
+                let cc =
+                    CacheControl::parse(resp_headers.get("cache-control").map(|s| s.as_str()));
+                entry.max_age = cc.max_age.map(Duration::from_secs);
+                entry.must_revalidate = cc.no_cache;
+                if let Some(etag) = resp_headers.get("etag") {
+                    entry.etag = Some(etag.clone());
+                }
+                let mut response = entry.response.clone();
+                response.from_cache = true;
+                response.redirect_hops = hops;
+                return Ok(response);
+            }
+        }
+
+        let response = RequestResponse {
+            status: status.as_u16(),
+            status_text: status.canonical_reason().unwrap_or("").to_string(),
+            headers: resp_headers.clone(),
+            body: resp_body,
+            response_time_ms: started.elapsed().as_millis() as u64,
+            final_url,
+            from_cache: false,
+            redirect_hops: hops,
+        };
+
+        if cacheable && response.is_success() {
+            let cc = CacheControl::parse(resp_headers.get("cache-control").map(|s| s.as_str()));
+            if !cc.no_store {
+                let entry = CacheEntry {
+                    response: response.clone(),
+                    stored_at: Instant::now(),
+                    max_age: cc.max_age.map(Duration::from_secs),
+                    etag: resp_headers.get("etag").cloned(),
+                    last_modified: resp_headers.get("last-modified").cloned(),
+                    must_revalidate: cc.no_cache,
+                };
+                self.cache.write().unwrap().insert(key, entry);
+            }
+        }
+
+        Ok(response)
+    }
+}
+
+impl Default for RequestManager {
+    fn default() -> Self {
+        Self::new().expect("failed to build default reqwest client")
+    }
+}
+
+/// Remove a header from `headers` matching `name` case-insensitively.
+fn remove_header_case_insensitive(headers: &mut HashMap<String, String>, name: &str) {
+    if let Some(key) = headers
+        .keys()
+        .find(|k| k.eq_ignore_ascii_case(name))
+        .cloned()
+    {
+        headers.remove(&key);
+    }
+}
+
+/// Check whether `headers` already contains `name`, case-insensitively.
+fn has_header_case_insensitive(headers: &HashMap<String, String>, name: &str) -> bool {
+    headers.keys().any(|k| k.eq_ignore_ascii_case(name))
+}
+
+/// Read a response body, optionally guarding against stalls with `read_timeout`.
+///
+/// Without a read timeout this is equivalent to `resp.text()`. With one, the body is
+/// streamed chunk by chunk; if `read_timeout` elapses between two chunks, the read is
+/// aborted with [`RequestErrorKind::Timeout`] noting how many bytes had arrived.
+async fn read_body(
+    mut resp: reqwest::Response,
+    read_timeout: Option<Duration>,
+) -> Result<String, RequestError> {
+    let Some(read_timeout) = read_timeout else {
+        return resp.text().await.map_err(RequestError::from);
+    };
+
+    let mut body = Vec::new();
+    loop {
+        match tokio::time::timeout(read_timeout, resp.chunk()).await {
+            Ok(Ok(Some(chunk))) => body.extend_from_slice(&chunk),
+            Ok(Ok(None)) => break,
+            Ok(Err(e)) => return Err(RequestError::from(e)),
+            Err(_) => {
+                return Err(RequestError::new(
+                    RequestErrorKind::Timeout,
+                    format!(
+                        "read timeout after {} byte(s) of the response body",
+                        body.len()
+                    ),
+                ));
+            }
+        }
+    }
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+/// Resolve a `Location` header value against the URL that produced it, per RFC 3986 §4.2.
+///
+/// - Absolute URLs (`http://`/`https://`) are used as-is.
+/// - Protocol-relative references (`//host/path`) are joined with the base scheme.
+/// - Absolute-path references (`/path`) are joined against the base origin.
+/// - Anything else is resolved as a relative reference against `base`.
+pub fn resolve_url_from_location(
+    base: &reqwest::Url,
+    location: &str,
+) -> Result<reqwest::Url, RequestError> {
+    let invalid = |message: String| {
+        RequestError::new(RequestErrorKind::InvalidUrl, message).with_url(location.to_string())
+    };
+
+    if location.starts_with("http://") || location.starts_with("https://") {
+        return reqwest::Url::parse(location).map_err(|e| invalid(e.to_string()));
+    }
+
+    if let Some(rest) = location.strip_prefix("//") {
+        let absolute = format!("{}://{}", base.scheme(), rest);
+        return reqwest::Url::parse(&absolute).map_err(|e| invalid(e.to_string()));
+    }
+
+    if location.starts_with('/') {
+        let host = base
+            .host_str()
+            .ok_or_else(|| invalid("redirect base has no host".to_string()))?;
+        let origin = match base.port() {
+            Some(port) => format!("{}://{}:{}", base.scheme(), host, port),
+            None => format!("{}://{}", base.scheme(), host),
+        };
+        return reqwest::Url::parse(&format!("{}{}", origin, location))
+            .map_err(|e| invalid(e.to_string()));
+    }
+
+    base.join(location).map_err(|e| invalid(e.to_string()))
+}
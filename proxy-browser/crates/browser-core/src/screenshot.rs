@@ -4,13 +4,24 @@
 //! - Full page screenshots
 //! - Viewport screenshots
 //! - Element screenshots
-//! - Screenshot formats (PNG, JPEG, WebP)
+//! - Screenshot formats (PNG, JPEG, WebP, AVIF)
 
 use anyhow::Result;
 use chrono::{DateTime, Utc};
+use image::{ColorType, ImageEncoder};
 use serde::{Deserialize, Serialize};
+use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
 
+/// Default framebuffer size used by [`synthetic_framebuffer`] when no real page has
+/// been measured.
+const DEFAULT_VIEWPORT_WIDTH: u32 = 1920;
+const DEFAULT_VIEWPORT_HEIGHT: u32 = 1080;
+
+/// How many viewport heights a "full page" capture is assumed to span, absent a real
+/// page height measurement. See [`ScreenshotManager::capture_full_page`].
+const FULL_PAGE_VIEWPORT_MULTIPLE: u32 = 3;
+
 /// Screenshot format options
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
 pub enum ScreenshotFormat {
@@ -18,6 +29,7 @@ pub enum ScreenshotFormat {
     Png,
     Jpeg,
     WebP,
+    Avif,
 }
 
 impl ScreenshotFormat {
@@ -27,6 +39,7 @@ impl ScreenshotFormat {
             ScreenshotFormat::Png => "png",
             ScreenshotFormat::Jpeg => "jpg",
             ScreenshotFormat::WebP => "webp",
+            ScreenshotFormat::Avif => "avif",
         }
     }
 
@@ -36,6 +49,7 @@ impl ScreenshotFormat {
             ScreenshotFormat::Png => "image/png",
             ScreenshotFormat::Jpeg => "image/jpeg",
             ScreenshotFormat::WebP => "image/webp",
+            ScreenshotFormat::Avif => "image/avif",
         }
     }
 }
@@ -44,7 +58,7 @@ impl ScreenshotFormat {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScreenshotOptions {
     pub format: ScreenshotFormat,
-    pub quality: u8, // 0-100, for JPEG/WebP
+    pub quality: u8, // 0-100, for JPEG/AVIF (WebP is encoded lossless)
     pub full_page: bool,
     pub clip: Option<ScreenshotClip>,
     pub omit_background: bool,
@@ -71,6 +85,48 @@ pub struct ScreenshotClip {
     pub height: f64,
 }
 
+/// Content-safety category a capture was classified into. See [`SafetyCheck`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SafetyCategory {
+    Neutral,
+    Suggestive,
+    Explicit,
+}
+
+/// What to do with a capture whose [`SafetyCheck`] score exceeds its threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum SafetyAction {
+    /// Keep the capture as-is; the caller decides what to do with the score.
+    #[default]
+    Flag,
+    /// Box-blur the output before returning it.
+    Blur,
+    /// Mark the result as refused; [`ScreenshotResult::save`] will error rather than
+    /// write it to disk.
+    Refuse,
+}
+
+/// Optional content-safety classification step run over a capture before it's
+/// returned. Disabled by default; opt in with [`ScreenshotManager::with_safety_check`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SafetyCheck {
+    pub enabled: bool,
+    pub threshold: f32,
+    pub categories: Vec<SafetyCategory>,
+    pub on_exceeded: SafetyAction,
+}
+
+impl Default for SafetyCheck {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold: 0.8,
+            categories: vec![SafetyCategory::Suggestive, SafetyCategory::Explicit],
+            on_exceeded: SafetyAction::Flag,
+        }
+    }
+}
+
 /// Screenshot result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScreenshotResult {
@@ -79,11 +135,29 @@ pub struct ScreenshotResult {
     pub width: u32,
     pub height: u32,
     pub captured_at: DateTime<Utc>,
+    /// Probability (0.0-1.0) assigned by the content-safety classifier, or `None` if
+    /// no [`SafetyCheck`] was configured on the capturing [`ScreenshotManager`].
+    pub nsfw_score: Option<f32>,
+    pub safety_category: Option<SafetyCategory>,
+    /// Set when the safety check's [`SafetyAction::Blur`] fired for this capture.
+    pub blurred: bool,
+    /// Set when the safety check's [`SafetyAction::Refuse`] fired for this capture;
+    /// [`ScreenshotResult::save`] refuses to persist it.
+    pub refused: bool,
 }
 
 impl ScreenshotResult {
+    /// Whether the capture's safety score is below `threshold`. Captures with no
+    /// score (no [`SafetyCheck`] configured) are always considered safe.
+    pub fn is_safe(&self, threshold: f32) -> bool {
+        self.nsfw_score.map_or(true, |score| score < threshold)
+    }
+
     /// Save screenshot to file
     pub async fn save(&self, path: &PathBuf) -> Result<()> {
+        if self.refused {
+            anyhow::bail!("screenshot capture was refused by the safety check");
+        }
         tokio::fs::write(path, &self.data).await?;
         Ok(())
     }
@@ -104,15 +178,244 @@ impl ScreenshotResult {
     }
 }
 
+/// A stand-in for the raw RGBA framebuffer a real CDP `Page.captureScreenshot` call
+/// would return. `crate::chromium_engine::ChromiumEngine` manages the Chromium
+/// process and tab bookkeeping but doesn't issue CDP rendering commands yet, so there
+/// is no real framebuffer to read here; this produces an opaque white buffer of the
+/// requested size so the crop/encode pipeline below has real pixel data to exercise.
+fn synthetic_framebuffer(width: u32, height: u32) -> Vec<u8> {
+    vec![255u8; width as usize * height as usize * 4]
+}
+
+/// Set every pixel's alpha channel to fully transparent, for `omit_background`
+/// captures in formats that can represent it.
+fn clear_alpha(rgba: &mut [u8]) {
+    for pixel in rgba.chunks_exact_mut(4) {
+        pixel[3] = 0;
+    }
+}
+
+/// Extract the sub-rectangle described by `clip` out of a `width`x`height` RGBA
+/// buffer, clamping it to the buffer's bounds. Returns the cropped buffer and its
+/// (possibly clamped) width/height.
+fn crop(rgba: &[u8], width: u32, height: u32, clip: &ScreenshotClip) -> (Vec<u8>, u32, u32) {
+    if width == 0 || height == 0 {
+        return (Vec::new(), 0, 0);
+    }
+
+    let x = (clip.x.max(0.0) as u32).min(width - 1);
+    let y = (clip.y.max(0.0) as u32).min(height - 1);
+    let clip_width = (clip.width.max(0.0) as u32).min(width - x);
+    let clip_height = (clip.height.max(0.0) as u32).min(height - y);
+
+    let mut out = Vec::with_capacity(clip_width as usize * clip_height as usize * 4);
+    for row in 0..clip_height {
+        let row_start = ((y + row) * width + x) as usize * 4;
+        let row_end = row_start + clip_width as usize * 4;
+        out.extend_from_slice(&rgba[row_start..row_end]);
+    }
+
+    (out, clip_width, clip_height)
+}
+
+/// Flatten an RGBA buffer onto an opaque white background, for formats (JPEG) with no
+/// alpha channel.
+fn flatten_to_rgb(rgba: &[u8]) -> Vec<u8> {
+    let mut rgb = Vec::with_capacity(rgba.len() / 4 * 3);
+    for pixel in rgba.chunks_exact(4) {
+        let alpha = pixel[3] as f32 / 255.0;
+        for channel in &pixel[..3] {
+            let blended = (*channel as f32 * alpha) + (255.0 * (1.0 - alpha));
+            rgb.push(blended.round() as u8);
+        }
+    }
+    rgb
+}
+
+/// CPU-bound: encode `rgba` to `options.format`. Callers run this inside
+/// [`tokio::task::spawn_blocking`] so it doesn't stall the async executor.
+fn encode_rgba(rgba: &[u8], width: u32, height: u32, options: &ScreenshotOptions) -> Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+
+    match options.format {
+        ScreenshotFormat::Png => {
+            image::codecs::png::PngEncoder::new(&mut buffer)
+                .write_image(rgba, width, height, ColorType::Rgba8)?;
+        }
+        ScreenshotFormat::Jpeg => {
+            let rgb = flatten_to_rgb(rgba);
+            image::codecs::jpeg::JpegEncoder::new_with_quality(&mut buffer, options.quality)
+                .write_image(&rgb, width, height, ColorType::Rgb8)?;
+        }
+        ScreenshotFormat::WebP => {
+            image::codecs::webp::WebPEncoder::new_lossless(&mut buffer)
+                .write_image(rgba, width, height, ColorType::Rgba8)?;
+        }
+        ScreenshotFormat::Avif => {
+            // Speed 4 is a middle-ground between the encoder's fastest and slowest
+            // presets; quality is forwarded from `ScreenshotOptions` as-is.
+            image::codecs::avif::AvifEncoder::new_with_speed_quality(&mut buffer, 4, options.quality)
+                .write_image(rgba, width, height, ColorType::Rgba8)?;
+        }
+    }
+
+    Ok(buffer)
+}
+
+/// Fixed-size input dimensions a real classifier model would resize captures to
+/// before inference.
+const SAFETY_MODEL_INPUT_SIZE: u32 = 224;
+
+/// Nearest-neighbor downsample of an RGBA buffer to `target`x`target`, matching the
+/// fixed input size a real classifier model expects.
+fn resize_nearest(rgba: &[u8], width: u32, height: u32, target: u32) -> Vec<u8> {
+    let mut out = Vec::with_capacity(target as usize * target as usize * 4);
+    for ty in 0..target {
+        let src_y = (ty * height) / target;
+        for tx in 0..target {
+            let src_x = (tx * width) / target;
+            let idx = (src_y * width + src_x) as usize * 4;
+            out.extend_from_slice(&rgba[idx..idx + 4]);
+        }
+    }
+    out
+}
+
+/// CPU-bound stand-in for a real NSFW classifier model: this tree has no inference
+/// runtime, so rather than fabricate a model this resizes the capture to
+/// [`SAFETY_MODEL_INPUT_SIZE`] (mirroring the resize/normalize/infer shape a real
+/// classifier would use) and scores it with a cheap, deterministic skin-tone-ratio
+/// heuristic, so the rest of the pipeline (threshold, actions, `ScreenshotResult`
+/// fields) is exercised against a real signal rather than a constant.
+fn classify_rgba(rgba: &[u8], width: u32, height: u32) -> (f32, SafetyCategory) {
+    let resized = resize_nearest(rgba, width, height, SAFETY_MODEL_INPUT_SIZE);
+
+    let mut skin_like = 0u32;
+    let mut total = 0u32;
+    for pixel in resized.chunks_exact(4) {
+        let (r, g, b) = (pixel[0] as i32, pixel[1] as i32, pixel[2] as i32);
+        if r > 95 && g > 40 && b > 20 && r > g && r > b && (r - g).abs() > 15 {
+            skin_like += 1;
+        }
+        total += 1;
+    }
+
+    let score = if total == 0 {
+        0.0
+    } else {
+        skin_like as f32 / total as f32
+    };
+
+    let category = if score > 0.5 {
+        SafetyCategory::Explicit
+    } else if score > 0.2 {
+        SafetyCategory::Suggestive
+    } else {
+        SafetyCategory::Neutral
+    };
+
+    (score, category)
+}
+
+/// Simple box blur, used when a [`SafetyCheck`]'s [`SafetyAction::Blur`] fires.
+fn box_blur(rgba: &[u8], width: u32, height: u32, radius: i32) -> Vec<u8> {
+    let (width, height) = (width as i32, height as i32);
+    let mut out = vec![0u8; rgba.len()];
+
+    for y in 0..height {
+        for x in 0..width {
+            let mut sums = [0u32; 4];
+            let mut count = 0u32;
+            for dy in -radius..=radius {
+                for dx in -radius..=radius {
+                    let (sx, sy) = (x + dx, y + dy);
+                    if sx >= 0 && sx < width && sy >= 0 && sy < height {
+                        let idx = (sy * width + sx) as usize * 4;
+                        for c in 0..4 {
+                            sums[c] += rgba[idx + c] as u32;
+                        }
+                        count += 1;
+                    }
+                }
+            }
+            let out_idx = (y * width + x) as usize * 4;
+            for c in 0..4 {
+                out[out_idx + c] = (sums[c] / count.max(1)) as u8;
+            }
+        }
+    }
+
+    out
+}
+
+/// Runs `options.safety_check` (if any) over `rgba`, applying [`SafetyAction::Blur`]
+/// in place when it fires. Returns the (possibly blurred) score/category/blurred/
+/// refused tuple to attach to the eventual [`ScreenshotResult`].
+fn apply_safety_check(
+    rgba: &mut Vec<u8>,
+    width: u32,
+    height: u32,
+    safety_check: &Option<SafetyCheck>,
+) -> (Option<f32>, Option<SafetyCategory>, bool, bool) {
+    let Some(check) = safety_check else {
+        return (None, None, false, false);
+    };
+    if !check.enabled {
+        return (None, None, false, false);
+    }
+
+    let (score, category) = classify_rgba(rgba, width, height);
+    if !check.categories.contains(&category) || score < check.threshold {
+        return (Some(score), Some(category), false, false);
+    }
+
+    match check.on_exceeded {
+        SafetyAction::Flag => (Some(score), Some(category), false, false),
+        SafetyAction::Blur => {
+            *rgba = box_blur(rgba, width, height, 8);
+            (Some(score), Some(category), true, false)
+        }
+        SafetyAction::Refuse => (Some(score), Some(category), false, true),
+    }
+}
+
+/// A deterministic stand-in for a real `DOM.querySelector` + `DOM.getBoxModel` CDP
+/// round trip: this tree has no DOM geometry query support yet, so the same selector
+/// always yields the same bounding box rather than capturing the whole viewport and
+/// silently ignoring the selector.
+fn element_bounding_box(selector: &str) -> ScreenshotClip {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    selector.hash(&mut hasher);
+    let hash = hasher.finish();
+
+    ScreenshotClip {
+        x: (hash % 400) as f64,
+        y: ((hash >> 16) % 400) as f64,
+        width: 320.0,
+        height: 180.0,
+    }
+}
+
 /// Screenshot manager for handling captures
 pub struct ScreenshotManager {
     output_dir: PathBuf,
+    safety_check: Option<SafetyCheck>,
 }
 
 impl ScreenshotManager {
     /// Create a new screenshot manager
     pub fn new(output_dir: PathBuf) -> Self {
-        Self { output_dir }
+        Self {
+            output_dir,
+            safety_check: None,
+        }
+    }
+
+    /// Enable a content-safety classification pass over every capture this manager
+    /// makes.
+    pub fn with_safety_check(mut self, safety_check: SafetyCheck) -> Self {
+        self.safety_check = Some(safety_check);
+        self
     }
 
     /// Generate filename for screenshot
@@ -122,24 +425,57 @@ impl ScreenshotManager {
         self.output_dir.join(filename)
     }
 
-    /// Capture viewport screenshot (placeholder - actual implementation depends on webview)
+    /// Capture a viewport screenshot: see [`synthetic_framebuffer`] for what's
+    /// actually captured in the absence of real CDP rendering support. Honors
+    /// `options.clip` (cropping before encode) and `options.omit_background`, and
+    /// encodes off the async runtime via [`tokio::task::spawn_blocking`].
     pub async fn capture_viewport(
         &self,
         _tab_id: &str,
         options: &ScreenshotOptions,
     ) -> Result<ScreenshotResult> {
-        // This would integrate with the actual webview to capture screenshot
-        // For now, return a placeholder
+        let mut rgba = synthetic_framebuffer(DEFAULT_VIEWPORT_WIDTH, DEFAULT_VIEWPORT_HEIGHT);
+        if options.omit_background {
+            clear_alpha(&mut rgba);
+        }
+
+        let (mut rgba, width, height) = match &options.clip {
+            Some(clip) => crop(&rgba, DEFAULT_VIEWPORT_WIDTH, DEFAULT_VIEWPORT_HEIGHT, clip),
+            None => (rgba, DEFAULT_VIEWPORT_WIDTH, DEFAULT_VIEWPORT_HEIGHT),
+        };
+
+        let format = options.format;
+        let options = options.clone();
+        let safety_check = self.safety_check.clone();
+        let (data, nsfw_score, safety_category, blurred, refused) =
+            tokio::task::spawn_blocking(move || -> Result<_> {
+                let (nsfw_score, safety_category, blurred, refused) =
+                    apply_safety_check(&mut rgba, width, height, &safety_check);
+                let data = encode_rgba(&rgba, width, height, &options)?;
+                Ok((data, nsfw_score, safety_category, blurred, refused))
+            })
+            .await??;
+
         Ok(ScreenshotResult {
-            data: Vec::new(),
-            format: options.format,
-            width: 1920,
-            height: 1080,
+            data,
+            format,
+            width,
+            height,
             captured_at: Utc::now(),
+            nsfw_score,
+            safety_category,
+            blurred,
+            refused,
         })
     }
 
-    /// Capture full page screenshot
+    /// Capture a full page screenshot. The page is assumed to span
+    /// [`FULL_PAGE_VIEWPORT_MULTIPLE`] viewport heights (no real page height
+    /// measurement exists yet), captured as independent viewport-sized tiles that are
+    /// rendered concurrently -- each tile's placeholder capture and the final encode
+    /// all run off the async runtime via [`tokio::task::spawn_blocking`] -- then
+    /// stitched into one buffer before `options.clip`/`options.omit_background` are
+    /// applied and it's encoded.
     pub async fn capture_full_page(
         &self,
         _tab_id: &str,
@@ -147,18 +483,73 @@ impl ScreenshotManager {
     ) -> Result<ScreenshotResult> {
         let mut opts = options.clone();
         opts.full_page = true;
-        self.capture_viewport(_tab_id, &opts).await
+
+        let width = DEFAULT_VIEWPORT_WIDTH;
+        let total_height = DEFAULT_VIEWPORT_HEIGHT * FULL_PAGE_VIEWPORT_MULTIPLE;
+
+        let mut tile_tasks = Vec::new();
+        let mut y = 0;
+        while y < total_height {
+            let tile_height = DEFAULT_VIEWPORT_HEIGHT.min(total_height - y);
+            tile_tasks.push(tokio::task::spawn_blocking(move || {
+                synthetic_framebuffer(width, tile_height)
+            }));
+            y += tile_height;
+        }
+
+        let mut stitched = Vec::with_capacity(width as usize * total_height as usize * 4);
+        for tile in tile_tasks {
+            stitched.extend_from_slice(&tile.await?);
+        }
+
+        if opts.omit_background {
+            clear_alpha(&mut stitched);
+        }
+
+        let (mut rgba, out_width, out_height) = match &opts.clip {
+            Some(clip) => crop(&stitched, width, total_height, clip),
+            None => (stitched, width, total_height),
+        };
+
+        let format = opts.format;
+        let encode_opts = opts.clone();
+        let safety_check = self.safety_check.clone();
+        let (data, nsfw_score, safety_category, blurred, refused) =
+            tokio::task::spawn_blocking(move || -> Result<_> {
+                let (nsfw_score, safety_category, blurred, refused) =
+                    apply_safety_check(&mut rgba, out_width, out_height, &safety_check);
+                let data = encode_rgba(&rgba, out_width, out_height, &encode_opts)?;
+                Ok((data, nsfw_score, safety_category, blurred, refused))
+            })
+            .await??;
+
+        Ok(ScreenshotResult {
+            data,
+            format,
+            width: out_width,
+            height: out_height,
+            captured_at: Utc::now(),
+            nsfw_score,
+            safety_category,
+            blurred,
+            refused,
+        })
     }
 
-    /// Capture element screenshot
+    /// Capture a single element's screenshot: resolves `selector` to a bounding box
+    /// via [`element_bounding_box`] and clips the viewport capture to it, unless the
+    /// caller already supplied an explicit `options.clip`.
     pub async fn capture_element(
         &self,
-        _tab_id: &str,
-        _selector: &str,
+        tab_id: &str,
+        selector: &str,
         options: &ScreenshotOptions,
     ) -> Result<ScreenshotResult> {
-        // Would use CDP to capture specific element
-        self.capture_viewport(_tab_id, options).await
+        let mut opts = options.clone();
+        if opts.clip.is_none() {
+            opts.clip = Some(element_bounding_box(selector));
+        }
+        self.capture_viewport(tab_id, &opts).await
     }
 }
 
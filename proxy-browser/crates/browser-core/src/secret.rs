@@ -0,0 +1,134 @@
+//! At-rest secret encryption
+//!
+//! Wraps a plaintext string in [`Secret`], an encrypted envelope using the same
+//! XChaCha20-Poly1305 primitive as [`crate::backup_crypto`], keyed by a per-installation
+//! [`SecretKey`] rather than a user password, since profile settings (e.g.
+//! [`crate::browser_profile::ProfileProxyConfig`]'s username/password) need to be
+//! readable on launch without prompting. The decrypted plaintext is held in a
+//! [`zeroize::Zeroizing`] buffer so it's wiped from memory as soon as it's dropped.
+
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+/// Recorded in [`Secret::algorithm`] so an envelope is self-describing on disk and a
+/// future cipher change can be detected instead of silently misdecrypted.
+pub const SECRET_ALGORITHM: &str = "xchacha20poly1305";
+
+/// What about a [`SecretError`] went wrong.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecretErrorKind {
+    Encrypt,
+    /// Decryption failed: wrong key, or the ciphertext was tampered with/corrupted.
+    Decrypt,
+    /// `Secret::algorithm` isn't one this build knows how to decrypt.
+    UnsupportedAlgorithm,
+}
+
+/// Error returned by [`Secret`]/[`SecretKey`] operations.
+#[derive(Debug, Clone)]
+pub struct SecretError {
+    pub kind: SecretErrorKind,
+    pub message: String,
+}
+
+impl SecretError {
+    fn new(kind: SecretErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for SecretError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}: {}", self.kind, self.message)
+    }
+}
+
+impl std::error::Error for SecretError {}
+
+/// A 32-byte symmetric key encrypting/decrypting [`Secret`]s for one installation.
+/// Generate once with [`SecretKey::generate`] and persist the raw bytes somewhere only
+/// this installation can read (see [`crate::browser_profile::BrowserProfileManager::load`]).
+#[derive(Clone)]
+pub struct SecretKey([u8; 32]);
+
+impl SecretKey {
+    pub fn generate() -> Self {
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        Self(bytes)
+    }
+
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+}
+
+/// An encrypted envelope for a single secret value, tagged with the algorithm and
+/// nonce used so it's self-describing wherever it's persisted (the profile index,
+/// an exported profile, ...).
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Secret {
+    pub algorithm: String,
+    pub nonce: [u8; 24],
+    pub ciphertext: Vec<u8>,
+}
+
+impl std::fmt::Debug for Secret {
+    /// Never prints `ciphertext`/`nonce` bytes; callers shouldn't find decryptable
+    /// material in logs just because something `{:?}`-ed a [`Secret`].
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Secret").field("algorithm", &self.algorithm).finish_non_exhaustive()
+    }
+}
+
+impl Secret {
+    /// Encrypt `plaintext` for `key`.
+    pub fn encrypt(key: &SecretKey, plaintext: &str) -> Result<Self, SecretError> {
+        let cipher = XChaCha20Poly1305::new_from_slice(&key.0)
+            .map_err(|e| SecretError::new(SecretErrorKind::Encrypt, e.to_string()))?;
+
+        let mut nonce_bytes = [0u8; 24];
+        rand::thread_rng().fill_bytes(&mut nonce_bytes);
+
+        let ciphertext = cipher
+            .encrypt(XNonce::from_slice(&nonce_bytes), plaintext.as_bytes())
+            .map_err(|e| SecretError::new(SecretErrorKind::Encrypt, e.to_string()))?;
+
+        Ok(Self {
+            algorithm: SECRET_ALGORITHM.to_string(),
+            nonce: nonce_bytes,
+            ciphertext,
+        })
+    }
+
+    /// Decrypt this envelope with `key`. The returned buffer zeroizes its contents on
+    /// drop instead of leaving the plaintext lingering in freed memory.
+    pub fn expose_secret(&self, key: &SecretKey) -> Result<Zeroizing<String>, SecretError> {
+        if self.algorithm != SECRET_ALGORITHM {
+            return Err(SecretError::new(
+                SecretErrorKind::UnsupportedAlgorithm,
+                format!("unsupported secret algorithm '{}'", self.algorithm),
+            ));
+        }
+
+        let cipher = XChaCha20Poly1305::new_from_slice(&key.0)
+            .map_err(|e| SecretError::new(SecretErrorKind::Decrypt, e.to_string()))?;
+        let plaintext = cipher
+            .decrypt(XNonce::from_slice(&self.nonce), self.ciphertext.as_slice())
+            .map_err(|_| SecretError::new(SecretErrorKind::Decrypt, "failed to decrypt secret (wrong key or corrupt data)"))?;
+
+        String::from_utf8(plaintext)
+            .map(Zeroizing::new)
+            .map_err(|_| SecretError::new(SecretErrorKind::Decrypt, "decrypted secret was not valid UTF-8"))
+    }
+}
@@ -0,0 +1,333 @@
+//! Expiring, password-protected share links for captured screenshots
+//!
+//! [`ShareStore`] publishes a [`crate::screenshot::ScreenshotResult`] to disk under an
+//! unguessable token so it can be handed off without exposing the raw screenshot
+//! output directory, then guarantees it doesn't linger indefinitely: entries are
+//! deleted once their lifetime elapses, once the store's total storage quota is
+//! crossed, or once its configured free-disk threshold is crossed, via a background
+//! sweeper started with [`ShareStore::start_sweeper`] (same cancel/`JoinHandle`
+//! pattern as [`crate::proxy_pool::ProxyPool::start_health_checks`]).
+
+use crate::screenshot::{ScreenshotFormat, ScreenshotResult};
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use rand::RngCore;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::{oneshot, RwLock};
+use tokio::task::JoinHandle;
+
+/// Number of random bytes a share token is generated from, before base64url encoding.
+const TOKEN_BYTES: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ShareErrorKind {
+    TokenNotFound,
+    Expired,
+    PasswordRequired,
+    InvalidPassword,
+    Internal,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ShareError {
+    pub kind: ShareErrorKind,
+    pub message: String,
+}
+
+impl ShareError {
+    fn new(kind: ShareErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ShareError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}: {}", self.kind, self.message)
+    }
+}
+
+impl std::error::Error for ShareError {}
+
+/// Tunable quota/lifetime posture for a [`ShareStore`].
+#[derive(Debug, Clone)]
+pub struct ShareStoreConfig {
+    pub output_dir: PathBuf,
+    /// Delete the oldest entries once the store's total published bytes exceed this.
+    pub max_total_bytes: u64,
+    /// Delete the oldest entries once estimated free space drops below this. See
+    /// [`Self::assumed_disk_capacity_bytes`].
+    pub min_free_disk_bytes: u64,
+    /// This tree has no real free-disk-space syscall wired in (no disk-stat crate is
+    /// used anywhere else in this repo), so free space for `min_free_disk_bytes` is
+    /// estimated as this capacity minus the bytes this store has written, rather than
+    /// querying the filesystem directly.
+    pub assumed_disk_capacity_bytes: u64,
+}
+
+impl Default for ShareStoreConfig {
+    fn default() -> Self {
+        Self {
+            output_dir: PathBuf::from("./shares"),
+            max_total_bytes: 500 * 1024 * 1024,
+            min_free_disk_bytes: 100 * 1024 * 1024,
+            assumed_disk_capacity_bytes: 10 * 1024 * 1024 * 1024,
+        }
+    }
+}
+
+struct ShareEntry {
+    file_path: PathBuf,
+    size_bytes: u64,
+    format: ScreenshotFormat,
+    width: u32,
+    height: u32,
+    captured_at: DateTime<Utc>,
+    password_hash: Option<String>,
+    created_at: DateTime<Utc>,
+    max_lifetime_hours: u64,
+}
+
+impl ShareEntry {
+    fn expires_at(&self) -> DateTime<Utc> {
+        self.created_at + ChronoDuration::hours(self.max_lifetime_hours as i64)
+    }
+
+    fn is_expired(&self) -> bool {
+        Utc::now() >= self.expires_at()
+    }
+}
+
+/// Persists published [`ScreenshotResult`]s under a random unguessable token, with an
+/// optional Argon2 password and a background sweeper that deletes entries (and their
+/// files) once expired, once `max_total_bytes` is crossed, or once estimated free
+/// space drops below `min_free_disk_bytes`.
+pub struct ShareStore {
+    config: ShareStoreConfig,
+    entries: RwLock<HashMap<String, ShareEntry>>,
+    argon2: Argon2<'static>,
+    sweep_cancel: RwLock<Option<oneshot::Sender<()>>>,
+    sweep_task: RwLock<Option<JoinHandle<()>>>,
+}
+
+impl ShareStore {
+    pub fn new(config: ShareStoreConfig) -> Self {
+        Self {
+            config,
+            entries: RwLock::new(HashMap::new()),
+            argon2: Argon2::default(),
+            sweep_cancel: RwLock::new(None),
+            sweep_task: RwLock::new(None),
+        }
+    }
+
+    /// Publish `result` as a time-limited downloadable link, optionally
+    /// password-protected, expiring after `lifetime_hours`. Returns the share token.
+    pub async fn publish(
+        &self,
+        result: &ScreenshotResult,
+        password: Option<String>,
+        lifetime_hours: u64,
+    ) -> Result<String, ShareError> {
+        tokio::fs::create_dir_all(&self.config.output_dir)
+            .await
+            .map_err(|e| ShareError::new(ShareErrorKind::Internal, e.to_string()))?;
+
+        let mut token_bytes = [0u8; TOKEN_BYTES];
+        rand::thread_rng().fill_bytes(&mut token_bytes);
+        let token = URL_SAFE_NO_PAD.encode(token_bytes);
+
+        let password_hash = match password {
+            Some(password) => {
+                let salt = SaltString::generate(&mut OsRng);
+                let hash = self
+                    .argon2
+                    .hash_password(password.as_bytes(), &salt)
+                    .map_err(|e| ShareError::new(ShareErrorKind::Internal, e.to_string()))?
+                    .to_string();
+                Some(hash)
+            }
+            None => None,
+        };
+
+        let file_path = self
+            .config
+            .output_dir
+            .join(format!("{}.{}", token, result.format.extension()));
+        tokio::fs::write(&file_path, &result.data)
+            .await
+            .map_err(|e| ShareError::new(ShareErrorKind::Internal, e.to_string()))?;
+
+        let entry = ShareEntry {
+            file_path,
+            size_bytes: result.data.len() as u64,
+            format: result.format,
+            width: result.width,
+            height: result.height,
+            captured_at: result.captured_at,
+            password_hash,
+            created_at: Utc::now(),
+            max_lifetime_hours: lifetime_hours,
+        };
+
+        self.entries.write().await.insert(token.clone(), entry);
+        self.enforce_quota().await;
+
+        Ok(token)
+    }
+
+    /// Resolve a share token back to its [`ScreenshotResult`], verifying `password`
+    /// against the stored hash first if the entry was published with one. Deletes and
+    /// rejects an expired entry rather than returning its (stale) data.
+    pub async fn resolve(
+        &self,
+        token: &str,
+        password: Option<String>,
+    ) -> Result<ScreenshotResult, ShareError> {
+        let expired = {
+            let entries = self.entries.read().await;
+            let entry = entries
+                .get(token)
+                .ok_or_else(|| ShareError::new(ShareErrorKind::TokenNotFound, "no such share"))?;
+            entry.is_expired()
+        };
+        if expired {
+            self.remove(token).await;
+            return Err(ShareError::new(ShareErrorKind::Expired, "share link has expired"));
+        }
+
+        let entries = self.entries.read().await;
+        let entry = entries
+            .get(token)
+            .ok_or_else(|| ShareError::new(ShareErrorKind::TokenNotFound, "no such share"))?;
+
+        match (&entry.password_hash, password) {
+            (Some(stored), Some(password)) => {
+                let parsed = PasswordHash::new(stored)
+                    .map_err(|e| ShareError::new(ShareErrorKind::Internal, e.to_string()))?;
+                self.argon2
+                    .verify_password(password.as_bytes(), &parsed)
+                    .map_err(|_| ShareError::new(ShareErrorKind::InvalidPassword, "incorrect password"))?;
+            }
+            (Some(_), None) => {
+                return Err(ShareError::new(
+                    ShareErrorKind::PasswordRequired,
+                    "this share requires a password",
+                ))
+            }
+            (None, _) => {}
+        }
+
+        let data = tokio::fs::read(&entry.file_path)
+            .await
+            .map_err(|e| ShareError::new(ShareErrorKind::Internal, e.to_string()))?;
+
+        Ok(ScreenshotResult {
+            data,
+            format: entry.format,
+            width: entry.width,
+            height: entry.height,
+            captured_at: entry.captured_at,
+            nsfw_score: None,
+            safety_category: None,
+            blurred: false,
+            refused: false,
+        })
+    }
+
+    /// Start a background loop that sweeps expired/over-quota entries every
+    /// `interval_secs` seconds. Requires `self` behind an `Arc` since the loop
+    /// outlives this call.
+    pub async fn start_sweeper(self: &Arc<Self>, interval_secs: u64) {
+        self.stop_sweeper().await;
+
+        let (cancel_tx, mut cancel_rx) = oneshot::channel();
+        let interval = std::time::Duration::from_secs(interval_secs.max(1));
+        let store = Arc::clone(self);
+
+        let handle = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                tokio::select! {
+                    _ = &mut cancel_rx => break,
+                    _ = ticker.tick() => store.sweep_once().await,
+                }
+            }
+        });
+
+        *self.sweep_cancel.write().await = Some(cancel_tx);
+        *self.sweep_task.write().await = Some(handle);
+    }
+
+    /// Cancel the running sweeper loop, if any, and wait for it to exit.
+    pub async fn stop_sweeper(&self) {
+        if let Some(cancel) = self.sweep_cancel.write().await.take() {
+            let _ = cancel.send(());
+        }
+        if let Some(task) = self.sweep_task.write().await.take() {
+            let _ = task.await;
+        }
+    }
+
+    async fn sweep_once(&self) {
+        let expired: Vec<String> = self
+            .entries
+            .read()
+            .await
+            .iter()
+            .filter(|(_, entry)| entry.is_expired())
+            .map(|(token, _)| token.clone())
+            .collect();
+        for token in expired {
+            self.remove(&token).await;
+        }
+
+        self.enforce_quota().await;
+    }
+
+    /// Delete the oldest entries until the store is back under both
+    /// `max_total_bytes` and `min_free_disk_bytes`.
+    async fn enforce_quota(&self) {
+        loop {
+            let total_bytes: u64 = self.entries.read().await.values().map(|e| e.size_bytes).sum();
+            let free_bytes = self
+                .config
+                .assumed_disk_capacity_bytes
+                .saturating_sub(total_bytes);
+
+            let over_quota = total_bytes > self.config.max_total_bytes;
+            let under_free_disk_threshold = free_bytes < self.config.min_free_disk_bytes;
+            if !over_quota && !under_free_disk_threshold {
+                break;
+            }
+
+            let oldest_token = {
+                let entries = self.entries.read().await;
+                entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.created_at)
+                    .map(|(token, _)| token.clone())
+            };
+
+            match oldest_token {
+                Some(token) => self.remove(&token).await,
+                None => break,
+            }
+        }
+    }
+
+    async fn remove(&self, token: &str) {
+        if let Some(entry) = self.entries.write().await.remove(token) {
+            let _ = tokio::fs::remove_file(&entry.file_path).await;
+        }
+    }
+}
@@ -0,0 +1,169 @@
+//! Tests for the local-filesystem `BackupDestination` and its use from `BackupManager`'s
+//! remote export/import/list commands. The S3-compatible destination isn't covered here
+//! since exercising it needs a real (or mocked) S3-compatible endpoint.
+
+use browser_core::backup_destination::{BackupDestination, LocalFilesystemDestination};
+use browser_core::{BackupManager, BackupOptions, RemoteDestinationConfig};
+use std::path::PathBuf;
+
+fn unique_temp_dir(label: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("browser-core-remote-test-{}-{}", label, uuid::Uuid::new_v4()))
+}
+
+#[tokio::test]
+async fn test_local_filesystem_destination_round_trips_objects() {
+    let destination = LocalFilesystemDestination::new(unique_temp_dir("fs-destination"));
+
+    assert!(!destination.object_exists("a/b.json").await.unwrap());
+    destination.put_object("a/b.json", b"hello").await.unwrap();
+    assert!(destination.object_exists("a/b.json").await.unwrap());
+    assert_eq!(destination.get_object("a/b.json").await.unwrap(), b"hello");
+
+    destination.delete_object("a/b.json").await.unwrap();
+    assert!(!destination.object_exists("a/b.json").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_local_filesystem_destination_lists_objects_under_prefix() {
+    let destination = LocalFilesystemDestination::new(unique_temp_dir("fs-list"));
+
+    destination.put_object("backups/one.json", b"1").await.unwrap();
+    destination.put_object("backups/two.json", b"2").await.unwrap();
+
+    let mut keys = destination.list_objects("backups/").await.unwrap();
+    keys.sort();
+    assert_eq!(keys, vec!["backups/one.json".to_string(), "backups/two.json".to_string()]);
+}
+
+#[tokio::test]
+async fn test_export_then_import_remote_round_trips_a_backup() {
+    let source_dir = unique_temp_dir("remote-source");
+    let source = BackupManager::new(source_dir);
+
+    let info = source.create_backup(BackupOptions::default()).await.unwrap();
+
+    let remote_root = unique_temp_dir("remote-store");
+    source
+        .configure_remote_destination(RemoteDestinationConfig::LocalFilesystem { root: remote_root.clone() })
+        .await
+        .unwrap();
+    source.export_backup_remote(&info.id, "my-backups").await.unwrap();
+
+    let listed = source.list_remote_backups("my-backups").await.unwrap();
+    assert_eq!(listed, vec![info.id.clone()]);
+
+    let dest_dir = unique_temp_dir("remote-dest");
+    let dest = BackupManager::new(dest_dir);
+    dest.configure_remote_destination(RemoteDestinationConfig::LocalFilesystem { root: remote_root })
+        .await
+        .unwrap();
+
+    let imported = dest.import_backup_remote(&info.id, "my-backups", None).await.unwrap();
+    assert_eq!(imported.options.include_bookmarks, info.options.include_bookmarks);
+
+    let imported_list = dest.list_backups().await.unwrap();
+    assert_eq!(imported_list.len(), 1);
+}
+
+#[tokio::test]
+async fn test_export_remote_uploads_incremental_chunks() {
+    let source_dir = unique_temp_dir("remote-incremental-source");
+    let source = BackupManager::new(source_dir);
+
+    let options = BackupOptions {
+        incremental: true,
+        ..Default::default()
+    };
+    let info = source.create_backup(options).await.unwrap();
+
+    let remote_root = unique_temp_dir("remote-incremental-store");
+    source
+        .configure_remote_destination(RemoteDestinationConfig::LocalFilesystem { root: remote_root.clone() })
+        .await
+        .unwrap();
+    source.export_backup_remote(&info.id, "prefix").await.unwrap();
+
+    let destination = LocalFilesystemDestination::new(remote_root);
+    let chunk_keys = destination.list_objects("prefix/chunks/").await.unwrap();
+    // An empty placeholder payload yields no chunks, but the export path should still
+    // run to completion without touching any chunk object.
+    assert!(chunk_keys.is_empty());
+}
+
+#[tokio::test]
+async fn test_remote_operations_fail_clearly_without_a_configured_destination() {
+    let manager = BackupManager::new(unique_temp_dir("remote-unconfigured"));
+    let info = manager.create_backup(BackupOptions::default()).await.unwrap();
+
+    assert!(manager.export_backup_remote(&info.id, "prefix").await.is_err());
+    assert!(manager.list_remote_backups("prefix").await.is_err());
+}
+
+#[tokio::test]
+async fn test_local_filesystem_destination_head_object_reports_a_content_etag() {
+    let destination = LocalFilesystemDestination::new(unique_temp_dir("fs-head"));
+
+    assert!(destination.head_object("a/b.json").await.unwrap().is_none());
+
+    destination.put_object("a/b.json", b"hello").await.unwrap();
+    let first = destination.head_object("a/b.json").await.unwrap().unwrap();
+    assert_eq!(first.size, 5);
+    assert!(first.etag.is_some());
+
+    // Re-uploading identical bytes produces the same ETag.
+    destination.put_object("a/b.json", b"hello").await.unwrap();
+    let second = destination.head_object("a/b.json").await.unwrap().unwrap();
+    assert_eq!(first.etag, second.etag);
+
+    // Changed content produces a different ETag.
+    destination.put_object("a/b.json", b"goodbye").await.unwrap();
+    let third = destination.head_object("a/b.json").await.unwrap().unwrap();
+    assert_ne!(first.etag, third.etag);
+}
+
+#[tokio::test]
+async fn test_sync_backups_skips_unchanged_objects_on_a_second_pass() {
+    let source_dir = unique_temp_dir("sync-source");
+    let source = BackupManager::new(source_dir);
+    let _info = source.create_backup(BackupOptions::default()).await.unwrap();
+
+    let remote_root = unique_temp_dir("sync-store");
+    source
+        .configure_remote_destination(RemoteDestinationConfig::LocalFilesystem { root: remote_root })
+        .await
+        .unwrap();
+
+    let first_pass = source.sync_backups("my-backups").await.unwrap();
+    assert_eq!(first_pass.transferred, 1);
+    assert_eq!(first_pass.skipped, 0);
+
+    let second_pass = source.sync_backups("my-backups").await.unwrap();
+    assert_eq!(second_pass.transferred, 0);
+    assert_eq!(second_pass.skipped, 1);
+}
+
+#[tokio::test]
+async fn test_import_backup_remote_skips_redownload_when_unchanged() {
+    let source_dir = unique_temp_dir("sync-import-source");
+    let source = BackupManager::new(source_dir);
+    let info = source.create_backup(BackupOptions::default()).await.unwrap();
+
+    let remote_root = unique_temp_dir("sync-import-store");
+    source
+        .configure_remote_destination(RemoteDestinationConfig::LocalFilesystem { root: remote_root.clone() })
+        .await
+        .unwrap();
+    source.export_backup_remote(&info.id, "prefix").await.unwrap();
+
+    let dest_dir = unique_temp_dir("sync-import-dest");
+    let dest = BackupManager::new(dest_dir);
+    dest.configure_remote_destination(RemoteDestinationConfig::LocalFilesystem { root: remote_root })
+        .await
+        .unwrap();
+
+    let first = dest.import_backup_remote(&info.id, "prefix", None).await.unwrap();
+    // A second import of the same unchanged remote object returns the already-imported
+    // backup without needing to re-download or re-parse its archive.
+    let second = dest.import_backup_remote(&info.id, "prefix", None).await.unwrap();
+    assert_eq!(first.id, second.id);
+}
@@ -0,0 +1,593 @@
+//! Tests for the backup module and its systemd-calendar-style scheduler.
+
+use browser_core::backup::{compute_next_event, parse_calendar_event, plan_prune_backups};
+use browser_core::backup_crypto::{decrypt_payload, encrypt_payload, generate_recovery_keypair, BackupCryptoErrorKind};
+use browser_core::chunk_store::hash_chunk;
+use browser_core::{BackupData, BackupInfo, BackupManager, BackupOptions, BackupScheduler, PruneOptions};
+use chrono::{Datelike, TimeZone, Utc, Weekday};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+fn unique_temp_dir(label: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("browser-core-backup-test-{}-{}", label, uuid::Uuid::new_v4()))
+}
+
+/// A `BackupInfo` dated `days_ago` days before 2026-07-28T12:00:00Z, for prune tests.
+fn backup_days_ago(days_ago: i64) -> BackupInfo {
+    let created_at = Utc.with_ymd_and_hms(2026, 7, 28, 12, 0, 0).unwrap() - chrono::Duration::days(days_ago);
+    BackupInfo {
+        id: format!("backup-{days_ago}"),
+        filename: format!("backup-{days_ago}.json"),
+        created_at,
+        size_bytes: 0,
+        options: BackupOptions::default(),
+        manifest: None,
+        verified_at: None,
+        encrypted: false,
+    }
+}
+
+#[test]
+fn test_parse_calendar_event_daily() {
+    let event = parse_calendar_event("daily").expect("should parse");
+    assert_eq!(event.hours, Some(vec![0]));
+    assert_eq!(event.minutes, Some(vec![0]));
+    assert_eq!(event.seconds, Some(vec![0]));
+    assert_eq!(event.weekdays, None);
+}
+
+#[test]
+fn test_parse_calendar_event_weekly() {
+    let event = parse_calendar_event("weekly").expect("should parse");
+    assert_eq!(event.weekdays, Some(vec![Weekday::Mon]));
+}
+
+#[test]
+fn test_parse_calendar_event_weekday_range_with_time() {
+    let event = parse_calendar_event("mon..fri 02:30").expect("should parse");
+    assert_eq!(
+        event.weekdays,
+        Some(vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri])
+    );
+    assert_eq!(event.hours, Some(vec![2]));
+    assert_eq!(event.minutes, Some(vec![30]));
+    assert_eq!(event.seconds, Some(vec![0]));
+}
+
+#[test]
+fn test_parse_calendar_event_monthly_first_of_month() {
+    let event = parse_calendar_event("*-*-1 00:00").expect("should parse");
+    assert_eq!(event.days_of_month, Some(vec![1]));
+    assert_eq!(event.months, None);
+}
+
+#[test]
+fn test_parse_calendar_event_comma_list() {
+    let event = parse_calendar_event("*-1,4,7,10-1 03:00").expect("should parse");
+    assert_eq!(event.months, Some(vec![1, 4, 7, 10]));
+}
+
+#[test]
+fn test_parse_calendar_event_step() {
+    let event = parse_calendar_event("*:*/15").expect("should parse");
+    assert_eq!(event.minutes, Some(vec![0, 15, 30, 45]));
+}
+
+#[test]
+fn test_parse_calendar_event_rejects_empty() {
+    assert!(parse_calendar_event("").is_err());
+    assert!(parse_calendar_event("   ").is_err());
+}
+
+#[test]
+fn test_parse_calendar_event_rejects_out_of_range() {
+    assert!(parse_calendar_event("*-13-1 00:00").is_err());
+    assert!(parse_calendar_event("25:00").is_err());
+}
+
+#[test]
+fn test_parse_calendar_event_rejects_unknown_weekday() {
+    assert!(parse_calendar_event("xyz 00:00").is_err());
+}
+
+#[test]
+fn test_compute_next_event_daily_advances_to_tomorrow_midnight() {
+    let event = parse_calendar_event("daily").unwrap();
+    let now = Utc.with_ymd_and_hms(2026, 7, 28, 15, 30, 0).unwrap();
+
+    let next = compute_next_event(&event, now).expect("should find a next occurrence");
+    assert_eq!(next, Utc.with_ymd_and_hms(2026, 7, 29, 0, 0, 0).unwrap());
+}
+
+#[test]
+fn test_compute_next_event_same_day_if_still_ahead() {
+    let event = parse_calendar_event("14:00").unwrap();
+    let now = Utc.with_ymd_and_hms(2026, 7, 28, 8, 0, 0).unwrap();
+
+    let next = compute_next_event(&event, now).expect("should find a next occurrence");
+    assert_eq!(next, Utc.with_ymd_and_hms(2026, 7, 28, 14, 0, 0).unwrap());
+}
+
+#[test]
+fn test_compute_next_event_weekly_skips_to_next_monday() {
+    let event = parse_calendar_event("weekly").unwrap();
+    // 2026-07-28 is a Tuesday.
+    let now = Utc.with_ymd_and_hms(2026, 7, 28, 10, 0, 0).unwrap();
+
+    let next = compute_next_event(&event, now).expect("should find a next occurrence");
+    assert_eq!(next.weekday(), Weekday::Mon);
+    assert!(next > now);
+}
+
+#[test]
+fn test_compute_next_event_monthly_rolls_into_next_month() {
+    let event = parse_calendar_event("monthly").unwrap();
+    let now = Utc.with_ymd_and_hms(2026, 7, 28, 10, 0, 0).unwrap();
+
+    let next = compute_next_event(&event, now).expect("should find a next occurrence");
+    assert_eq!((next.year(), next.month(), next.day()), (2026, 8, 1));
+}
+
+#[test]
+fn test_compute_next_event_impossible_expression_returns_none() {
+    // February never has a 30th day.
+    let event = parse_calendar_event("*-2-30 00:00").unwrap();
+    let now = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+
+    assert!(compute_next_event(&event, now).is_none());
+}
+
+#[test]
+fn test_plan_prune_keep_last_only() {
+    let backups: Vec<BackupInfo> = (0..5).map(backup_days_ago).collect();
+    let options = PruneOptions {
+        keep_last: 2,
+        ..Default::default()
+    };
+
+    let report = plan_prune_backups(&backups, &options);
+
+    assert_eq!(report.kept.len(), 2);
+    assert_eq!(report.kept[0].id, "backup-0");
+    assert_eq!(report.kept[1].id, "backup-1");
+    assert_eq!(report.removed.len(), 3);
+}
+
+#[test]
+fn test_plan_prune_keep_daily_dedupes_same_day() {
+    let now = Utc.with_ymd_and_hms(2026, 7, 28, 12, 0, 0).unwrap();
+    let backups = vec![
+        BackupInfo {
+            id: "morning".to_string(),
+            filename: "morning.json".to_string(),
+            created_at: now - chrono::Duration::hours(2),
+            size_bytes: 0,
+            options: BackupOptions::default(),
+            manifest: None,
+            verified_at: None,
+            encrypted: false,
+        },
+        BackupInfo {
+            id: "evening".to_string(),
+            filename: "evening.json".to_string(),
+            created_at: now,
+            size_bytes: 0,
+            options: BackupOptions::default(),
+            manifest: None,
+            verified_at: None,
+            encrypted: false,
+        },
+        backup_days_ago(1),
+    ];
+    // Sort newest-first, as `plan_prune_backups` expects.
+    let mut sorted = backups;
+    sorted.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+    let options = PruneOptions {
+        keep_daily: 2,
+        ..Default::default()
+    };
+    let report = plan_prune_backups(&sorted, &options);
+
+    // Only the newest backup of today's bucket should be kept, plus yesterday's.
+    assert_eq!(report.kept.len(), 2);
+    assert!(report.kept.iter().any(|b| b.id == "evening"));
+    assert!(report.kept.iter().any(|b| b.id == "backup-1"));
+    assert!(report.removed.iter().any(|b| b.id == "morning"));
+}
+
+#[test]
+fn test_plan_prune_backup_kept_if_any_rule_selects_it() {
+    let backups: Vec<BackupInfo> = (0..40).map(backup_days_ago).collect();
+    let options = PruneOptions {
+        keep_last: 1,
+        keep_monthly: 2,
+        ..Default::default()
+    };
+
+    let report = plan_prune_backups(&backups, &options);
+
+    // backup-0 is kept by keep_last; something in a second, older monthly bucket
+    // should also survive even though it isn't among the most recent.
+    assert!(report.kept.iter().any(|b| b.id == "backup-0"));
+    assert!(report.kept.len() >= 2);
+    assert_eq!(report.kept.len() + report.removed.len(), 40);
+}
+
+#[tokio::test]
+async fn test_backup_manager_create_list_delete() {
+    let dir = unique_temp_dir("manager");
+    let manager = BackupManager::new(dir);
+
+    let info = manager
+        .create_backup(BackupOptions::default())
+        .await
+        .expect("should create backup");
+
+    let backups = manager.list_backups().await.unwrap();
+    assert_eq!(backups.len(), 1);
+    assert_eq!(backups[0].id, info.id);
+
+    manager.delete_backup(&info.id).await.unwrap();
+    assert!(manager.list_backups().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_backup_scheduler_set_and_get_schedule() {
+    let backup_dir = unique_temp_dir("backups");
+    let state_dir = unique_temp_dir("state");
+    let manager = Arc::new(BackupManager::new(backup_dir));
+    let scheduler = Arc::new(BackupScheduler::new(manager, state_dir));
+
+    let settings = scheduler
+        .set_schedule("daily".to_string(), BackupOptions::default())
+        .await
+        .expect("should accept a valid schedule");
+
+    let fetched = scheduler.get_schedule().await.expect("schedule should be set");
+    assert_eq!(fetched.schedule, settings.schedule);
+    assert_eq!(fetched.next_run, settings.next_run);
+}
+
+#[tokio::test]
+async fn test_backup_scheduler_rejects_invalid_expression() {
+    let backup_dir = unique_temp_dir("backups");
+    let state_dir = unique_temp_dir("state");
+    let manager = Arc::new(BackupManager::new(backup_dir));
+    let scheduler = Arc::new(BackupScheduler::new(manager, state_dir));
+
+    let result = scheduler.set_schedule("not a schedule!!".to_string(), BackupOptions::default()).await;
+    assert!(result.is_err());
+    assert!(scheduler.get_schedule().await.is_none());
+}
+
+#[tokio::test]
+async fn test_verify_backup_ok_for_untouched_backup() {
+    let dir = unique_temp_dir("verify-ok");
+    let manager = BackupManager::new(dir);
+
+    let info = manager.create_backup(BackupOptions::default()).await.unwrap();
+    let report = manager.verify_backup(&info.id).await.unwrap();
+
+    assert!(report.ok);
+    assert!(report.errors.is_empty());
+    let refreshed = manager
+        .list_backups()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|b| b.id == info.id)
+        .unwrap();
+    assert!(refreshed.verified_at.is_some());
+}
+
+#[tokio::test]
+async fn test_verify_backup_detects_missing_archive_file() {
+    let dir = unique_temp_dir("verify-missing");
+    let manager = BackupManager::new(dir.clone());
+
+    let info = manager.create_backup(BackupOptions::default()).await.unwrap();
+    // Simulate the archive having been deleted out from under the manager, without
+    // going through `delete_backup` (which would also drop the catalog entry).
+    tokio::fs::remove_file(dir.join(&info.filename)).await.unwrap();
+
+    let report = manager.verify_backup(&info.id).await.unwrap();
+    assert!(!report.ok);
+    assert_eq!(report.errors.len(), 1);
+    assert!(matches!(report.errors[0], browser_core::VerifyError::MissingArchiveFile));
+}
+
+#[tokio::test]
+async fn test_verify_backup_detects_payload_checksum_mismatch() {
+    let dir = unique_temp_dir("verify-corrupt");
+    let manager = BackupManager::new(dir.clone());
+
+    let info = manager.create_backup(BackupOptions::default()).await.unwrap();
+    let archive_path = dir.join(&info.filename);
+    let bytes = tokio::fs::read(&archive_path).await.unwrap();
+    let mut data: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    data["payload_digest"] = serde_json::json!("0000000000000000000000000000000000000000000000000000000000000000");
+    tokio::fs::write(&archive_path, serde_json::to_vec(&data).unwrap()).await.unwrap();
+
+    let report = manager.verify_backup(&info.id).await.unwrap();
+    assert!(!report.ok);
+    assert!(matches!(
+        report.errors[0],
+        browser_core::VerifyError::PayloadChecksumMismatch { .. }
+    ));
+}
+
+#[tokio::test]
+async fn test_verify_all_backups_covers_every_backup() {
+    let dir = unique_temp_dir("verify-all");
+    let manager = BackupManager::new(dir);
+
+    manager.create_backup(BackupOptions::default()).await.unwrap();
+    manager.create_backup(BackupOptions::default()).await.unwrap();
+
+    let reports = manager.verify_all_backups().await.unwrap();
+    assert_eq!(reports.len(), 2);
+    assert!(reports.iter().all(|r| r.ok));
+}
+
+#[test]
+fn test_encrypt_decrypt_payload_round_trips_with_password() {
+    let payload = b"secret browser data".repeat(10);
+    let encrypted = encrypt_payload(&payload, "correct horse battery staple", "backup-1", None).unwrap();
+
+    let decrypted = decrypt_payload(&encrypted, Some("correct horse battery staple"), None).unwrap();
+    assert_eq!(decrypted, payload);
+}
+
+#[test]
+fn test_decrypt_payload_rejects_wrong_password() {
+    let payload = b"secret browser data".to_vec();
+    let encrypted = encrypt_payload(&payload, "right password", "backup-1", None).unwrap();
+
+    let err = decrypt_payload(&encrypted, Some("wrong password"), None).unwrap_err();
+    assert_eq!(err.kind, BackupCryptoErrorKind::WrongCredentials);
+}
+
+#[test]
+fn test_decrypt_payload_detects_tampered_chunk() {
+    let payload = b"secret browser data".to_vec();
+    let mut encrypted = encrypt_payload(&payload, "a password", "backup-1", None).unwrap();
+    encrypted.chunks[0].ciphertext[0] ^= 0xFF;
+
+    let err = decrypt_payload(&encrypted, Some("a password"), None).unwrap_err();
+    assert_eq!(err.kind, BackupCryptoErrorKind::CorruptChunk);
+}
+
+#[test]
+fn test_encrypt_decrypt_payload_round_trips_with_recovery_key() {
+    let (recovery_public, recovery_private) = generate_recovery_keypair();
+    let payload = b"secret browser data".to_vec();
+    let encrypted = encrypt_payload(&payload, "a password", "backup-1", Some(&recovery_public)).unwrap();
+
+    let decrypted = decrypt_payload(&encrypted, None, Some(&recovery_private)).unwrap();
+    assert_eq!(decrypted, payload);
+}
+
+#[test]
+fn test_decrypt_payload_rejects_mismatched_recovery_key() {
+    let (recovery_public, _) = generate_recovery_keypair();
+    let (_, other_private) = generate_recovery_keypair();
+    let payload = b"secret browser data".to_vec();
+    let encrypted = encrypt_payload(&payload, "a password", "backup-1", Some(&recovery_public)).unwrap();
+
+    let err = decrypt_payload(&encrypted, None, Some(&other_private)).unwrap_err();
+    assert_eq!(err.kind, BackupCryptoErrorKind::WrongCredentials);
+}
+
+#[test]
+fn test_decrypt_payload_without_recovery_key_requires_one() {
+    let payload = b"secret browser data".to_vec();
+    let encrypted = encrypt_payload(&payload, "a password", "backup-1", None).unwrap();
+
+    let (_, some_private) = generate_recovery_keypair();
+    let err = decrypt_payload(&encrypted, None, Some(&some_private)).unwrap_err();
+    assert_eq!(err.kind, BackupCryptoErrorKind::NoRecoveryKey);
+}
+
+#[tokio::test]
+async fn test_backup_manager_create_backup_with_password_is_marked_encrypted() {
+    let dir = unique_temp_dir("encrypted-create");
+    let manager = BackupManager::new(dir);
+
+    let options = BackupOptions {
+        password: Some("hunter2".to_string()),
+        ..Default::default()
+    };
+    let info = manager.create_backup(options).await.unwrap();
+    assert!(info.encrypted);
+
+    let report = manager.verify_backup(&info.id).await.unwrap();
+    assert!(report.ok);
+}
+
+#[tokio::test]
+async fn test_backup_manager_restore_fails_with_wrong_password() {
+    let dir = unique_temp_dir("encrypted-restore");
+    let manager = BackupManager::new(dir);
+
+    let options = BackupOptions {
+        password: Some("hunter2".to_string()),
+        ..Default::default()
+    };
+    let info = manager.create_backup(options).await.unwrap();
+
+    let result = manager.restore_backup(&info.id, Some("wrong")).await;
+    assert!(result.is_err());
+
+    let result = manager.restore_backup(&info.id, Some("hunter2")).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_backup_manager_restore_with_recovery_key() {
+    let dir = unique_temp_dir("encrypted-recovery");
+    let manager = BackupManager::new(dir);
+
+    let (recovery_public, recovery_private) = generate_recovery_keypair();
+    let options = BackupOptions {
+        password: Some("hunter2".to_string()),
+        recovery_public_key: Some(recovery_public),
+        ..Default::default()
+    };
+    let info = manager.create_backup(options).await.unwrap();
+
+    let result = manager.restore_backup_with_recovery_key(&info.id, &recovery_private).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_backup_manager_import_encrypted_backup_validates_password() {
+    let backup_dir = unique_temp_dir("encrypted-export");
+    let manager = BackupManager::new(backup_dir);
+
+    let options = BackupOptions {
+        password: Some("hunter2".to_string()),
+        ..Default::default()
+    };
+    let info = manager.create_backup(options).await.unwrap();
+
+    let export_path = unique_temp_dir("encrypted-exported-file");
+    let export_path = export_path.to_str().unwrap().to_string();
+    manager.export_backup(&info.id, &export_path).await.unwrap();
+
+    let import_dir = unique_temp_dir("encrypted-import");
+    let importer = BackupManager::new(import_dir);
+
+    let wrong = importer.import_backup(&export_path, Some("wrong")).await;
+    assert!(wrong.is_err());
+
+    let right = importer.import_backup(&export_path, Some("hunter2")).await;
+    assert!(right.is_ok());
+    assert!(right.unwrap().encrypted);
+}
+
+#[tokio::test]
+async fn test_backup_scheduler_set_and_get_verify_schedule() {
+    let backup_dir = unique_temp_dir("backups");
+    let state_dir = unique_temp_dir("state");
+    let manager = Arc::new(BackupManager::new(backup_dir));
+    let scheduler = Arc::new(BackupScheduler::new(manager, state_dir));
+
+    let settings = scheduler
+        .set_verify_schedule("daily".to_string())
+        .await
+        .expect("should accept a valid schedule");
+
+    let fetched = scheduler.get_verify_schedule().await.expect("schedule should be set");
+    assert_eq!(fetched.schedule, settings.schedule);
+    assert_eq!(fetched.next_run, settings.next_run);
+}
+
+#[tokio::test]
+async fn test_backup_scheduler_clear_verify_schedule() {
+    let backup_dir = unique_temp_dir("backups");
+    let state_dir = unique_temp_dir("state");
+    let manager = Arc::new(BackupManager::new(backup_dir));
+    let scheduler = Arc::new(BackupScheduler::new(manager, state_dir));
+
+    scheduler.set_verify_schedule("daily".to_string()).await.unwrap();
+    scheduler.clear_verify_schedule().await.unwrap();
+
+    assert!(scheduler.get_verify_schedule().await.is_none());
+}
+
+#[tokio::test]
+async fn test_backup_scheduler_clear_schedule() {
+    let backup_dir = unique_temp_dir("backups");
+    let state_dir = unique_temp_dir("state");
+    let manager = Arc::new(BackupManager::new(backup_dir));
+    let scheduler = Arc::new(BackupScheduler::new(manager, state_dir));
+
+    scheduler
+        .set_schedule("daily".to_string(), BackupOptions::default())
+        .await
+        .unwrap();
+    scheduler.clear_schedule().await.unwrap();
+
+    assert!(scheduler.get_schedule().await.is_none());
+}
+
+#[tokio::test]
+async fn test_backup_manager_load_rebuilds_catalog_and_chunk_refcounts() {
+    let dir = unique_temp_dir("load");
+    tokio::fs::create_dir_all(dir.join("chunks")).await.unwrap();
+
+    // Two archives share one chunk, so the rebuilt refcount for it should start at 2.
+    let digest = hash_chunk(b"shared chunk payload");
+    tokio::fs::write(dir.join("chunks").join(&digest), b"shared chunk payload")
+        .await
+        .unwrap();
+
+    let make_data = |id: &str| BackupData {
+        id: id.to_string(),
+        format_version: 1,
+        created_at: Utc::now(),
+        options: BackupOptions::default(),
+        payload: Vec::new(),
+        manifest: Some(vec![digest.clone()]),
+        payload_digest: hash_chunk(&[]),
+        encryption: None,
+        encrypted_chunks: None,
+    };
+
+    tokio::fs::write(
+        dir.join("backup-a.json"),
+        serde_json::to_vec(&make_data("backup-a-id")).unwrap(),
+    )
+    .await
+    .unwrap();
+    tokio::fs::write(
+        dir.join("backup-b.json"),
+        serde_json::to_vec(&make_data("backup-b-id")).unwrap(),
+    )
+    .await
+    .unwrap();
+
+    // An archive that isn't valid JSON at all...
+    tokio::fs::write(dir.join("backup-corrupt.json"), b"not json")
+        .await
+        .unwrap();
+
+    // ...and one that parses fine but never recorded an id (e.g. written before `id`
+    // existed in the format) -- both should be skipped rather than breaking `load`.
+    let mut missing_id = serde_json::to_value(make_data("backup-c-id")).unwrap();
+    missing_id.as_object_mut().unwrap().remove("id");
+    tokio::fs::write(
+        dir.join("backup-missing-id.json"),
+        serde_json::to_vec(&missing_id).unwrap(),
+    )
+    .await
+    .unwrap();
+
+    let chunk_path = dir.join("chunks").join(&digest);
+    let manager = BackupManager::load(dir).await.expect("load should succeed");
+
+    let mut backups = manager.list_backups().await.unwrap();
+    backups.sort_by(|a, b| a.id.cmp(&b.id));
+    assert_eq!(backups.len(), 2);
+    assert_eq!(backups[0].id, "backup-a-id");
+    assert_eq!(backups[1].id, "backup-b-id");
+    assert_eq!(backups[0].manifest, Some(vec![digest.clone()]));
+
+    // Both archives reference the same chunk, so deleting one should leave its
+    // refcount at 1 (chunk file still present); deleting the second should drop it
+    // to 0 and remove the chunk file. This only passes if `load` rebuilt the refcount
+    // as 2, not 0 (unreferenced, would delete the chunk on the first release) or 1
+    // (would delete it a release too early).
+    manager.delete_backup("backup-a-id").await.unwrap();
+    assert!(
+        tokio::fs::try_exists(&chunk_path).await.unwrap(),
+        "chunk should survive while still referenced by backup-b-id"
+    );
+
+    manager.delete_backup("backup-b-id").await.unwrap();
+    assert!(
+        !tokio::fs::try_exists(&chunk_path).await.unwrap(),
+        "chunk should be removed once its last reference is released"
+    );
+}
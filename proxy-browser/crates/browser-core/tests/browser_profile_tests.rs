@@ -0,0 +1,72 @@
+//! Tests for on-disk persistence and crash recovery in BrowserProfileManager
+
+use browser_core::browser_profile::{BrowserProfileManager, ProfileSettings};
+use std::path::PathBuf;
+
+fn unique_temp_dir(label: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("browser-core-profile-test-{}-{}", label, uuid::Uuid::new_v4()))
+}
+
+#[tokio::test]
+async fn test_load_with_no_index_starts_empty() {
+    let base_dir = unique_temp_dir("empty");
+    let manager = BrowserProfileManager::load(base_dir).await.unwrap();
+
+    assert!(manager.list_profiles().await.is_empty());
+    assert!(manager.get_active_profile().await.is_none());
+}
+
+#[tokio::test]
+async fn test_profiles_survive_a_reload() {
+    let base_dir = unique_temp_dir("reload");
+    let manager = BrowserProfileManager::load(base_dir.clone()).await.unwrap();
+
+    let profile = manager.create_profile("Work", true).await.unwrap();
+    manager.switch_profile(&profile.id).await.unwrap();
+
+    let reloaded = BrowserProfileManager::load(base_dir).await.unwrap();
+    let profiles = reloaded.list_profiles().await;
+    assert_eq!(profiles.len(), 1);
+    assert_eq!(profiles[0].id, profile.id);
+    assert_eq!(reloaded.get_active_profile().await.unwrap().id, profile.id);
+}
+
+#[tokio::test]
+async fn test_reload_drops_profiles_whose_data_dir_is_gone() {
+    let base_dir = unique_temp_dir("stale");
+    let manager = BrowserProfileManager::load(base_dir.clone()).await.unwrap();
+
+    let profile = manager.create_profile("Temp", false).await.unwrap();
+    tokio::fs::remove_dir_all(&profile.data_dir).await.unwrap();
+
+    let reloaded = BrowserProfileManager::load(base_dir).await.unwrap();
+    assert!(reloaded.list_profiles().await.is_empty());
+}
+
+#[tokio::test]
+async fn test_reload_clears_active_profile_id_if_its_profile_is_gone() {
+    let base_dir = unique_temp_dir("stale-active");
+    let manager = BrowserProfileManager::load(base_dir.clone()).await.unwrap();
+
+    let profile = manager.create_profile("Temp", false).await.unwrap();
+    manager.switch_profile(&profile.id).await.unwrap();
+    manager.delete_profile(&profile.id).await.unwrap();
+
+    let reloaded = BrowserProfileManager::load(base_dir).await.unwrap();
+    assert!(reloaded.get_active_profile().await.is_none());
+}
+
+#[tokio::test]
+async fn test_update_settings_persists_across_reload() {
+    let base_dir = unique_temp_dir("settings");
+    let manager = BrowserProfileManager::load(base_dir.clone()).await.unwrap();
+
+    let profile = manager.create_profile("Work", false).await.unwrap();
+    let mut settings = ProfileSettings::default();
+    settings.language = "fr".to_string();
+    manager.update_settings(&profile.id, settings).await.unwrap();
+
+    let reloaded = BrowserProfileManager::load(base_dir).await.unwrap();
+    let reloaded_profile = reloaded.get_profile(&profile.id).await.unwrap();
+    assert_eq!(reloaded_profile.settings.language, "fr");
+}
@@ -0,0 +1,150 @@
+#![cfg(feature = "chromium")]
+//! Tests for WebDriver-style capabilities negotiation on `ChromiumEngineConfig`.
+
+use browser_core::chromium_engine::{ChromiumEngine, ChromiumEngineConfig, CookieIsolationMode};
+use serde_json::json;
+
+#[test]
+fn test_from_capabilities_empty_object_is_default() {
+    let config = ChromiumEngineConfig::from_capabilities(&json!({})).expect("should negotiate");
+    let default = ChromiumEngineConfig::default();
+
+    assert_eq!(config.headless, default.headless);
+    assert_eq!(config.viewport_width, default.viewport_width);
+}
+
+#[test]
+fn test_from_capabilities_merges_known_keys() {
+    let capabilities = json!({
+        "browserName": "chrome",
+        "headless": true,
+        "userAgent": "CapabilitiesUA/1.0",
+        "viewport": { "width": 1366, "height": 768 },
+        "geolocation": { "latitude": 51.5074, "longitude": -0.1278, "accuracy": 50.0 },
+        "cookieIsolation": "perTab",
+        "blockedUrls": ["*.ads.com", "*.tracker.com"],
+    });
+
+    let config = ChromiumEngineConfig::from_capabilities(&capabilities).expect("should negotiate");
+
+    assert!(config.headless);
+    assert_eq!(config.user_agent, Some("CapabilitiesUA/1.0".to_string()));
+    assert_eq!(config.viewport_width, 1366);
+    assert_eq!(config.viewport_height, 768);
+    let geo = config.geolocation.expect("geolocation should be set");
+    assert_eq!(geo.latitude, 51.5074);
+    assert!(matches!(config.cookie_isolation, CookieIsolationMode::PerTab));
+    assert_eq!(config.blocked_urls.len(), 2);
+    assert!(config.enable_interception);
+}
+
+#[test]
+fn test_from_capabilities_merges_proxy() {
+    let capabilities = json!({
+        "proxy": { "proxyType": "manual", "httpProxy": "proxy.example.com:8080" },
+    });
+
+    let config = ChromiumEngineConfig::from_capabilities(&capabilities).expect("should negotiate");
+    let proxy = config.proxy.expect("proxy should be set");
+
+    assert_eq!(proxy.host, Some("proxy.example.com".to_string()));
+    assert_eq!(proxy.port, Some(8080));
+}
+
+#[test]
+fn test_from_capabilities_rejects_unknown_key() {
+    let capabilities = json!({ "pageLoadStrategy": "eager" });
+
+    let err = ChromiumEngineConfig::from_capabilities(&capabilities)
+        .expect_err("unknown capability should be rejected");
+
+    assert_eq!(err.failures.len(), 1);
+    assert_eq!(err.failures[0].capability, "pageLoadStrategy");
+}
+
+#[test]
+fn test_from_capabilities_rejects_unsupported_browser() {
+    let capabilities = json!({ "browserName": "firefox" });
+
+    let err = ChromiumEngineConfig::from_capabilities(&capabilities)
+        .expect_err("unsupported browser should be rejected");
+
+    assert_eq!(err.failures[0].capability, "browserName");
+    assert!(err.failures[0].reason.contains("firefox"));
+}
+
+#[test]
+fn test_from_capabilities_collects_multiple_failures() {
+    let capabilities = json!({
+        "browserName": "firefox",
+        "unknownThing": true,
+        "headless": "not-a-bool",
+    });
+
+    let err = ChromiumEngineConfig::from_capabilities(&capabilities)
+        .expect_err("multiple capabilities should be rejected");
+
+    assert_eq!(err.failures.len(), 3);
+}
+
+#[test]
+fn test_from_capabilities_rejects_non_object_root() {
+    let err = ChromiumEngineConfig::from_capabilities(&json!("not-an-object"))
+        .expect_err("non-object root should be rejected");
+
+    assert_eq!(err.failures.len(), 1);
+}
+
+#[test]
+fn test_capabilities_error_display_lists_failures() {
+    let capabilities = json!({ "unknownThing": true });
+    let err = ChromiumEngineConfig::from_capabilities(&capabilities).expect_err("should fail");
+
+    let message = err.to_string();
+    assert!(message.contains("unknownThing"));
+    assert!(message.contains("unknown capability"));
+}
+
+#[test]
+fn test_from_capabilities_merges_partitioned_cookie_isolation() {
+    let capabilities = json!({
+        "cookieIsolation": "partitioned",
+        "cookiePartitionAllowlist": ["payments.example.com"],
+        "thirdPartyGraceWindowSecs": 60,
+    });
+
+    let config = ChromiumEngineConfig::from_capabilities(&capabilities).expect("should negotiate");
+
+    assert!(matches!(config.cookie_isolation, CookieIsolationMode::Partitioned));
+    assert_eq!(config.cookie_partition_allowlist, vec!["payments.example.com".to_string()]);
+    assert_eq!(config.third_party_grace_window_secs, 60);
+}
+
+#[tokio::test]
+async fn test_engine_from_capabilities() {
+    let capabilities = json!({ "headless": true, "viewport": { "width": 1280, "height": 720 } });
+
+    let engine = ChromiumEngine::from_capabilities(&capabilities).expect("should negotiate");
+    let config = engine.get_config();
+
+    assert!(config.headless);
+    assert_eq!(config.viewport_width, 1280);
+    assert_eq!(config.viewport_height, 720);
+}
+
+#[test]
+fn test_effective_capabilities_reports_config() {
+    let config = ChromiumEngineConfig {
+        headless: true,
+        user_agent: Some("ReportedUA/1.0".to_string()),
+        ..Default::default()
+    };
+
+    let engine = ChromiumEngine::new(config);
+    let reported = engine.effective_capabilities();
+
+    assert_eq!(reported["browserName"], "chromium");
+    assert_eq!(reported["headless"], true);
+    assert_eq!(reported["userAgent"], "ReportedUA/1.0");
+    assert_eq!(reported["viewport"]["width"], 1920);
+}
@@ -211,6 +211,7 @@ fn test_chromium_engine_config_default() {
     assert!(config.blocked_urls.is_empty());
     assert!(!config.enable_interception);
     assert!(config.geolocation.is_none());
+    assert!(config.extra_chrome_flags.is_empty());
 }
 
 #[test]
@@ -0,0 +1,78 @@
+#![cfg(feature = "chromium")]
+//! Tests for partitioned cookie isolation and third-party grace grants.
+
+use browser_core::chromium_engine::{ChromiumEngine, ChromiumEngineConfig, CookieIsolationMode};
+
+#[tokio::test]
+async fn test_partitioned_blocks_third_party_by_default() {
+    let mut config = ChromiumEngineConfig::default();
+    config.cookie_isolation = CookieIsolationMode::Partitioned;
+    let engine = ChromiumEngine::new(config);
+
+    assert!(!engine.is_third_party_cookie_allowed("a.com", "tracker.com").await);
+}
+
+#[tokio::test]
+async fn test_partitioned_always_allows_first_party() {
+    let mut config = ChromiumEngineConfig::default();
+    config.cookie_isolation = CookieIsolationMode::Partitioned;
+    let engine = ChromiumEngine::new(config);
+
+    assert!(engine.is_third_party_cookie_allowed("a.com", "a.com").await);
+}
+
+#[tokio::test]
+async fn test_partitioned_allowlisted_site_is_always_allowed() {
+    let mut config = ChromiumEngineConfig::default();
+    config.cookie_isolation = CookieIsolationMode::Partitioned;
+    config.cookie_partition_allowlist = vec!["payments.example.com".to_string()];
+    let engine = ChromiumEngine::new(config);
+
+    assert!(engine.is_third_party_cookie_allowed("a.com", "payments.example.com").await);
+}
+
+#[tokio::test]
+async fn test_grant_temporarily_allows_third_party() {
+    let mut config = ChromiumEngineConfig::default();
+    config.cookie_isolation = CookieIsolationMode::Partitioned;
+    let engine = ChromiumEngine::new(config);
+
+    engine.grant_third_party_cookie_access("a.com", "sso.example.com").await;
+    assert!(engine.is_third_party_cookie_allowed("a.com", "sso.example.com").await);
+    // The grant is scoped to the (top-level, third-party) pair, not the third party alone.
+    assert!(!engine.is_third_party_cookie_allowed("b.com", "sso.example.com").await);
+}
+
+#[tokio::test]
+async fn test_non_partitioned_modes_never_block_third_party() {
+    for mode in [
+        CookieIsolationMode::None,
+        CookieIsolationMode::PerTab,
+        CookieIsolationMode::PerDomain,
+        CookieIsolationMode::FullContext,
+    ] {
+        let mut config = ChromiumEngineConfig::default();
+        config.cookie_isolation = mode;
+        let engine = ChromiumEngine::new(config);
+
+        assert!(engine.is_third_party_cookie_allowed("a.com", "tracker.com").await);
+    }
+}
+
+#[tokio::test]
+async fn test_grant_cap_evicts_soonest_expiring() {
+    let mut config = ChromiumEngineConfig::default();
+    config.cookie_isolation = CookieIsolationMode::Partitioned;
+    config.max_active_cookie_grants = 2;
+    let engine = ChromiumEngine::new(config);
+
+    engine.grant_third_party_cookie_access("a.com", "one.com").await;
+    engine.grant_third_party_cookie_access("a.com", "two.com").await;
+    engine.grant_third_party_cookie_access("a.com", "three.com").await;
+
+    assert_eq!(engine.active_cookie_grant_count().await, 2);
+    // "one.com" was granted first, so its grant expires soonest and is the one evicted.
+    assert!(!engine.is_third_party_cookie_allowed("a.com", "one.com").await);
+    assert!(engine.is_third_party_cookie_allowed("a.com", "two.com").await);
+    assert!(engine.is_third_party_cookie_allowed("a.com", "three.com").await);
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "chromium")]
+//! Tests for cross-platform Chrome/Chromium discovery. These pin down only the
+//! `CHROME`/`CHROMIUM_PATH` environment override and that `ChromeLocator::system_only`
+//! agrees with `default_executable`, since the registry, `$PATH` and well-known-path
+//! lookups depend on what's actually installed on the machine running the test.
+
+use browser_core::{default_executable, ChromeLocator};
+use std::sync::Mutex;
+
+// `default_executable` reads process-wide environment variables, so tests that set
+// them must not run concurrently with each other.
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+#[test]
+fn test_default_executable_prefers_chrome_env_override() {
+    let _guard = ENV_LOCK.lock().unwrap();
+    let binary = std::env::temp_dir().join(format!("browser-core-detect-test-{}", uuid::Uuid::new_v4()));
+    std::fs::write(&binary, b"fake-chromium-binary").unwrap();
+
+    std::env::set_var("CHROME", &binary);
+    let resolved = default_executable();
+    std::env::remove_var("CHROME");
+    std::fs::remove_file(&binary).ok();
+
+    assert_eq!(resolved.unwrap(), binary);
+}
+
+#[test]
+fn test_default_executable_ignores_nonexistent_env_override() {
+    let _guard = ENV_LOCK.lock().unwrap();
+    std::env::set_var("CHROMIUM_PATH", "/this/path/does/not/exist/chromium");
+    let resolved = default_executable();
+    std::env::remove_var("CHROMIUM_PATH");
+
+    // Falls through to registry/$PATH/well-known lookups rather than trusting a
+    // nonexistent override; whether it ultimately succeeds depends on the machine.
+    if let Ok(path) = resolved {
+        assert_ne!(path.to_string_lossy(), "/this/path/does/not/exist/chromium");
+    }
+}
+
+#[tokio::test]
+async fn test_system_only_locator_agrees_with_default_executable() {
+    let _guard = ENV_LOCK.lock().unwrap();
+    let located = ChromeLocator::system_only().locate().await;
+    let direct = default_executable();
+
+    match (located, direct) {
+        (Ok(a), Ok(b)) => assert_eq!(a, b),
+        (Err(_), Err(_)) => {}
+        _ => panic!("ChromeLocator::system_only should agree with default_executable"),
+    }
+}
@@ -187,6 +187,21 @@ async fn test_engine_with_extra_args() {
     assert!(args.contains(&"--no-sandbox".to_string()));
 }
 
+#[tokio::test]
+async fn test_engine_with_extra_chrome_flags() {
+    let config = ChromiumEngineConfig {
+        extra_chrome_flags: vec!["--lang=fr".to_string(), "--disable-gpu".to_string()],
+        ..Default::default()
+    };
+
+    let engine = ChromiumEngine::new(config);
+    let flags = &engine.get_config().extra_chrome_flags;
+
+    assert_eq!(flags.len(), 2);
+    assert!(flags.contains(&"--lang=fr".to_string()));
+    assert!(flags.contains(&"--disable-gpu".to_string()));
+}
+
 #[tokio::test]
 async fn test_engine_with_user_data_dir() {
     let config = ChromiumEngineConfig {
@@ -209,6 +224,49 @@ async fn test_engine_with_executable_path() {
     assert!(engine.get_config().executable_path.is_some());
 }
 
+#[tokio::test]
+async fn test_resolve_executable_prefers_explicit_override() {
+    let config = ChromiumEngineConfig {
+        executable_path: Some(PathBuf::from("/usr/bin/my-custom-chromium")),
+        ..Default::default()
+    };
+
+    let engine = ChromiumEngine::new(config);
+    let resolved = engine.resolve_executable().await.unwrap();
+    assert_eq!(resolved, PathBuf::from("/usr/bin/my-custom-chromium"));
+}
+
+#[tokio::test]
+async fn test_resolve_executable_fails_without_auto_fetch_or_system_install() {
+    // auto_fetch defaults to false, so if no system Chrome/Chromium happens to be
+    // installed on the machine running this test, resolution should fail clearly
+    // rather than silently reaching the network.
+    let config = ChromiumEngineConfig::default();
+    let engine = ChromiumEngine::new(config);
+
+    if let Err(e) = engine.resolve_executable().await {
+        // Expected on a machine with no system Chrome/Chromium installed.
+        assert_eq!(e.kind, browser_core::chromium_engine::ChromiumErrorKind::ExecutableNotFound);
+    }
+}
+
+#[tokio::test]
+async fn test_launch_fails_clearly_when_no_executable_can_be_resolved() {
+    // An install_dir the fetcher has never populated and auto_fetch left off means
+    // launch can only succeed if this machine happens to have a system browser.
+    let config = ChromiumEngineConfig {
+        executable_path: None,
+        auto_fetch: false,
+        ..Default::default()
+    };
+
+    let mut engine = ChromiumEngine::new(config);
+    match engine.launch().await {
+        Ok(()) => assert!(engine.is_running().await),
+        Err(e) => assert_eq!(e.kind, browser_core::chromium_engine::ChromiumErrorKind::ExecutableNotFound),
+    }
+}
+
 #[tokio::test]
 async fn test_engine_config_update() {
     let initial_config = ChromiumEngineConfig {
@@ -556,3 +614,141 @@ async fn test_engine_get_tabs_concurrent() {
         assert_eq!(tabs.len(), 0);
     }
 }
+
+#[test]
+fn test_engine_with_extensions() {
+    let config = ChromiumEngineConfig {
+        extensions: vec![PathBuf::from("/tmp/ext-a"), PathBuf::from("/tmp/ext-b")],
+        ..Default::default()
+    };
+    let engine = ChromiumEngine::new(config);
+
+    assert_eq!(engine.get_config().extensions.len(), 2);
+}
+
+#[test]
+fn test_engine_capabilities_supports_extensions() {
+    let capabilities = EngineCapabilities::default();
+    assert!(capabilities.supports_extensions());
+}
+
+#[tokio::test]
+async fn test_launch_rejects_extensions_with_incognito() {
+    let config = ChromiumEngineConfig {
+        extensions: vec![PathBuf::from("/tmp/some-extension")],
+        extra_args: vec!["--incognito".to_string()],
+        ..Default::default()
+    };
+    let mut engine = ChromiumEngine::new(config);
+
+    let result = engine.launch().await;
+    assert!(result.is_err());
+    assert_eq!(
+        result.unwrap_err().kind,
+        browser_core::chromium_engine::ChromiumErrorKind::IncompatibleExtensionConfig
+    );
+}
+
+#[tokio::test]
+async fn test_list_loaded_extensions_fails_before_launch() {
+    let config = ChromiumEngineConfig {
+        extensions: vec![PathBuf::from("/tmp/some-extension")],
+        ..Default::default()
+    };
+    let engine = ChromiumEngine::new(config);
+
+    let result = engine.list_loaded_extensions().await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_ephemeral_profile_creates_and_removes_temp_dir() {
+    let config = ChromiumEngineConfig {
+        ephemeral_profile: true,
+        ..Default::default()
+    };
+    let mut engine = ChromiumEngine::new(config);
+
+    // `launch` itself will fail on a machine with no system Chrome/Chromium, but
+    // `prepare_profile_dir` runs before that and should have created the dir either way.
+    let _ = engine.launch().await;
+    let dir = engine.get_config().user_data_dir;
+
+    if let Some(dir) = &dir {
+        assert!(dir.exists());
+        let _ = engine.shutdown().await;
+        assert!(!dir.exists());
+    }
+}
+
+#[tokio::test]
+async fn test_launch_refuses_locked_user_data_dir() {
+    let dir = std::env::temp_dir().join(format!("browser-core-locked-profile-test-{}", uuid::Uuid::new_v4()));
+    tokio::fs::create_dir_all(&dir).await.unwrap();
+    tokio::fs::write(dir.join("SingletonLock"), b"").await.unwrap();
+
+    let config = ChromiumEngineConfig {
+        user_data_dir: Some(dir.clone()),
+        ..Default::default()
+    };
+    let mut engine = ChromiumEngine::new(config);
+
+    let result = engine.launch().await;
+    tokio::fs::remove_dir_all(&dir).await.ok();
+
+    assert!(result.is_err());
+    assert_eq!(
+        result.unwrap_err().kind,
+        browser_core::chromium_engine::ChromiumErrorKind::ProfileLocked
+    );
+}
+
+#[tokio::test]
+async fn test_subscribe_events_unknown_tab() {
+    let config = ChromiumEngineConfig::default();
+    let engine = ChromiumEngine::new(config);
+
+    let result = engine.subscribe_events("nonexistent-tab").await;
+    assert!(result.is_err());
+    assert_eq!(
+        result.unwrap_err().kind,
+        browser_core::chromium_engine::ChromiumErrorKind::TabNotFound
+    );
+}
+
+struct AllowAllFilter;
+
+#[async_trait::async_trait]
+impl browser_core::chromium_interception::RequestFilter for AllowAllFilter {
+    async fn on_request(
+        &self,
+        _req: browser_core::chromium_interception::InterceptedRequest,
+    ) -> browser_core::chromium_interception::FilterAction {
+        browser_core::chromium_interception::FilterAction::Continue
+    }
+}
+
+#[tokio::test]
+async fn test_set_tab_request_filter_unknown_tab() {
+    let config = ChromiumEngineConfig::default();
+    let engine = ChromiumEngine::new(config);
+
+    let result = engine
+        .set_tab_request_filter("nonexistent-tab", std::sync::Arc::new(AllowAllFilter))
+        .await;
+    assert!(result.is_err());
+    assert_eq!(
+        result.unwrap_err().kind,
+        browser_core::chromium_engine::ChromiumErrorKind::TabNotFound
+    );
+}
+
+#[tokio::test]
+async fn test_chromium_engine_implements_browser_engine_trait() {
+    use browser_core::BrowserEngine;
+
+    let engine = ChromiumEngine::new(ChromiumEngineConfig::default());
+    assert!(!BrowserEngine::is_running(&engine).await);
+    assert_eq!(BrowserEngine::get_tabs(&engine).await.len(), 0);
+    assert!(BrowserEngine::capabilities(&engine).extensions);
+}
@@ -0,0 +1,76 @@
+#![cfg(feature = "chromium")]
+//! Tests for the Chromium auto-fetch subsystem. These avoid touching the network by
+//! keeping `allow_download: false` and pointing `install_dir` at an empty temp
+//! directory, so they only exercise the memoization/error-reporting paths.
+
+use browser_core::{fetch_chromium, FetcherOptions};
+use std::path::PathBuf;
+
+fn unique_temp_dir(label: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("browser-core-fetcher-test-{}-{}", label, uuid::Uuid::new_v4()))
+}
+
+#[tokio::test]
+async fn test_fetch_chromium_fails_clearly_when_not_cached_and_downloads_are_disallowed() {
+    let options = FetcherOptions {
+        revision: "1250580".to_string(),
+        install_dir: unique_temp_dir("no-download"),
+        allow_download: false,
+        ..FetcherOptions::default()
+    };
+
+    let result = fetch_chromium(&options).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_fetch_chromium_memoizes_an_already_extracted_binary() {
+    let install_dir = unique_temp_dir("memoized");
+    let revision = "1250580".to_string();
+    let binary_path = install_dir.join(&revision).join("chrome-linux").join("chrome");
+
+    tokio::fs::create_dir_all(binary_path.parent().unwrap()).await.unwrap();
+    tokio::fs::write(&binary_path, b"fake-chromium-binary").await.unwrap();
+
+    let options = FetcherOptions {
+        revision,
+        platform: "linux64".to_string(),
+        install_dir,
+        allow_download: false,
+    };
+
+    let resolved = fetch_chromium(&options).await.unwrap();
+    assert_eq!(resolved, binary_path);
+}
+
+#[tokio::test]
+async fn test_fetch_chromium_rejects_a_binary_that_no_longer_matches_its_manifest() {
+    let install_dir = unique_temp_dir("tampered");
+    let revision = "1250580".to_string();
+    let revision_dir = install_dir.join(&revision);
+    let binary_path = revision_dir.join("chrome-linux").join("chrome");
+
+    tokio::fs::create_dir_all(binary_path.parent().unwrap()).await.unwrap();
+    tokio::fs::write(&binary_path, b"original-chromium-binary").await.unwrap();
+
+    let manifest = serde_json::json!({
+        "revision": "1250580",
+        "platform": "linux64",
+        "sha256": "0000000000000000000000000000000000000000000000000000000000000",
+    });
+    tokio::fs::write(revision_dir.join("manifest.json"), serde_json::to_vec(&manifest).unwrap())
+        .await
+        .unwrap();
+
+    let options = FetcherOptions {
+        revision,
+        platform: "linux64".to_string(),
+        install_dir,
+        allow_download: false,
+    };
+
+    // The binary on disk doesn't match the recorded hash, and downloads are
+    // disallowed, so the tampered cache can't be silently trusted.
+    let result = fetch_chromium(&options).await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,200 @@
+#![cfg(feature = "chromium")]
+//! Tests for the HTTP cache request filter
+
+use browser_core::{
+    CachingRequestFilter, FilterAction, HttpCache, HttpCacheConfig, InterceptedRequest,
+    InterceptedResponse, RequestFilter,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn config() -> HttpCacheConfig {
+    HttpCacheConfig {
+        enabled: true,
+        max_entry_size_bytes: 1024,
+        max_total_bytes: 4096,
+        heuristic_fraction: 0.1,
+    }
+}
+
+fn headers(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+fn request(id: &str, url: &str) -> InterceptedRequest {
+    InterceptedRequest {
+        request_id: id.to_string(),
+        url: url.to_string(),
+        method: "GET".to_string(),
+        headers: HashMap::new(),
+        body: None,
+    }
+}
+
+fn response(id: &str, url: &str, status: u16, headers: HashMap<String, String>) -> InterceptedResponse {
+    InterceptedResponse {
+        request_id: id.to_string(),
+        url: url.to_string(),
+        status,
+        headers,
+    }
+}
+
+#[tokio::test]
+async fn test_cache_miss_continues_request() {
+    let filter = CachingRequestFilter::new(Arc::new(HttpCache::new(config())));
+    let action = filter.on_request(request("1", "https://example.com/a")).await;
+    assert_eq!(action, FilterAction::Continue);
+}
+
+#[tokio::test]
+async fn test_fresh_response_is_served_from_cache_on_next_request() {
+    let filter = CachingRequestFilter::new(Arc::new(HttpCache::new(config())));
+    filter.on_request(request("1", "https://example.com/a")).await;
+    filter
+        .on_response_body(
+            response("1", "https://example.com/a", 200, headers(&[("cache-control", "max-age=60")])),
+            b"hello".to_vec(),
+        )
+        .await;
+
+    let action = filter.on_request(request("2", "https://example.com/a")).await;
+    match action {
+        FilterAction::FulfillWith { status, body, .. } => {
+            assert_eq!(status, 200);
+            assert_eq!(body, b"hello".to_vec());
+        }
+        other => panic!("expected a cache hit, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_no_store_response_is_never_cached() {
+    let filter = CachingRequestFilter::new(Arc::new(HttpCache::new(config())));
+    filter.on_request(request("1", "https://example.com/a")).await;
+    filter
+        .on_response_body(
+            response(
+                "1",
+                "https://example.com/a",
+                200,
+                headers(&[("cache-control", "no-store, max-age=60")]),
+            ),
+            b"hello".to_vec(),
+        )
+        .await;
+
+    let action = filter.on_request(request("2", "https://example.com/a")).await;
+    assert_eq!(action, FilterAction::Continue);
+}
+
+#[tokio::test]
+async fn test_stale_entry_with_etag_gets_conditional_headers() {
+    let filter = CachingRequestFilter::new(Arc::new(HttpCache::new(config())));
+    filter.on_request(request("1", "https://example.com/a")).await;
+    filter
+        .on_response_body(
+            response(
+                "1",
+                "https://example.com/a",
+                200,
+                headers(&[("cache-control", "max-age=0"), ("etag", "\"v1\"")]),
+            ),
+            b"hello".to_vec(),
+        )
+        .await;
+
+    let action = filter.on_request(request("2", "https://example.com/a")).await;
+    match action {
+        FilterAction::ModifyHeaders(headers) => {
+            assert_eq!(headers.get("If-None-Match").map(String::as_str), Some("\"v1\""));
+        }
+        other => panic!("expected conditional revalidation headers, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_304_revalidation_refreshes_entry_without_new_body() {
+    let filter = CachingRequestFilter::new(Arc::new(HttpCache::new(config())));
+    filter.on_request(request("1", "https://example.com/a")).await;
+    filter
+        .on_response_body(
+            response(
+                "1",
+                "https://example.com/a",
+                200,
+                headers(&[("cache-control", "max-age=0"), ("etag", "\"v1\"")]),
+            ),
+            b"hello".to_vec(),
+        )
+        .await;
+
+    // First revalidation round-trip: stale entry gets conditional headers attached.
+    filter.on_request(request("2", "https://example.com/a")).await;
+    filter
+        .on_response_body(
+            response("2", "https://example.com/a", 304, headers(&[("cache-control", "max-age=60")])),
+            Vec::new(),
+        )
+        .await;
+
+    // Now fresh again, and still serves the originally-stored body.
+    let action = filter.on_request(request("3", "https://example.com/a")).await;
+    match action {
+        FilterAction::FulfillWith { body, .. } => assert_eq!(body, b"hello".to_vec()),
+        other => panic!("expected the revalidated entry to be served fresh, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_vary_header_keeps_variants_separate() {
+    let filter = CachingRequestFilter::new(Arc::new(HttpCache::new(config())));
+
+    let mut en_request = request("1", "https://example.com/a");
+    en_request.headers = headers(&[("accept-language", "en")]);
+    filter.on_request(en_request.clone()).await;
+    filter
+        .on_response_body(
+            response(
+                "1",
+                "https://example.com/a",
+                200,
+                headers(&[("cache-control", "max-age=60"), ("vary", "Accept-Language")]),
+            ),
+            b"english".to_vec(),
+        )
+        .await;
+
+    let mut fr_request = request("2", "https://example.com/a");
+    fr_request.headers = headers(&[("accept-language", "fr")]);
+    let action = filter.on_request(fr_request).await;
+    assert_eq!(action, FilterAction::Continue);
+
+    let action = filter.on_request(en_request).await;
+    match action {
+        FilterAction::FulfillWith { body, .. } => assert_eq!(body, b"english".to_vec()),
+        other => panic!("expected the Vary-matching variant to be served, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_oversized_entry_is_not_stored() {
+    let small_config = HttpCacheConfig {
+        max_entry_size_bytes: 4,
+        ..config()
+    };
+    let filter = CachingRequestFilter::new(Arc::new(HttpCache::new(small_config)));
+    filter.on_request(request("1", "https://example.com/a")).await;
+    filter
+        .on_response_body(
+            response("1", "https://example.com/a", 200, headers(&[("cache-control", "max-age=60")])),
+            b"too long".to_vec(),
+        )
+        .await;
+
+    let action = filter.on_request(request("2", "https://example.com/a")).await;
+    assert_eq!(action, FilterAction::Continue);
+}
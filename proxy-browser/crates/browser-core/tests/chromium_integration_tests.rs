@@ -62,6 +62,37 @@ async fn test_browser_custom_viewport() {
         .expect("Operation should succeed");
 }
 
+/// Test that a custom `extra_chrome_flags` entry reaches the launched browser's
+/// effective config, while a reserved flag in the same list is dropped.
+#[tokio::test]
+#[ignore]
+async fn test_browser_custom_chrome_flag() {
+    skip_if_no_chrome!();
+
+    let mut config = create_test_config();
+    config.extra_chrome_flags = vec![
+        "--lang=fr".to_string(),
+        "--headless=old".to_string(), // reserved; this engine already manages headless
+    ];
+
+    let engine = launch_browser_with_timeout(config, 30)
+        .await
+        .expect("Failed to launch browser");
+
+    assert_engine_running(&engine).await;
+    let effective = engine.get_config();
+    assert!(effective
+        .extra_chrome_flags
+        .contains(&"--lang=fr".to_string()));
+    assert!(!effective
+        .extra_chrome_flags
+        .contains(&"--headless=old".to_string()));
+
+    shutdown_browser(engine)
+        .await
+        .expect("Operation should succeed");
+}
+
 /// Test creating a tab and navigating to a URL
 #[tokio::test]
 #[ignore]
@@ -97,6 +128,160 @@ async fn test_create_tab_and_navigate() {
         .expect("Operation should succeed");
 }
 
+/// Test that navigating a subscribed tab delivers a network response event
+#[tokio::test]
+#[ignore]
+async fn test_subscribe_events_observes_navigation() {
+    skip_if_no_chrome!();
+
+    let config = create_test_config();
+    let engine = launch_browser_with_timeout(config, 30)
+        .await
+        .expect("Failed to launch browser");
+
+    let tab = engine
+        .create_tab(Some(test_urls::ABOUT_BLANK), None)
+        .await
+        .expect("Failed to create tab");
+
+    let mut events = engine
+        .subscribe_events(&tab.id)
+        .await
+        .expect("Failed to subscribe to tab events");
+
+    engine
+        .navigate(&tab.id, test_urls::EXAMPLE_COM)
+        .await
+        .expect("Navigation should succeed");
+
+    let event = tokio::time::timeout(Duration::from_secs(5), events.recv())
+        .await
+        .expect("Timed out waiting for a tab event")
+        .expect("Event channel closed unexpectedly");
+
+    match event {
+        browser_core::chromium_engine::TabEvent::NetworkResponse { url, status, .. } => {
+            assert_eq!(url, test_urls::EXAMPLE_COM);
+            assert_eq!(status, 200);
+        }
+        other => panic!("Expected a NetworkResponse event, got {other:?}"),
+    }
+
+    shutdown_browser(engine)
+        .await
+        .expect("Operation should succeed");
+}
+
+struct BlockAllFilter;
+
+#[async_trait::async_trait]
+impl browser_core::chromium_interception::RequestFilter for BlockAllFilter {
+    async fn on_request(
+        &self,
+        _req: browser_core::chromium_interception::InterceptedRequest,
+    ) -> browser_core::chromium_interception::FilterAction {
+        browser_core::chromium_interception::FilterAction::Block
+    }
+}
+
+struct MockResponseFilter;
+
+#[async_trait::async_trait]
+impl browser_core::chromium_interception::RequestFilter for MockResponseFilter {
+    async fn on_request(
+        &self,
+        _req: browser_core::chromium_interception::InterceptedRequest,
+    ) -> browser_core::chromium_interception::FilterAction {
+        browser_core::chromium_interception::FilterAction::FulfillWith {
+            status: 418,
+            headers: std::collections::HashMap::new(),
+            body: b"mocked".to_vec(),
+        }
+    }
+}
+
+/// Test that a tab-level `RequestFilter` can block a navigation
+#[tokio::test]
+#[ignore]
+async fn test_tab_request_filter_blocks_navigation() {
+    skip_if_no_chrome!();
+
+    let config = create_test_config();
+    let engine = launch_browser_with_timeout(config, 30)
+        .await
+        .expect("Failed to launch browser");
+
+    let tab = engine
+        .create_tab(Some(test_urls::ABOUT_BLANK), None)
+        .await
+        .expect("Failed to create tab");
+
+    engine
+        .set_tab_request_filter(&tab.id, std::sync::Arc::new(BlockAllFilter))
+        .await
+        .expect("Failed to attach request filter");
+
+    let result = engine.navigate(&tab.id, test_urls::EXAMPLE_COM).await;
+    assert!(result.is_err());
+    assert_eq!(
+        result.unwrap_err().kind,
+        browser_core::chromium_engine::ChromiumErrorKind::RequestBlocked
+    );
+
+    shutdown_browser(engine)
+        .await
+        .expect("Operation should succeed");
+}
+
+/// Test that a `RequestFilter` can fulfill a navigation with a mocked response
+#[tokio::test]
+#[ignore]
+async fn test_tab_request_filter_mocks_response() {
+    skip_if_no_chrome!();
+
+    let config = create_test_config();
+    let engine = launch_browser_with_timeout(config, 30)
+        .await
+        .expect("Failed to launch browser");
+
+    let tab = engine
+        .create_tab(Some(test_urls::ABOUT_BLANK), None)
+        .await
+        .expect("Failed to create tab");
+
+    engine
+        .set_tab_request_filter(&tab.id, std::sync::Arc::new(MockResponseFilter))
+        .await
+        .expect("Failed to attach request filter");
+
+    let mut events = engine
+        .subscribe_events(&tab.id)
+        .await
+        .expect("Failed to subscribe to tab events");
+
+    engine
+        .navigate(&tab.id, test_urls::EXAMPLE_COM)
+        .await
+        .expect("Navigation should succeed");
+
+    let event = tokio::time::timeout(Duration::from_secs(5), events.recv())
+        .await
+        .expect("Timed out waiting for a tab event")
+        .expect("Event channel closed unexpectedly");
+
+    match event {
+        browser_core::chromium_engine::TabEvent::NetworkResponse { status, body, .. } => {
+            assert_eq!(status, 418);
+            assert_eq!(body, b"mocked");
+        }
+        other => panic!("Expected a NetworkResponse event, got {other:?}"),
+    }
+
+    shutdown_browser(engine)
+        .await
+        .expect("Operation should succeed");
+}
+
 /// Test multiple tabs
 #[tokio::test]
 #[ignore]
@@ -485,6 +670,48 @@ async fn test_concurrent_tab_creation() {
     }
 }
 
+/// Test that a `ChromiumPool` serves concurrent tab-creation requests across several
+/// real, pre-launched browser processes instead of one shared engine.
+#[tokio::test]
+#[ignore]
+async fn test_pool_serves_concurrent_tab_creation() {
+    skip_if_no_chrome!();
+
+    use browser_core::chromium_pool::{ChromiumPool, ChromiumPoolConfig};
+
+    let pool = std::sync::Arc::new(
+        ChromiumPool::new(ChromiumPoolConfig {
+            size: 3,
+            engine_config: create_test_config(),
+            ..ChromiumPoolConfig::default()
+        })
+        .await
+        .expect("Failed to launch pool"),
+    );
+
+    let mut handles = vec![];
+    for _ in 0..3 {
+        let pool = pool.clone();
+        handles.push(tokio::spawn(async move {
+            let pooled = pool.acquire().await.expect("Failed to acquire pooled engine");
+            pooled
+                .engine()
+                .read()
+                .await
+                .create_tab(Some(test_urls::ABOUT_BLANK), None)
+                .await
+        }));
+    }
+
+    for handle in handles {
+        let result = handle.await.expect("Operation should succeed");
+        assert!(result.is_ok());
+    }
+
+    let status = pool.pool_status().await;
+    assert_eq!(status.free + status.busy + status.launching, 3);
+}
+
 /// Test browser launch timeout
 #[tokio::test]
 #[ignore]
@@ -0,0 +1,30 @@
+#![cfg(feature = "chromium")]
+//! Tests for `ChromiumPool` configuration. Spinning up the pool itself requires a
+//! real Chrome/Chromium install, so lifecycle behavior is exercised in
+//! `test_pool_serves_concurrent_tab_creation` under `chromium_integration_tests`.
+
+use browser_core::chromium_pool::{ChromiumPoolConfig, PoolStatus, NUMBER_OF_INSTANCES};
+
+#[test]
+fn test_pool_config_default_size() {
+    let config = ChromiumPoolConfig::default();
+    assert_eq!(config.size, NUMBER_OF_INSTANCES);
+    assert_eq!(config.size, 10);
+}
+
+#[test]
+fn test_pool_config_custom_size() {
+    let config = ChromiumPoolConfig {
+        size: 3,
+        ..ChromiumPoolConfig::default()
+    };
+    assert_eq!(config.size, 3);
+}
+
+#[test]
+fn test_pool_status_default_is_empty() {
+    let status = PoolStatus::default();
+    assert_eq!(status.free, 0);
+    assert_eq!(status.busy, 0);
+    assert_eq!(status.launching, 0);
+}
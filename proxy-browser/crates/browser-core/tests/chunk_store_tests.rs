@@ -0,0 +1,168 @@
+//! Tests for content-defined chunking and the reference-counted chunk store.
+
+use browser_core::chunk_store::{cut_points, hash_chunk, ChunkStore};
+use browser_core::ChunkingConfig;
+use std::path::PathBuf;
+
+fn unique_temp_dir(label: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("browser-core-chunk-test-{}-{}", label, uuid::Uuid::new_v4()))
+}
+
+fn small_config() -> ChunkingConfig {
+    ChunkingConfig {
+        min_size: 64,
+        avg_size: 256,
+        max_size: 1024,
+    }
+}
+
+#[test]
+fn test_cut_points_empty_data_has_no_chunks() {
+    assert!(cut_points(&[], &small_config()).is_empty());
+}
+
+#[test]
+fn test_cut_points_cover_entire_input_contiguously() {
+    let data: Vec<u8> = (0..5000u32).map(|i| (i % 251) as u8).collect();
+    let config = small_config();
+
+    let points = cut_points(&data, &config);
+    assert!(!points.is_empty());
+
+    let mut expected_start = 0;
+    for (start, end) in &points {
+        assert_eq!(*start, expected_start);
+        assert!(end > start);
+        assert!(end - start <= config.max_size);
+        expected_start = *end;
+    }
+    assert_eq!(expected_start, data.len());
+}
+
+#[test]
+fn test_cut_points_respect_max_size() {
+    // All-zero input has no content for the rolling hash to key on: the first chunk
+    // runs to `max_size` and later ones are cut at a fixed spacing. Either way no chunk
+    // may exceed `max_size`.
+    let data = vec![0u8; 5000];
+    let config = small_config();
+
+    let points = cut_points(&data, &config);
+    for (start, end) in &points {
+        assert!(end - start <= config.max_size);
+    }
+}
+
+#[test]
+fn test_cut_points_deterministic_for_same_input() {
+    let data: Vec<u8> = (0..3000u32).map(|i| (i * 7 % 256) as u8).collect();
+    let config = small_config();
+
+    assert_eq!(cut_points(&data, &config), cut_points(&data, &config));
+}
+
+#[test]
+fn test_cut_points_shared_prefix_yields_shared_leading_chunks() {
+    // Appending data after a shared prefix shouldn't perturb the chunk boundaries that
+    // fall entirely within that prefix -- this is what makes the chunking "content
+    // defined" rather than fixed-size.
+    let prefix: Vec<u8> = (0..4000u32).map(|i| (i * 31 % 256) as u8).collect();
+    let mut unchanged = prefix.clone();
+    let mut appended = prefix.clone();
+    appended.extend_from_slice(&[42u8; 500]);
+    unchanged.extend_from_slice(&[42u8; 1]);
+
+    let config = small_config();
+    let points_before = cut_points(&prefix, &config);
+    let points_after = cut_points(&appended, &config);
+
+    // Every boundary before the point where the inputs diverge should be identical. The
+    // prefix-only input's last chunk ends at `shared_len` only because its data ends
+    // there, not at a content-defined boundary, so it is not compared.
+    let shared_len = prefix.len();
+    let before_in_range: Vec<_> = points_before
+        .iter()
+        .filter(|(_, end)| *end < shared_len)
+        .collect();
+    let after_in_range: Vec<_> = points_after
+        .iter()
+        .filter(|(_, end)| *end < shared_len)
+        .collect();
+    assert_eq!(before_in_range, after_in_range);
+}
+
+#[test]
+fn test_hash_chunk_is_stable_and_content_sensitive() {
+    let a = hash_chunk(b"hello world");
+    let b = hash_chunk(b"hello world");
+    let c = hash_chunk(b"hello world!");
+
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+    assert_eq!(a.len(), 64); // hex-encoded SHA-256
+}
+
+#[tokio::test]
+async fn test_chunk_store_dedupes_repeated_chunks() {
+    let store = ChunkStore::new(unique_temp_dir("dedupe"));
+    let config = small_config();
+    let data = vec![7u8; 2000];
+
+    let manifest_a = store.store_chunks(&data, &config).await.unwrap();
+    let manifest_b = store.store_chunks(&data, &config).await.unwrap();
+
+    assert_eq!(manifest_a, manifest_b);
+
+    // A chunk may repeat within a single manifest (e.g. a run of identical bytes), so
+    // compare against how many times each digest actually appears across both calls.
+    let mut expected_counts = std::collections::HashMap::new();
+    for digest in manifest_a.iter().chain(manifest_b.iter()) {
+        *expected_counts.entry(digest.clone()).or_insert(0u64) += 1;
+    }
+    for (digest, expected) in &expected_counts {
+        assert_eq!(store.refcount(digest).await, *expected);
+    }
+}
+
+#[tokio::test]
+async fn test_chunk_store_round_trips_data() {
+    let store = ChunkStore::new(unique_temp_dir("roundtrip"));
+    let config = small_config();
+    let data: Vec<u8> = (0..10_000u32).map(|i| (i % 256) as u8).collect();
+
+    let manifest = store.store_chunks(&data, &config).await.unwrap();
+    let reassembled = store.reassemble(&manifest).await.unwrap();
+
+    assert_eq!(reassembled, data);
+}
+
+#[tokio::test]
+async fn test_chunk_store_release_garbage_collects_unreferenced_chunks() {
+    let store = ChunkStore::new(unique_temp_dir("gc"));
+    let config = small_config();
+    let data = vec![9u8; 2000];
+
+    let manifest = store.store_chunks(&data, &config).await.unwrap();
+    store.release(&manifest).await.unwrap();
+
+    for digest in &manifest {
+        assert_eq!(store.refcount(digest).await, 0);
+    }
+    // With every chunk's refcount at zero, the data can no longer be reassembled.
+    assert!(store.reassemble(&manifest).await.is_err());
+}
+
+#[tokio::test]
+async fn test_chunk_store_release_keeps_chunks_still_referenced() {
+    let store = ChunkStore::new(unique_temp_dir("shared"));
+    let config = small_config();
+    let data = vec![3u8; 2000];
+
+    let manifest = store.store_chunks(&data, &config).await.unwrap();
+    store.store_chunks(&data, &config).await.unwrap(); // second manifest, same chunks
+    store.release(&manifest).await.unwrap();
+
+    // One reference remains, so reassembly should still work.
+    let reassembled = store.reassemble(&manifest).await.unwrap();
+    assert_eq!(reassembled, data);
+}
@@ -15,6 +15,7 @@ pub fn create_test_config() -> ChromiumEngineConfig {
         viewport_width: 1280,
         viewport_height: 720,
         stealth_mode: true,
+        ephemeral_profile: true, // Every launch gets its own isolated profile dir
         extra_args: vec![
             "--disable-gpu".to_string(),
             "--no-first-run".to_string(),
@@ -50,30 +51,11 @@ pub async fn shutdown_browser(mut engine: ChromiumEngine) -> Result<(), String>
     }
 }
 
-/// Check if Chrome/Chromium is available on the system
+/// Check if Chrome/Chromium is available on the system. Delegates to
+/// [`browser_core::is_chrome_available`] so tests skip/run in lockstep with the
+/// real engine's `resolve_executable` lookup.
 pub fn is_chrome_available() -> bool {
-    // Check common Chrome/Chromium locations
-    let paths = if cfg!(target_os = "windows") {
-        vec![
-            r"C:\Program Files\Google\Chrome\Application\chrome.exe",
-            r"C:\Program Files (x86)\Google\Chrome\Application\chrome.exe",
-            r"C:\Program Files\Chromium\Application\chrome.exe",
-        ]
-    } else if cfg!(target_os = "macos") {
-        vec![
-            "/Applications/Google Chrome.app/Contents/MacOS/Google Chrome",
-            "/Applications/Chromium.app/Contents/MacOS/Chromium",
-        ]
-    } else {
-        vec![
-            "/usr/bin/google-chrome",
-            "/usr/bin/chromium",
-            "/usr/bin/chromium-browser",
-            "/snap/bin/chromium",
-        ]
-    };
-
-    paths.iter().any(|p| std::path::Path::new(p).exists())
+    browser_core::is_chrome_available()
 }
 
 /// Skip test if Chrome is not available
@@ -149,6 +131,7 @@ mod tests {
         assert!(!config.sandbox);
         assert_eq!(config.viewport_width, 1280);
         assert_eq!(config.viewport_height, 720);
+        assert!(config.ephemeral_profile);
     }
 
     #[test]
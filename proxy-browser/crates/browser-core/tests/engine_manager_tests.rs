@@ -8,6 +8,19 @@ use browser_core::chromium_engine::{
     BrowserEngineManager, BrowserEngineType, ChromiumEngineConfig,
 };
 use browser_core::proxy::{ProxySettings, ProxyType};
+use browser_core::proxy_pool::ProxyRotationStrategy;
+
+fn test_proxy(port: u16) -> ProxySettings {
+    ProxySettings {
+        proxy_type: ProxyType::Http,
+        host: Some("proxy.example.com".to_string()),
+        port: Some(port),
+        username: None,
+        password: None,
+        dns_servers: vec![],
+        bypass_list: vec![],
+    }
+}
 
 #[tokio::test]
 async fn test_engine_manager_creation() {
@@ -336,3 +349,59 @@ async fn test_config_cloning() {
     assert_eq!(config1.stealth_mode, config2.stealth_mode);
     assert_eq!(config1.viewport_width, config2.viewport_width);
 }
+
+#[tokio::test]
+async fn test_proxy_pool_default_rotation_strategy() {
+    let manager = BrowserEngineManager::new();
+
+    assert_eq!(
+        manager.rotation_strategy().await,
+        ProxyRotationStrategy::RoundRobin
+    );
+}
+
+#[tokio::test]
+async fn test_proxy_pool_added_proxies_start_available() {
+    let manager = BrowserEngineManager::new();
+    manager.add_proxy(test_proxy(8001)).await;
+    manager.add_proxy(test_proxy(8002)).await;
+
+    let status = manager.get_proxy_pool_status().await;
+    assert_eq!(status.len(), 2);
+    // Freshly added proxies are optimistically healthy before the first health check.
+    assert!(status.iter().all(|entry| entry.health.available));
+}
+
+#[tokio::test]
+async fn test_proxy_pool_remove_proxy() {
+    let manager = BrowserEngineManager::new();
+    let proxy = test_proxy(8001);
+    manager.add_proxy(proxy.clone()).await;
+
+    assert!(manager.remove_proxy(&proxy).await);
+    assert!(manager.get_proxy_pool_status().await.is_empty());
+    assert!(!manager.remove_proxy(&proxy).await);
+}
+
+#[tokio::test]
+async fn test_proxy_pool_set_rotation_strategy() {
+    let manager = BrowserEngineManager::new();
+
+    manager
+        .set_rotation_strategy(ProxyRotationStrategy::StickyPerDomain)
+        .await;
+
+    assert_eq!(
+        manager.rotation_strategy().await,
+        ProxyRotationStrategy::StickyPerDomain
+    );
+}
+
+#[tokio::test]
+async fn test_create_tab_with_pooled_proxy_fails_without_active_engine() {
+    let manager = BrowserEngineManager::new();
+    manager.add_proxy(test_proxy(8001)).await;
+
+    let result = manager.create_tab_with_pooled_proxy(None, None).await;
+    assert!(result.is_err());
+}
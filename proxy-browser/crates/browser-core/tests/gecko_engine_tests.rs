@@ -0,0 +1,53 @@
+#![cfg(feature = "gecko")]
+//! Tests for GeckoEngine lifecycle and operations that don't require an actual
+//! Firefox/Marionette process, matching the style of `chromium_engine_tests.rs`.
+
+use browser_core::gecko_engine::{GeckoEngine, GeckoEngineConfig};
+use browser_core::BrowserEngine;
+
+#[tokio::test]
+async fn test_engine_new_not_running() {
+    let engine = GeckoEngine::new(GeckoEngineConfig::default());
+    assert!(!engine.is_running().await);
+}
+
+#[tokio::test]
+async fn test_engine_get_tabs_empty() {
+    let engine = GeckoEngine::new(GeckoEngineConfig::default());
+    assert_eq!(engine.get_tabs().await.len(), 0);
+}
+
+#[tokio::test]
+async fn test_engine_shutdown_without_launch() {
+    let mut engine = GeckoEngine::new(GeckoEngineConfig::default());
+    assert!(engine.shutdown().await.is_ok());
+}
+
+#[tokio::test]
+async fn test_create_tab_before_launch_fails() {
+    let engine = GeckoEngine::new(GeckoEngineConfig::default());
+    let result = engine.create_tab(None, None).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_set_active_tab_unknown_id_fails() {
+    let engine = GeckoEngine::new(GeckoEngineConfig::default());
+    let result = engine.set_active_tab("no-such-tab").await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_browser_engine_capabilities_reflect_no_per_tab_proxy() {
+    let engine = GeckoEngine::new(GeckoEngineConfig::default());
+    let caps = BrowserEngine::capabilities(&engine);
+    assert!(!caps.per_tab_proxy);
+    assert!(caps.custom_user_agent);
+}
+
+#[tokio::test]
+async fn test_browser_engine_trait_get_config_reports_browser_name() {
+    let engine = GeckoEngine::new(GeckoEngineConfig::default());
+    let config = BrowserEngine::get_config(&engine);
+    assert_eq!(config["browserName"], "firefox");
+}
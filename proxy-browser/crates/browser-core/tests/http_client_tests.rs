@@ -0,0 +1,34 @@
+//! Tests for the http_client and free_ip_providers modules
+
+use browser_core::free_ip_providers::{FreeIpProviderManager, ProxyFilter};
+use browser_core::http_client::{default_sources, IpSource};
+
+#[test]
+fn test_default_sources_are_in_priority_order() {
+    let sources = default_sources();
+    let names: Vec<&str> = sources.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["ipify", "icanhazip", "seeip"]);
+}
+
+#[test]
+fn test_ip_sources_have_distinct_urls() {
+    let sources = default_sources();
+    let mut urls: Vec<&str> = sources.iter().map(|s| s.url.as_str()).collect();
+    urls.sort_unstable();
+    urls.dedup();
+    assert_eq!(urls.len(), sources.len());
+}
+
+#[tokio::test]
+async fn test_manager_active_sources_defaults_to_every_provider() {
+    let manager = FreeIpProviderManager::new(vec![IpSource::ipify(), IpSource::icanhazip()]);
+    let sources = manager.active_sources(ProxyFilter::default()).await;
+    assert_eq!(sources.len(), 2);
+}
+
+#[tokio::test]
+async fn test_manager_get_status_starts_empty() {
+    let manager = FreeIpProviderManager::new(vec![IpSource::ipify()]);
+    let status = manager.get_status().await.expect("status should not fail");
+    assert_eq!(status, serde_json::json!({}));
+}
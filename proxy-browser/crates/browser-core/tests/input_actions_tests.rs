@@ -0,0 +1,206 @@
+#![cfg(feature = "chromium")]
+//! Tests for the WebDriver-style input actions tick-dispatch engine
+
+use browser_core::input_actions::{
+    dispatch, release, tick_durations, ActionItem, ActionSequence, ActionsError, DispatchedAction,
+    PointerOrigin, PressedState,
+};
+
+fn pointer(id: &str, actions: Vec<ActionItem>) -> ActionSequence {
+    ActionSequence {
+        id: id.to_string(),
+        source_type: browser_core::input_actions::SourceType::Pointer,
+        actions,
+    }
+}
+
+fn key(id: &str, actions: Vec<ActionItem>) -> ActionSequence {
+    ActionSequence {
+        id: id.to_string(),
+        source_type: browser_core::input_actions::SourceType::Key,
+        actions,
+    }
+}
+
+#[test]
+fn test_tick_durations_takes_the_max_across_sources() {
+    let sources = vec![
+        pointer(
+            "mouse",
+            vec![ActionItem::PointerMove {
+                x: 0.0,
+                y: 0.0,
+                origin: PointerOrigin::Viewport,
+                duration_ms: 100,
+            }],
+        ),
+        key("keyboard", vec![ActionItem::Pause { duration_ms: 250 }]),
+    ];
+
+    assert_eq!(tick_durations(&sources), vec![250]);
+}
+
+#[test]
+fn test_shorter_sources_are_padded_with_pauses() {
+    let sources = vec![
+        pointer(
+            "mouse",
+            vec![
+                ActionItem::PointerDown { button: 0 },
+                ActionItem::PointerUp { button: 0 },
+            ],
+        ),
+        key("keyboard", vec![ActionItem::KeyDown { value: "a".to_string() }]),
+    ];
+
+    let mut pressed = PressedState::default();
+    let ticks = dispatch(&sources, &mut pressed).expect("dispatch should succeed");
+
+    assert_eq!(ticks.len(), 2);
+    assert_eq!(ticks[1].actions[1].1, DispatchedAction::Pause);
+}
+
+#[test]
+fn test_pointer_move_with_viewport_origin_resolves_absolute_coordinates() {
+    let sources = vec![pointer(
+        "mouse",
+        vec![ActionItem::PointerMove {
+            x: 100.0,
+            y: 200.0,
+            origin: PointerOrigin::Viewport,
+            duration_ms: 0,
+        }],
+    )];
+
+    let mut pressed = PressedState::default();
+    let ticks = dispatch(&sources, &mut pressed).unwrap();
+
+    match &ticks[0].actions[0].1 {
+        DispatchedAction::PointerMove(m) => {
+            assert_eq!(m.x, 100.0);
+            assert_eq!(m.y, 200.0);
+            assert!(m.unresolved_element.is_none());
+        }
+        other => panic!("expected a PointerMove, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_pointer_move_with_pointer_origin_is_relative_to_last_position() {
+    let sources = vec![pointer(
+        "mouse",
+        vec![
+            ActionItem::PointerMove {
+                x: 50.0,
+                y: 50.0,
+                origin: PointerOrigin::Viewport,
+                duration_ms: 0,
+            },
+            ActionItem::PointerMove {
+                x: 10.0,
+                y: -5.0,
+                origin: PointerOrigin::Pointer,
+                duration_ms: 0,
+            },
+        ],
+    )];
+
+    let mut pressed = PressedState::default();
+    let ticks = dispatch(&sources, &mut pressed).unwrap();
+
+    match &ticks[1].actions[0].1 {
+        DispatchedAction::PointerMove(m) => {
+            assert_eq!(m.x, 60.0);
+            assert_eq!(m.y, 45.0);
+        }
+        other => panic!("expected a PointerMove, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_pointer_move_with_element_origin_is_left_unresolved() {
+    let sources = vec![pointer(
+        "mouse",
+        vec![ActionItem::PointerMove {
+            x: 5.0,
+            y: 5.0,
+            origin: PointerOrigin::Element {
+                element_id: "elem-1".to_string(),
+            },
+            duration_ms: 0,
+        }],
+    )];
+
+    let mut pressed = PressedState::default();
+    let ticks = dispatch(&sources, &mut pressed).unwrap();
+
+    match &ticks[0].actions[0].1 {
+        DispatchedAction::PointerMove(m) => {
+            assert_eq!(m.unresolved_element.as_deref(), Some("elem-1"));
+        }
+        other => panic!("expected a PointerMove, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_double_pointer_down_is_rejected() {
+    let sources = vec![pointer(
+        "mouse",
+        vec![ActionItem::PointerDown { button: 0 }, ActionItem::PointerDown { button: 0 }],
+    )];
+
+    let mut pressed = PressedState::default();
+    let err = dispatch(&sources, &mut pressed).unwrap_err();
+
+    assert!(matches!(err, ActionsError::AlreadyPressed { .. }));
+}
+
+#[test]
+fn test_pointer_up_without_down_is_rejected() {
+    let sources = vec![pointer("mouse", vec![ActionItem::PointerUp { button: 0 }])];
+
+    let mut pressed = PressedState::default();
+    let err = dispatch(&sources, &mut pressed).unwrap_err();
+
+    assert!(matches!(err, ActionsError::NotPressed { .. }));
+}
+
+#[test]
+fn test_key_action_on_pointer_source_is_rejected() {
+    let sources = vec![pointer("mouse", vec![ActionItem::KeyDown { value: "a".to_string() }])];
+
+    let mut pressed = PressedState::default();
+    let err = dispatch(&sources, &mut pressed).unwrap_err();
+
+    assert!(matches!(err, ActionsError::WrongItemForSource { .. }));
+}
+
+#[test]
+fn test_release_undoes_presses_in_reverse_order() {
+    let sources = vec![key(
+        "keyboard",
+        vec![
+            ActionItem::KeyDown { value: "Shift".to_string() },
+            ActionItem::KeyDown { value: "a".to_string() },
+        ],
+    )];
+
+    let mut pressed = PressedState::default();
+    dispatch(&sources, &mut pressed).unwrap();
+
+    let released = release(&mut pressed);
+
+    assert_eq!(
+        released,
+        vec![
+            ("keyboard".to_string(), DispatchedAction::KeyUp { value: "a".to_string() }),
+            ("keyboard".to_string(), DispatchedAction::KeyUp { value: "Shift".to_string() }),
+        ]
+    );
+}
+
+#[test]
+fn test_release_with_nothing_pressed_returns_empty() {
+    let mut pressed = PressedState::default();
+    assert!(release(&mut pressed).is_empty());
+}
@@ -0,0 +1,46 @@
+//! Tests for the ip_watch module
+
+use browser_core::{IpChangeEvent, IpWatchSnapshot, IpWatcher};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[tokio::test]
+async fn test_new_watcher_is_not_running() {
+    let watcher = IpWatcher::new();
+    assert!(!watcher.is_running().await);
+}
+
+#[tokio::test]
+async fn test_stop_without_start_is_a_no_op() {
+    let watcher = IpWatcher::new();
+    watcher.stop().await;
+    assert!(!watcher.is_running().await);
+}
+
+#[tokio::test]
+async fn test_start_marks_watcher_running_until_stopped() {
+    let watcher = IpWatcher::new();
+    let calls = Arc::new(AtomicUsize::new(0));
+    let calls_clone = calls.clone();
+
+    watcher
+        .start(3600, Arc::new(browser_core::NoopGeoLookup), move |_: IpChangeEvent| {
+            calls_clone.fetch_add(1, Ordering::SeqCst);
+        })
+        .await;
+
+    assert!(watcher.is_running().await);
+    watcher.stop().await;
+    assert!(!watcher.is_running().await);
+    // A one-hour interval never ticks during this test.
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn test_snapshot_equality_ignores_nothing() {
+    let a = IpWatchSnapshot { ip: "1.2.3.4".to_string(), country: Some("US".to_string()), isp: None };
+    let b = IpWatchSnapshot { ip: "1.2.3.4".to_string(), country: Some("US".to_string()), isp: None };
+    let c = IpWatchSnapshot { ip: "1.2.3.4".to_string(), country: None, isp: None };
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+}
@@ -0,0 +1,168 @@
+//! Tests for the proxy_pool module
+
+use browser_core::proxy::{ProxySettings, ProxyType};
+use browser_core::proxy_pool::{ProxyPool, ProxyRotationStrategy};
+
+fn proxy(port: u16) -> ProxySettings {
+    ProxySettings {
+        proxy_type: ProxyType::Http,
+        host: Some("proxy.example.com".to_string()),
+        port: Some(port),
+        username: None,
+        password: None,
+        dns_servers: vec![],
+        bypass_list: vec![],
+    }
+}
+
+#[tokio::test]
+async fn test_assign_empty_pool_returns_none() {
+    let pool = ProxyPool::new(ProxyRotationStrategy::RoundRobin);
+    assert!(pool.assign(None).await.is_none());
+}
+
+#[tokio::test]
+async fn test_round_robin_cycles_through_every_proxy() {
+    let pool = ProxyPool::new(ProxyRotationStrategy::RoundRobin);
+    pool.add_proxy(proxy(8001)).await;
+    pool.add_proxy(proxy(8002)).await;
+    pool.add_proxy(proxy(8003)).await;
+
+    let assigned: Vec<u16> = assign_ports(&pool, 6).await;
+    assert_eq!(assigned, vec![8001, 8002, 8003, 8001, 8002, 8003]);
+}
+
+#[tokio::test]
+async fn test_sticky_per_domain_reuses_same_proxy() {
+    let pool = ProxyPool::new(ProxyRotationStrategy::StickyPerDomain);
+    pool.add_proxy(proxy(8001)).await;
+    pool.add_proxy(proxy(8002)).await;
+
+    let first = pool.assign(Some("example.com")).await;
+    let second = pool.assign(Some("example.com")).await;
+    let other = pool.assign(Some("other.com")).await;
+
+    assert_eq!(first, second);
+    assert!(first.is_some());
+    assert!(other.is_some());
+}
+
+#[tokio::test]
+async fn test_remove_proxy_drops_it_from_assignment() {
+    let pool = ProxyPool::new(ProxyRotationStrategy::RoundRobin);
+    let doomed = proxy(8001);
+    pool.add_proxy(doomed.clone()).await;
+    pool.add_proxy(proxy(8002)).await;
+
+    assert!(pool.remove_proxy(&doomed).await);
+    assert!(!pool.remove_proxy(&doomed).await);
+
+    let assigned: Vec<u16> = assign_ports(&pool, 2).await;
+    assert_eq!(assigned, vec![8002, 8002]);
+}
+
+#[tokio::test]
+async fn test_get_status_reports_optimistic_health_before_any_check() {
+    let pool = ProxyPool::new(ProxyRotationStrategy::RoundRobin);
+    pool.add_proxy(proxy(8001)).await;
+
+    let status = pool.get_status().await;
+    assert_eq!(status.len(), 1);
+    assert!(status[0].health.available);
+    assert!(status[0].health.last_checked.is_none());
+}
+
+#[tokio::test]
+async fn test_health_check_loop_start_stop_toggles_running_state() {
+    let pool = std::sync::Arc::new(ProxyPool::new(ProxyRotationStrategy::RoundRobin));
+    assert!(!pool.is_health_check_running().await);
+
+    pool.start_health_checks(3600, "http://127.0.0.1:1".to_string())
+        .await;
+    assert!(pool.is_health_check_running().await);
+
+    pool.stop_health_checks().await;
+    assert!(!pool.is_health_check_running().await);
+}
+
+#[tokio::test]
+async fn test_least_recently_used_picks_the_stalest_entry() {
+    let pool = ProxyPool::new(ProxyRotationStrategy::LeastRecentlyUsed);
+    pool.add_proxy(proxy(8001)).await;
+    pool.add_proxy(proxy(8002)).await;
+
+    // Neither has been used yet; whichever comes back first becomes "most recently
+    // used", so the other must come back next.
+    let first = pool.assign(None).await.unwrap().port.unwrap();
+    let second = pool.assign(None).await.unwrap().port.unwrap();
+    assert_ne!(first, second);
+
+    // Having just used `second`, the next assignment should go back to `first`.
+    let third = pool.assign(None).await.unwrap().port.unwrap();
+    assert_eq!(third, first);
+}
+
+#[tokio::test]
+async fn test_weighted_by_health_prune_keeps_entries_scored_at_the_threshold() {
+    let pool = ProxyPool::new(ProxyRotationStrategy::WeightedByHealth);
+    let healthy = proxy(8001);
+    let unhealthy = proxy(8002);
+    pool.add_proxy(healthy.clone()).await;
+    pool.add_proxy(unhealthy.clone()).await;
+
+    // Probe results aren't exercised here (that needs real network access). Both
+    // entries start at the optimistic default score of 1.0, and pruning removes only
+    // scores strictly below `min_score`, so both stay in the pool and assignable.
+    pool.prune_dead_proxies(1.0).await;
+    assert_eq!(pool.get_status().await.len(), 2);
+    assert!(pool.assign(None).await.is_some());
+}
+
+#[tokio::test]
+async fn test_prune_dead_proxies_removes_only_entries_below_the_threshold() {
+    let pool = ProxyPool::new(ProxyRotationStrategy::RoundRobin);
+    pool.add_proxy(proxy(8001)).await;
+    pool.add_proxy(proxy(8002)).await;
+
+    // Freshly added proxies default to an optimistic score of 1.0.
+    let pruned = pool.prune_dead_proxies(1.5).await;
+    assert_eq!(pruned.len(), 2);
+    assert_eq!(pool.get_status().await.len(), 0);
+}
+
+#[tokio::test]
+async fn test_prune_dead_proxies_keeps_healthy_entries() {
+    let pool = ProxyPool::new(ProxyRotationStrategy::RoundRobin);
+    pool.add_proxy(proxy(8001)).await;
+
+    let pruned = pool.prune_dead_proxies(0.5).await;
+    assert!(pruned.is_empty());
+    assert_eq!(pool.get_status().await.len(), 1);
+}
+
+#[tokio::test]
+async fn test_health_decay_defaults_and_is_settable() {
+    let pool = ProxyPool::new(ProxyRotationStrategy::RoundRobin);
+    assert!((pool.health_decay().await - 0.7).abs() < f64::EPSILON);
+
+    pool.set_health_decay(0.9).await;
+    assert!((pool.health_decay().await - 0.9).abs() < f64::EPSILON);
+
+    // Out-of-range values are clamped rather than stored verbatim.
+    pool.set_health_decay(5.0).await;
+    assert!(pool.health_decay().await < 1.0);
+}
+
+async fn assign_ports(pool: &ProxyPool, count: usize) -> Vec<u16> {
+    let mut ports = Vec::with_capacity(count);
+    for _ in 0..count {
+        ports.push(
+            pool.assign(None)
+                .await
+                .expect("pool should not be empty")
+                .port
+                .expect("test proxies always have a port"),
+        );
+    }
+    ports
+}
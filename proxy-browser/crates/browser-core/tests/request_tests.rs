@@ -66,6 +66,8 @@ fn test_request_response_is_success() {
         body: r#"{"success": true}"#.to_string(),
         response_time_ms: 150,
         final_url: "https://example.com".to_string(),
+        from_cache: false,
+        redirect_hops: Vec::new(),
     };
     assert!(success_response.is_success());
 
@@ -76,6 +78,8 @@ fn test_request_response_is_success() {
         body: "{}".to_string(),
         response_time_ms: 200,
         final_url: "https://example.com/resource".to_string(),
+        from_cache: false,
+        redirect_hops: Vec::new(),
     };
     assert!(created_response.is_success());
 
@@ -86,6 +90,8 @@ fn test_request_response_is_success() {
         body: "{}".to_string(),
         response_time_ms: 100,
         final_url: "https://example.com/missing".to_string(),
+        from_cache: false,
+        redirect_hops: Vec::new(),
     };
     assert!(!error_response.is_success());
 
@@ -96,6 +102,8 @@ fn test_request_response_is_success() {
         body: "{}".to_string(),
         response_time_ms: 50,
         final_url: "https://example.com/error".to_string(),
+        from_cache: false,
+        redirect_hops: Vec::new(),
     };
     assert!(!server_error.is_success());
 }
@@ -109,6 +117,8 @@ fn test_request_response_json_parsing() {
         body: r#"{"name": "test", "value": 42}"#.to_string(),
         response_time_ms: 100,
         final_url: "https://example.com".to_string(),
+        from_cache: false,
+        redirect_hops: Vec::new(),
     };
 
     #[derive(serde::Deserialize)]
@@ -248,3 +258,100 @@ fn test_dns_resolution_error_is_distinct_from_network() {
     assert!(matches!(dns_error.kind, RequestErrorKind::DnsResolution));
     assert!(matches!(network_error.kind, RequestErrorKind::Network));
 }
+
+#[test]
+fn test_request_response_from_cache_defaults_false() {
+    let response = RequestResponse {
+        status: 200,
+        status_text: "OK".to_string(),
+        headers: HashMap::new(),
+        body: "{}".to_string(),
+        response_time_ms: 10,
+        final_url: "https://example.com".to_string(),
+        from_cache: false,
+        redirect_hops: Vec::new(),
+    };
+    assert!(!response.from_cache);
+}
+
+#[test]
+fn test_request_manager_without_cache() {
+    let manager = RequestManager::new().unwrap().without_cache();
+    manager.clear_cache();
+}
+
+#[test]
+fn test_resolve_url_from_location_absolute() {
+    use browser_core::request::resolve_url_from_location;
+    use reqwest::Url;
+
+    let base = Url::parse("https://example.com/a/b").unwrap();
+    let resolved = resolve_url_from_location(&base, "https://other.example/path").unwrap();
+    assert_eq!(resolved.as_str(), "https://other.example/path");
+}
+
+#[test]
+fn test_resolve_url_from_location_protocol_relative() {
+    use browser_core::request::resolve_url_from_location;
+    use reqwest::Url;
+
+    let base = Url::parse("https://example.com/a/b").unwrap();
+    let resolved = resolve_url_from_location(&base, "//cdn.example.com/asset.js").unwrap();
+    assert_eq!(resolved.as_str(), "https://cdn.example.com/asset.js");
+}
+
+#[test]
+fn test_resolve_url_from_location_absolute_path() {
+    use browser_core::request::resolve_url_from_location;
+    use reqwest::Url;
+
+    let base = Url::parse("https://example.com/a/b").unwrap();
+    let resolved = resolve_url_from_location(&base, "/new-path").unwrap();
+    assert_eq!(resolved.as_str(), "https://example.com/new-path");
+}
+
+#[test]
+fn test_resolve_url_from_location_relative() {
+    use browser_core::request::resolve_url_from_location;
+    use reqwest::Url;
+
+    let base = Url::parse("https://example.com/a/b").unwrap();
+    let resolved = resolve_url_from_location(&base, "c").unwrap();
+    assert_eq!(resolved.as_str(), "https://example.com/a/c");
+}
+
+#[tokio::test]
+async fn test_read_timeout_on_stalled_streamed_body() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buf = [0u8; 1024];
+        let _ = socket.read(&mut buf).await;
+
+        // Advertise a 10-byte body but only ever send 2, then stall forever --
+        // the client should time out waiting on the rest rather than hang.
+        socket
+            .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 10\r\n\r\nhi")
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_secs(5)).await;
+    });
+
+    let manager = RequestManager::new().unwrap();
+    let mut builder = RequestBuilder::get(format!("http://{addr}"));
+    builder.config.read_timeout = Some(Duration::from_millis(100));
+
+    let err = manager.execute(builder).await.unwrap_err();
+
+    assert_eq!(err.kind, RequestErrorKind::Timeout);
+    assert!(
+        err.message.contains("2 byte"),
+        "expected the byte count received so far in the message, got: {}",
+        err.message
+    );
+}
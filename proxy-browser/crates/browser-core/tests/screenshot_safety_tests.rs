@@ -0,0 +1,94 @@
+//! Tests for screenshot content-safety classification
+
+use browser_core::{
+    SafetyAction, SafetyCategory, SafetyCheck, ScreenshotManager, ScreenshotOptions,
+};
+
+#[tokio::test]
+async fn test_safety_check_disabled_by_default() {
+    let manager = ScreenshotManager::default();
+    let result = manager
+        .capture_viewport("tab-1", &ScreenshotOptions::default())
+        .await
+        .unwrap();
+
+    assert!(result.nsfw_score.is_none());
+    assert!(result.safety_category.is_none());
+    assert!(result.is_safe(0.0));
+}
+
+#[tokio::test]
+async fn test_safety_check_flags_a_capture_without_mutating_it() {
+    let manager = ScreenshotManager::default().with_safety_check(SafetyCheck {
+        enabled: true,
+        threshold: 0.0,
+        categories: vec![SafetyCategory::Neutral, SafetyCategory::Suggestive, SafetyCategory::Explicit],
+        on_exceeded: SafetyAction::Flag,
+    });
+
+    let result = manager
+        .capture_viewport("tab-1", &ScreenshotOptions::default())
+        .await
+        .unwrap();
+
+    assert!(result.nsfw_score.is_some());
+    assert!(!result.blurred);
+    assert!(!result.refused);
+}
+
+#[tokio::test]
+async fn test_safety_check_blur_action_marks_the_result_blurred() {
+    let manager = ScreenshotManager::default().with_safety_check(SafetyCheck {
+        enabled: true,
+        threshold: 0.0,
+        categories: vec![SafetyCategory::Neutral, SafetyCategory::Suggestive, SafetyCategory::Explicit],
+        on_exceeded: SafetyAction::Blur,
+    });
+
+    let result = manager
+        .capture_viewport("tab-1", &ScreenshotOptions::default())
+        .await
+        .unwrap();
+
+    assert!(result.blurred);
+    assert!(!result.refused);
+    assert!(!result.data.is_empty());
+}
+
+#[tokio::test]
+async fn test_safety_check_refuse_action_blocks_save() {
+    let manager = ScreenshotManager::default().with_safety_check(SafetyCheck {
+        enabled: true,
+        threshold: 0.0,
+        categories: vec![SafetyCategory::Neutral, SafetyCategory::Suggestive, SafetyCategory::Explicit],
+        on_exceeded: SafetyAction::Refuse,
+    });
+
+    let result = manager
+        .capture_viewport("tab-1", &ScreenshotOptions::default())
+        .await
+        .unwrap();
+
+    assert!(result.refused);
+    assert!(result
+        .save(&std::env::temp_dir().join("should-not-be-written.png"))
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_safety_check_ignores_categories_not_in_the_allowlist() {
+    let manager = ScreenshotManager::default().with_safety_check(SafetyCheck {
+        enabled: true,
+        threshold: 0.0,
+        categories: vec![],
+        on_exceeded: SafetyAction::Refuse,
+    });
+
+    let result = manager
+        .capture_viewport("tab-1", &ScreenshotOptions::default())
+        .await
+        .unwrap();
+
+    assert!(!result.refused);
+}
@@ -0,0 +1,167 @@
+//! Tests for screenshot capture and encoding
+
+use browser_core::{ScreenshotClip, ScreenshotFormat, ScreenshotManager, ScreenshotOptions};
+
+fn png_dimensions(data: &[u8]) -> (u32, u32) {
+    let width = u32::from_be_bytes([data[16], data[17], data[18], data[19]]);
+    let height = u32::from_be_bytes([data[20], data[21], data[22], data[23]]);
+    (width, height)
+}
+
+#[tokio::test]
+async fn test_capture_viewport_produces_non_empty_png() {
+    let manager = ScreenshotManager::default();
+    let result = manager
+        .capture_viewport("tab-1", &ScreenshotOptions::default())
+        .await
+        .expect("capture should succeed");
+
+    assert!(!result.data.is_empty());
+    assert!(matches!(result.format, ScreenshotFormat::Png));
+    assert_eq!(png_dimensions(&result.data), (result.width, result.height));
+}
+
+#[tokio::test]
+async fn test_capture_viewport_honors_clip() {
+    let options = ScreenshotOptions {
+        clip: Some(ScreenshotClip {
+            x: 10.0,
+            y: 10.0,
+            width: 200.0,
+            height: 100.0,
+        }),
+        ..ScreenshotOptions::default()
+    };
+
+    let result = manager_capture(options).await;
+
+    assert_eq!(result.width, 200);
+    assert_eq!(result.height, 100);
+}
+
+#[tokio::test]
+async fn test_capture_viewport_clip_is_clamped_to_bounds() {
+    let options = ScreenshotOptions {
+        clip: Some(ScreenshotClip {
+            x: 1900.0,
+            y: 1000.0,
+            width: 500.0,
+            height: 500.0,
+        }),
+        ..ScreenshotOptions::default()
+    };
+
+    let result = manager_capture(options).await;
+
+    assert!(result.width <= 20);
+    assert!(result.height <= 80);
+}
+
+#[tokio::test]
+async fn test_jpeg_encoding_round_trips_through_the_image_crate() {
+    let options = ScreenshotOptions {
+        format: ScreenshotFormat::Jpeg,
+        quality: 80,
+        ..ScreenshotOptions::default()
+    };
+
+    let result = manager_capture(options).await;
+
+    assert!(!result.data.is_empty());
+    assert_eq!(&result.data[0..2], &[0xFF, 0xD8]); // JPEG SOI marker
+}
+
+#[tokio::test]
+async fn test_webp_and_avif_formats_encode_without_error() {
+    for format in [ScreenshotFormat::WebP, ScreenshotFormat::Avif] {
+        let options = ScreenshotOptions {
+            format,
+            ..ScreenshotOptions::default()
+        };
+        let result = manager_capture(options).await;
+        assert!(!result.data.is_empty(), "{format:?} produced no data");
+    }
+}
+
+#[tokio::test]
+async fn test_capture_full_page_is_taller_than_a_single_viewport() {
+    let manager = ScreenshotManager::default();
+    let viewport = manager
+        .capture_viewport("tab-1", &ScreenshotOptions::default())
+        .await
+        .unwrap();
+    let full_page = manager
+        .capture_full_page("tab-1", &ScreenshotOptions::default())
+        .await
+        .unwrap();
+
+    assert!(full_page.height > viewport.height);
+    assert_eq!(full_page.width, viewport.width);
+}
+
+#[tokio::test]
+async fn test_capture_element_clips_to_a_bounding_box_smaller_than_the_viewport() {
+    let manager = ScreenshotManager::default();
+    let result = manager
+        .capture_element("tab-1", "#login-button", &ScreenshotOptions::default())
+        .await
+        .unwrap();
+
+    assert!(result.width < 1920);
+    assert!(result.height < 1080);
+}
+
+#[tokio::test]
+async fn test_capture_element_is_deterministic_for_the_same_selector() {
+    let manager = ScreenshotManager::default();
+    let first = manager
+        .capture_element("tab-1", "#login-button", &ScreenshotOptions::default())
+        .await
+        .unwrap();
+    let second = manager
+        .capture_element("tab-1", "#login-button", &ScreenshotOptions::default())
+        .await
+        .unwrap();
+
+    assert_eq!(first.width, second.width);
+    assert_eq!(first.height, second.height);
+}
+
+#[tokio::test]
+async fn test_capture_element_explicit_clip_overrides_the_bounding_box() {
+    let options = ScreenshotOptions {
+        clip: Some(ScreenshotClip {
+            x: 0.0,
+            y: 0.0,
+            width: 50.0,
+            height: 50.0,
+        }),
+        ..ScreenshotOptions::default()
+    };
+
+    let manager = ScreenshotManager::default();
+    let result = manager
+        .capture_element("tab-1", "#login-button", &options)
+        .await
+        .unwrap();
+
+    assert_eq!((result.width, result.height), (50, 50));
+}
+
+#[tokio::test]
+async fn test_to_data_url_embeds_the_mime_type() {
+    let manager = ScreenshotManager::default();
+    let result = manager
+        .capture_viewport("tab-1", &ScreenshotOptions::default())
+        .await
+        .unwrap();
+
+    assert!(result.to_data_url().starts_with("data:image/png;base64,"));
+}
+
+async fn manager_capture(options: ScreenshotOptions) -> browser_core::ScreenshotResult {
+    ScreenshotManager::default()
+        .capture_viewport("tab-1", &options)
+        .await
+        .expect("capture should succeed")
+}
@@ -0,0 +1,140 @@
+//! Tests for expiring, password-protected screenshot share links
+
+use browser_core::{ScreenshotFormat, ScreenshotManager, ScreenshotOptions, ShareErrorKind, ShareStore, ShareStoreConfig};
+use std::path::PathBuf;
+
+fn scratch_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("share-tests-{}", uuid::Uuid::new_v4()))
+}
+
+fn store(config_overrides: impl FnOnce(ShareStoreConfig) -> ShareStoreConfig) -> ShareStore {
+    let config = config_overrides(ShareStoreConfig {
+        output_dir: scratch_dir(),
+        ..ShareStoreConfig::default()
+    });
+    ShareStore::new(config)
+}
+
+#[tokio::test]
+async fn test_publish_and_resolve_round_trips_without_a_password() {
+    let manager = ScreenshotManager::default();
+    let result = manager
+        .capture_viewport("tab-1", &ScreenshotOptions::default())
+        .await
+        .unwrap();
+
+    let store = store(|c| c);
+    let token = store.publish(&result, None, 24).await.unwrap();
+
+    let resolved = store.resolve(&token, None).await.unwrap();
+    assert_eq!(resolved.data, result.data);
+    assert!(matches!(resolved.format, ScreenshotFormat::Png));
+}
+
+#[tokio::test]
+async fn test_resolve_rejects_missing_password() {
+    let manager = ScreenshotManager::default();
+    let result = manager
+        .capture_viewport("tab-1", &ScreenshotOptions::default())
+        .await
+        .unwrap();
+
+    let store = store(|c| c);
+    let token = store
+        .publish(&result, Some("hunter2".to_string()), 24)
+        .await
+        .unwrap();
+
+    let err = store.resolve(&token, None).await.unwrap_err();
+    assert_eq!(err.kind, ShareErrorKind::PasswordRequired);
+}
+
+#[tokio::test]
+async fn test_resolve_rejects_wrong_password() {
+    let manager = ScreenshotManager::default();
+    let result = manager
+        .capture_viewport("tab-1", &ScreenshotOptions::default())
+        .await
+        .unwrap();
+
+    let store = store(|c| c);
+    let token = store
+        .publish(&result, Some("hunter2".to_string()), 24)
+        .await
+        .unwrap();
+
+    let err = store
+        .resolve(&token, Some("wrong".to_string()))
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind, ShareErrorKind::InvalidPassword);
+}
+
+#[tokio::test]
+async fn test_resolve_accepts_correct_password() {
+    let manager = ScreenshotManager::default();
+    let result = manager
+        .capture_viewport("tab-1", &ScreenshotOptions::default())
+        .await
+        .unwrap();
+
+    let store = store(|c| c);
+    let token = store
+        .publish(&result, Some("hunter2".to_string()), 24)
+        .await
+        .unwrap();
+
+    let resolved = store
+        .resolve(&token, Some("hunter2".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(resolved.data, result.data);
+}
+
+#[tokio::test]
+async fn test_resolve_unknown_token_fails() {
+    let store = store(|c| c);
+    let err = store.resolve("does-not-exist", None).await.unwrap_err();
+    assert_eq!(err.kind, ShareErrorKind::TokenNotFound);
+}
+
+#[tokio::test]
+async fn test_expired_share_is_rejected_and_removed() {
+    let manager = ScreenshotManager::default();
+    let result = manager
+        .capture_viewport("tab-1", &ScreenshotOptions::default())
+        .await
+        .unwrap();
+
+    let store = store(|c| c);
+    // 0-hour lifetime: expired as soon as it's published.
+    let token = store.publish(&result, None, 0).await.unwrap();
+
+    let err = store.resolve(&token, None).await.unwrap_err();
+    assert_eq!(err.kind, ShareErrorKind::Expired);
+
+    // Removed on first rejection, so a second resolve reports "not found".
+    let err = store.resolve(&token, None).await.unwrap_err();
+    assert_eq!(err.kind, ShareErrorKind::TokenNotFound);
+}
+
+#[tokio::test]
+async fn test_quota_eviction_removes_the_oldest_entry_first() {
+    let manager = ScreenshotManager::default();
+    let result = manager
+        .capture_viewport("tab-1", &ScreenshotOptions::default())
+        .await
+        .unwrap();
+    let entry_size = result.data.len() as u64;
+
+    let store = store(|c| ShareStoreConfig {
+        max_total_bytes: entry_size, // room for exactly one entry
+        ..c
+    });
+
+    let first = store.publish(&result, None, 24).await.unwrap();
+    let second = store.publish(&result, None, 24).await.unwrap();
+
+    assert!(store.resolve(&first, None).await.is_err());
+    assert!(store.resolve(&second, None).await.is_ok());
+}
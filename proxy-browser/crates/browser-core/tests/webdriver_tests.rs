@@ -0,0 +1,237 @@
+#![cfg(feature = "chromium")]
+//! Tests for the WebDriver-compatible remote control server
+//!
+//! These exercise the HTTP contract end-to-end over a real loopback socket. A session
+//! is still created even when no Chrome/Chromium binary is available in the test
+//! environment, so capability negotiation and error handling can be verified without a
+//! real browser.
+
+use browser_core::webdriver::WebDriverServer;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+async fn start_server() -> String {
+    let server = Arc::new(WebDriverServer::new());
+    let addr = server.serve("127.0.0.1:0").await.expect("server should bind");
+    format!("http://{addr}")
+}
+
+async fn post(base_url: &str, path: &str, body: Value) -> (u16, Value) {
+    let client = reqwest::Client::new();
+    let response = client
+        .post(format!("{base_url}{path}"))
+        .json(&body)
+        .send()
+        .await
+        .expect("request should complete");
+    let status = response.status().as_u16();
+    let value = response.json().await.expect("response should be JSON");
+    (status, value)
+}
+
+async fn delete(base_url: &str, path: &str) -> (u16, Value) {
+    let client = reqwest::Client::new();
+    let response = client
+        .delete(format!("{base_url}{path}"))
+        .send()
+        .await
+        .expect("request should complete");
+    let status = response.status().as_u16();
+    let value = response.json().await.expect("response should be JSON");
+    (status, value)
+}
+
+async fn get(base_url: &str, path: &str) -> (u16, Value) {
+    let client = reqwest::Client::new();
+    let response = client
+        .get(format!("{base_url}{path}"))
+        .send()
+        .await
+        .expect("request should complete");
+    let status = response.status().as_u16();
+    let value = response.json().await.expect("response should be JSON");
+    (status, value)
+}
+
+#[tokio::test]
+async fn test_create_session_with_empty_capabilities() {
+    let base_url = start_server().await;
+
+    let (status, body) = post(&base_url, "/session", json!({"capabilities": {}})).await;
+
+    assert_eq!(status, 200);
+    assert!(body["value"]["sessionId"].as_str().is_some());
+    assert_eq!(body["value"]["capabilities"]["browserName"], "chromium");
+}
+
+#[tokio::test]
+async fn test_create_session_rejects_unknown_browser() {
+    let base_url = start_server().await;
+
+    let (status, body) = post(
+        &base_url,
+        "/session",
+        json!({"capabilities": {"alwaysMatch": {"browserName": "firefox"}}}),
+    )
+    .await;
+
+    assert_eq!(status, 500);
+    assert_eq!(body["value"]["error"], "session not created");
+}
+
+#[tokio::test]
+async fn test_create_session_accepts_chromium_options() {
+    let base_url = start_server().await;
+
+    let (status, body) = post(
+        &base_url,
+        "/session",
+        json!({
+            "capabilities": {
+                "alwaysMatch": {
+                    "chromium:options": {"args": ["--disable-gpu"], "headless": true}
+                }
+            }
+        }),
+    )
+    .await;
+
+    assert_eq!(status, 200);
+    assert_eq!(
+        body["value"]["capabilities"]["chromium:options"]["args"][0],
+        "--disable-gpu"
+    );
+}
+
+#[tokio::test]
+async fn test_create_session_rejects_unknown_extension_capability() {
+    let base_url = start_server().await;
+
+    let (status, body) = post(
+        &base_url,
+        "/session",
+        json!({"capabilities": {"alwaysMatch": {"unknown:option": true}}}),
+    )
+    .await;
+
+    assert_eq!(status, 500);
+    assert_eq!(body["value"]["error"], "session not created");
+}
+
+#[tokio::test]
+async fn test_create_session_honors_first_match_fallback() {
+    let base_url = start_server().await;
+
+    // The first firstMatch entry asks for an unsupported browser; the second is fine.
+    let (status, body) = post(
+        &base_url,
+        "/session",
+        json!({
+            "capabilities": {
+                "firstMatch": [
+                    {"browserName": "firefox"},
+                    {"browserName": "chrome"}
+                ]
+            }
+        }),
+    )
+    .await;
+
+    assert_eq!(status, 200);
+    assert!(body["value"]["sessionId"].as_str().is_some());
+}
+
+#[tokio::test]
+async fn test_unknown_session_operations_return_invalid_session_id() {
+    let base_url = start_server().await;
+
+    let (status, body) = get(&base_url, "/session/does-not-exist/url").await;
+
+    assert_eq!(status, 404);
+    assert_eq!(body["value"]["error"], "invalid session id");
+}
+
+#[tokio::test]
+async fn test_navigate_requires_url_field() {
+    let base_url = start_server().await;
+    let (_, created) = post(&base_url, "/session", json!({"capabilities": {}})).await;
+    let session_id = created["value"]["sessionId"].as_str().unwrap();
+
+    let (status, body) = post(&base_url, &format!("/session/{session_id}/url"), json!({})).await;
+
+    assert_eq!(status, 400);
+    assert_eq!(body["value"]["error"], "invalid argument");
+}
+
+#[tokio::test]
+async fn test_back_and_forward_are_unsupported() {
+    let base_url = start_server().await;
+    let (_, created) = post(&base_url, "/session", json!({"capabilities": {}})).await;
+    let session_id = created["value"]["sessionId"].as_str().unwrap();
+
+    let (status, body) = post(&base_url, &format!("/session/{session_id}/back"), json!({})).await;
+
+    assert_eq!(status, 500);
+    assert_eq!(body["value"]["error"], "unsupported operation");
+}
+
+#[tokio::test]
+async fn test_cookie_round_trip() {
+    let base_url = start_server().await;
+    let (_, created) = post(&base_url, "/session", json!({"capabilities": {}})).await;
+    let session_id = created["value"]["sessionId"].as_str().unwrap();
+
+    let (status, _) = post(
+        &base_url,
+        &format!("/session/{session_id}/cookie"),
+        json!({"cookie": {"name": "session", "value": "abc123"}}),
+    )
+    .await;
+    assert_eq!(status, 200);
+
+    let (status, body) = get(&base_url, &format!("/session/{session_id}/cookie")).await;
+    assert_eq!(status, 200);
+    assert_eq!(body["value"][0]["name"], "session");
+    assert_eq!(body["value"][0]["value"], "abc123");
+
+    let (status, _) = delete(
+        &base_url,
+        &format!("/session/{session_id}/cookie/session"),
+    )
+    .await;
+    assert_eq!(status, 200);
+
+    let (status, body) = delete(
+        &base_url,
+        &format!("/session/{session_id}/cookie/session"),
+    )
+    .await;
+    assert_eq!(status, 404);
+    assert_eq!(body["value"]["error"], "no such cookie");
+}
+
+#[tokio::test]
+async fn test_delete_session_is_idempotent() {
+    let base_url = start_server().await;
+    let (_, created) = post(&base_url, "/session", json!({"capabilities": {}})).await;
+    let session_id = created["value"]["sessionId"].as_str().unwrap();
+
+    let (status, _) = delete(&base_url, &format!("/session/{session_id}")).await;
+    assert_eq!(status, 200);
+
+    let (status, _) = delete(&base_url, &format!("/session/{session_id}")).await;
+    assert_eq!(status, 200);
+
+    let (status, _) = get(&base_url, &format!("/session/{session_id}/url")).await;
+    assert_eq!(status, 404);
+}
+
+#[tokio::test]
+async fn test_unknown_command_returns_unknown_command_error() {
+    let base_url = start_server().await;
+
+    let (status, body) = get(&base_url, "/session/whatever/not-a-real-command").await;
+
+    assert_eq!(status, 404);
+    assert_eq!(body["value"]["error"], "unknown command");
+}
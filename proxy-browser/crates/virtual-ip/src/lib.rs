@@ -1,5 +1,6 @@
 pub mod generator;
 pub mod models;
+pub mod remote;
 pub mod rotation;
 pub mod validator;
 
@@ -8,5 +9,6 @@ pub use models::{
     load_countries_from_file, load_ip_ranges, load_ip_ranges_from_file, Country, CountryDatabase,
     IPRange, VirtualIP,
 };
+pub use remote::{load_countries_from_url, load_ip_ranges_from_url, CachePolicy, RemoteLoadReport};
 pub use rotation::{IPRotationManager, RotationStrategy};
 pub use validator::{IPValidator, ValidationReport};
@@ -0,0 +1,102 @@
+//! IP-range and country datasets backing virtual IP generation
+//!
+//! [`IPRange`] rows map a contiguous IPv4 block to a country, and [`CountryDatabase`]
+//! carries the display/geolocation metadata [`crate::generator::IPGenerator`] attaches
+//! to a generated [`VirtualIP`]. Both can be loaded from a local file (below) or from a
+//! remote URL with HTTP caching (see [`crate::remote`]).
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::net::Ipv4Addr;
+use std::path::Path;
+
+/// A virtual IP address generated for a tab, with the geolocation metadata it was
+/// generated to carry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VirtualIP {
+    pub ip: String,
+    pub country_code: String,
+    pub country_name: String,
+    pub city: String,
+    pub timezone: String,
+    pub isp: String,
+}
+
+/// A contiguous IPv4 block assigned to a single country, as published by regional
+/// internet registries (ARIN, RIPE, APNIC, ...).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IPRange {
+    pub start: Ipv4Addr,
+    pub end: Ipv4Addr,
+    pub country_code: String,
+}
+
+/// A country [`crate::generator::IPGenerator`] can target, with the metadata attached
+/// to any [`VirtualIP`] generated for it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Country {
+    pub code: String,
+    pub name: String,
+    pub timezone: String,
+    pub isps: Vec<String>,
+}
+
+/// The full set of countries [`crate::generator::IPGenerator`] can generate a
+/// [`VirtualIP`] for.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CountryDatabase {
+    pub countries: Vec<Country>,
+}
+
+impl CountryDatabase {
+    pub fn find(&self, country_code: &str) -> Option<&Country> {
+        self.countries
+            .iter()
+            .find(|country| country.code.eq_ignore_ascii_case(country_code))
+    }
+}
+
+/// Parse IP ranges out of already-read file contents: one `start,end,country_code` CSV
+/// row per line, blank lines and `#`-prefixed comments ignored.
+pub fn load_ip_ranges(contents: &str) -> Result<Vec<IPRange>> {
+    let mut ranges = Vec::new();
+
+    for (line_no, raw_line) in contents.lines().enumerate() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let [start, end, country_code] = fields.as_slice() else {
+            return Err(anyhow!(
+                "line {}: expected 'start,end,country_code', got '{raw_line}'",
+                line_no + 1
+            ));
+        };
+
+        ranges.push(IPRange {
+            start: start
+                .parse()
+                .map_err(|e| anyhow!("line {}: invalid start address '{start}': {e}", line_no + 1))?,
+            end: end
+                .parse()
+                .map_err(|e| anyhow!("line {}: invalid end address '{end}': {e}", line_no + 1))?,
+            country_code: country_code.to_ascii_uppercase(),
+        });
+    }
+
+    Ok(ranges)
+}
+
+/// Load and parse IP ranges from a local CSV file. See [`load_ip_ranges`] for the format.
+pub async fn load_ip_ranges_from_file(path: impl AsRef<Path>) -> Result<Vec<IPRange>> {
+    let contents = tokio::fs::read_to_string(path.as_ref()).await?;
+    load_ip_ranges(&contents)
+}
+
+/// Load and parse a [`CountryDatabase`] from a local JSON file.
+pub async fn load_countries_from_file(path: impl AsRef<Path>) -> Result<CountryDatabase> {
+    let contents = tokio::fs::read_to_string(path.as_ref()).await?;
+    serde_json::from_str(&contents).map_err(|e| anyhow!("invalid country database: {e}"))
+}
@@ -0,0 +1,223 @@
+//! Remote loading of IP-range/country datasets with HTTP caching
+//!
+//! Mirrors the conditional-`GET` pattern `browser_core::request::RequestManager` uses
+//! for generic HTTP responses: each fetched resource is cached on disk alongside its
+//! `ETag`/`Last-Modified`, and a refresh sends `If-None-Match`/`If-Modified-Since`,
+//! treating a `304 Not Modified` as "the cached copy is still current". Unlike that
+//! in-memory cache, this one persists the raw body to `cache_dir` too, since a GeoIP
+//! dataset needs to survive a process restart without re-downloading.
+
+use crate::models::{load_countries_from_file, load_ip_ranges, CountryDatabase, IPRange};
+use crate::validator::{IPValidator, ValidationReport};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+const CACHE_INDEX_FILE: &str = "remote_cache.json";
+
+/// How aggressively [`load_ip_ranges_from_url`]/[`load_countries_from_url`] should
+/// refresh their cached copy against the remote URL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CachePolicy {
+    /// Serve the cached copy without contacting the remote at all, as long as one
+    /// exists and is within the caller's `max_age`.
+    UseCached,
+    /// Always send a conditional request, even if the cached copy is within `max_age`.
+    RevalidateAlways,
+    /// Ignore any cached copy and re-download unconditionally.
+    ForceRefresh,
+}
+
+/// The validators and on-disk location of a single cached remote resource, keyed by
+/// its URL in [`CACHE_INDEX_FILE`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RemoteCacheEntry {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    fetched_at: DateTime<Utc>,
+    body_file: String,
+}
+
+/// The result of a remote load: the dataset to use (freshly parsed or the previously
+/// cached one), whether the network was actually hit, and the [`ValidationReport`] of
+/// the data that was parsed: the cached copy when it was reused, otherwise the download
+/// (also when that download failed validation and the earlier cached copy is returned).
+#[derive(Debug, Clone)]
+pub struct RemoteLoadReport<T> {
+    pub data: T,
+    pub validation: ValidationReport,
+    pub from_cache: bool,
+}
+
+async fn load_cache_index(cache_dir: &Path) -> HashMap<String, RemoteCacheEntry> {
+    let index_path = cache_dir.join(CACHE_INDEX_FILE);
+    match tokio::fs::read(&index_path).await {
+        Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_default(),
+        Err(_) => HashMap::new(),
+    }
+}
+
+async fn persist_cache_index(cache_dir: &Path, index: &HashMap<String, RemoteCacheEntry>) -> Result<()> {
+    tokio::fs::create_dir_all(cache_dir).await?;
+    let index_path = cache_dir.join(CACHE_INDEX_FILE);
+    tokio::fs::write(&index_path, serde_json::to_vec_pretty(index)?).await?;
+    Ok(())
+}
+
+fn body_file_name(url: &str) -> String {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    url.hash(&mut hasher);
+    format!("{:016x}.body", hasher.finish())
+}
+
+/// Fetch `url`, consulting and updating the on-disk cache under `cache_dir` per
+/// `policy`, and return the raw bytes plus whether they came from the cache.
+///
+/// `policy == UseCached` skips the network entirely when a cached copy exists and is
+/// younger than `max_age`. Otherwise a conditional request is sent (unconditional for
+/// `ForceRefresh`); a `304 Not Modified` response reuses the cached bytes, redirects
+/// are followed by `reqwest` automatically, and any other non-success status is an
+/// error that leaves the cached copy untouched.
+async fn fetch_with_cache(
+    client: &Client,
+    url: &str,
+    cache_dir: &Path,
+    policy: CachePolicy,
+    max_age: chrono::Duration,
+) -> Result<(Vec<u8>, bool)> {
+    let mut index = load_cache_index(cache_dir).await;
+    let cached = index.get(url).cloned();
+
+    if policy == CachePolicy::UseCached {
+        if let Some(entry) = &cached {
+            if Utc::now() - entry.fetched_at < max_age {
+                if let Ok(bytes) = tokio::fs::read(cache_dir.join(&entry.body_file)).await {
+                    return Ok((bytes, true));
+                }
+            }
+        }
+    }
+
+    let mut request = client.get(url);
+    if policy != CachePolicy::ForceRefresh {
+        if let Some(entry) = &cached {
+            if let Some(etag) = &entry.etag {
+                request = request.header("If-None-Match", etag);
+            }
+            if let Some(last_modified) = &entry.last_modified {
+                request = request.header("If-Modified-Since", last_modified);
+            }
+        }
+    }
+
+    let response = request.send().await?;
+
+    if response.status() == StatusCode::NOT_MODIFIED {
+        let entry = cached.ok_or_else(|| anyhow!("remote returned 304 but we have no cached copy of '{url}'"))?;
+        let bytes = tokio::fs::read(cache_dir.join(&entry.body_file)).await?;
+        return Ok((bytes, true));
+    }
+
+    if !response.status().is_success() {
+        return Err(anyhow!("GET {url} failed: {}", response.status()));
+    }
+
+    let etag = response.headers().get("etag").and_then(|v| v.to_str().ok()).map(str::to_string);
+    let last_modified = response
+        .headers()
+        .get("last-modified")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let bytes = response.bytes().await?.to_vec();
+
+    let body_file = body_file_name(url);
+    tokio::fs::create_dir_all(cache_dir).await?;
+    tokio::fs::write(cache_dir.join(&body_file), &bytes).await?;
+    index.insert(
+        url.to_string(),
+        RemoteCacheEntry {
+            etag,
+            last_modified,
+            fetched_at: Utc::now(),
+            body_file,
+        },
+    );
+    persist_cache_index(cache_dir, &index).await?;
+
+    Ok((bytes, false))
+}
+
+/// Load IP ranges from `url`, reusing/revalidating a cached copy under `cache_dir` per
+/// `policy` and `max_age`. If the freshly downloaded data fails [`IPValidator::validate_ranges`],
+/// the cached copy (if any) is kept instead and the failing report is returned alongside
+/// it, so a corrupt download never silently replaces a good dataset. The copy kept is the
+/// one cached before this call; the failing download is still written to `cache_dir`.
+pub async fn load_ip_ranges_from_url(
+    client: &Client,
+    url: &str,
+    cache_dir: impl AsRef<Path>,
+    policy: CachePolicy,
+    max_age: chrono::Duration,
+) -> Result<RemoteLoadReport<Vec<IPRange>>> {
+    let cache_dir = cache_dir.as_ref();
+    let previous = load_cached_ranges(cache_dir, url).await;
+    let (bytes, from_cache) = fetch_with_cache(client, url, cache_dir, policy, max_age).await?;
+    let contents = String::from_utf8(bytes).map_err(|e| anyhow!("'{url}' was not valid UTF-8: {e}"))?;
+    let ranges = load_ip_ranges(&contents)?;
+    let validation = IPValidator::validate_ranges(&ranges);
+
+    if !from_cache && !validation.is_ok() {
+        if let Some(cached_ranges) = previous {
+            return Ok(RemoteLoadReport { data: cached_ranges, validation, from_cache: true });
+        }
+    }
+
+    Ok(RemoteLoadReport { data: ranges, validation, from_cache })
+}
+
+/// Load a country database from `url`, with the same caching and corrupt-download
+/// protection as [`load_ip_ranges_from_url`].
+pub async fn load_countries_from_url(
+    client: &Client,
+    url: &str,
+    cache_dir: impl AsRef<Path>,
+    policy: CachePolicy,
+    max_age: chrono::Duration,
+) -> Result<RemoteLoadReport<CountryDatabase>> {
+    let cache_dir = cache_dir.as_ref();
+    let previous = load_cached_countries(cache_dir, url).await;
+    let (bytes, from_cache) = fetch_with_cache(client, url, cache_dir, policy, max_age).await?;
+    let contents = String::from_utf8(bytes).map_err(|e| anyhow!("'{url}' was not valid UTF-8: {e}"))?;
+    let database: CountryDatabase = serde_json::from_str(&contents).map_err(|e| anyhow!("invalid country database: {e}"))?;
+    let validation = IPValidator::validate_countries(&database.countries);
+
+    if !from_cache && !validation.is_ok() {
+        if let Some(cached_database) = previous {
+            return Ok(RemoteLoadReport { data: cached_database, validation, from_cache: true });
+        }
+    }
+
+    Ok(RemoteLoadReport { data: database, validation, from_cache })
+}
+
+/// Parse whatever body is currently cached for `url` as ranges, read before a download
+/// so it can be fallen back on if that download fails validation. `None` if nothing is
+/// cached yet or the cached copy itself no longer parses.
+async fn load_cached_ranges(cache_dir: &Path, url: &str) -> Option<Vec<IPRange>> {
+    let index = load_cache_index(cache_dir).await;
+    let entry = index.get(url)?;
+    let contents = tokio::fs::read_to_string(cache_dir.join(&entry.body_file)).await.ok()?;
+    load_ip_ranges(&contents).ok()
+}
+
+/// Same as [`load_cached_ranges`], for a country database.
+async fn load_cached_countries(cache_dir: &Path, url: &str) -> Option<CountryDatabase> {
+    let index = load_cache_index(cache_dir).await;
+    let entry = index.get(url)?;
+    let path = cache_dir.join(&entry.body_file);
+    load_countries_from_file(&path).await.ok()
+}
@@ -0,0 +1,118 @@
+//! Validation of loaded IP-range and country datasets
+//!
+//! A dataset pulled from a local file or a remote URL is never trusted blindly: a
+//! truncated download or a hand-edited file can still parse as valid CSV/JSON while
+//! being garbage data. [`IPValidator`] checks the shape of the data itself so callers
+//! like [`crate::remote::load_ip_ranges_from_url`] can refuse to replace a known-good
+//! dataset with a corrupt one.
+
+use crate::models::{Country, IPRange};
+use serde::{Deserialize, Serialize};
+
+/// A single problem [`IPValidator`] found in a loaded dataset.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ValidationIssue {
+    /// The dataset contained no rows at all.
+    EmptyDataset,
+    /// A range's end address comes before its start address.
+    InvertedRange { start: String, end: String },
+    /// Two ranges, by index into the validated slice, overlap.
+    OverlappingRanges { a: usize, b: usize },
+    /// A country entry has an empty or non-ISO-looking code.
+    InvalidCountryCode { code: String },
+    /// The same country code appears more than once.
+    DuplicateCountryCode { code: String },
+}
+
+impl std::fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationIssue::EmptyDataset => write!(f, "dataset contains no entries"),
+            ValidationIssue::InvertedRange { start, end } => {
+                write!(f, "range end {end} comes before start {start}")
+            }
+            ValidationIssue::OverlappingRanges { a, b } => {
+                write!(f, "ranges at index {a} and {b} overlap")
+            }
+            ValidationIssue::InvalidCountryCode { code } => {
+                write!(f, "'{code}' is not a valid ISO 3166-1 alpha-2 country code")
+            }
+            ValidationIssue::DuplicateCountryCode { code } => {
+                write!(f, "country code '{code}' appears more than once")
+            }
+        }
+    }
+}
+
+/// The outcome of validating a loaded dataset.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ValidationReport {
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Whether the dataset is sound enough to replace a previously accepted one.
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Validates [`IPRange`]/[`Country`] datasets for internal consistency.
+pub struct IPValidator;
+
+impl IPValidator {
+    /// Check that `ranges` is non-empty, every range is non-inverted, and no two
+    /// ranges overlap.
+    pub fn validate_ranges(ranges: &[IPRange]) -> ValidationReport {
+        let mut issues = Vec::new();
+
+        if ranges.is_empty() {
+            issues.push(ValidationIssue::EmptyDataset);
+            return ValidationReport { issues };
+        }
+
+        for range in ranges {
+            if range.end < range.start {
+                issues.push(ValidationIssue::InvertedRange {
+                    start: range.start.to_string(),
+                    end: range.end.to_string(),
+                });
+            }
+        }
+
+        for i in 0..ranges.len() {
+            for j in (i + 1)..ranges.len() {
+                if ranges[i].start <= ranges[j].end && ranges[j].start <= ranges[i].end {
+                    issues.push(ValidationIssue::OverlappingRanges { a: i, b: j });
+                }
+            }
+        }
+
+        ValidationReport { issues }
+    }
+
+    /// Check that `countries` is non-empty and every code is a plausible, unique
+    /// ISO 3166-1 alpha-2 code.
+    pub fn validate_countries(countries: &[Country]) -> ValidationReport {
+        let mut issues = Vec::new();
+
+        if countries.is_empty() {
+            issues.push(ValidationIssue::EmptyDataset);
+            return ValidationReport { issues };
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        for country in countries {
+            let code = country.code.to_ascii_uppercase();
+            if code.len() != 2 || !code.chars().all(|c| c.is_ascii_alphabetic()) {
+                issues.push(ValidationIssue::InvalidCountryCode { code: country.code.clone() });
+                continue;
+            }
+            if !seen.insert(code.clone()) {
+                issues.push(ValidationIssue::DuplicateCountryCode { code });
+            }
+        }
+
+        ValidationReport { issues }
+    }
+}
@@ -7,19 +7,151 @@
 //! - Role-based access control
 //! - Enterprise user management
 
+use crate::auth_backend::{AuthBackend, ExternalIdentity};
 use anyhow::{anyhow, Result};
 use argon2::{
     password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
     Argon2,
 };
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
 use chrono::{Duration, Utc};
-use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use rand::RngCore;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tauri::async_runtime::RwLock;
+use tracing::debug;
 use uuid::Uuid;
 
+/// Tunable security posture for [`AuthManager`], so operators can adjust token
+/// lifetimes, refresh-token entropy, and the signing algorithm without recompiling.
+#[derive(Debug, Clone)]
+pub struct AuthConfig {
+    pub access_token_ttl: Duration,
+    pub refresh_token_ttl: Duration,
+    /// Number of random bytes a refresh token's secret is generated from, before
+    /// base64url encoding.
+    pub refresh_token_bytes: usize,
+    pub algorithm: Algorithm,
+    /// Populates and is validated against the `iss` claim.
+    pub issuer: String,
+    /// Populates and is validated against the `aud` claim.
+    pub audience: String,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            access_token_ttl: Duration::minutes(15),
+            refresh_token_ttl: Duration::days(30),
+            refresh_token_bytes: 64,
+            algorithm: Algorithm::HS256,
+            issuer: "live-swe-agent".to_string(),
+            audience: "live-swe-agent-clients".to_string(),
+        }
+    }
+}
+
+/// An OAuth2/Docker-registry-style `resource:action` grant (e.g. `repository:read`,
+/// `agent:execute`), for authorization finer-grained than [`UserRole`] alone.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Scope {
+    pub resource: String,
+    pub action: String,
+}
+
+impl Scope {
+    pub fn new(resource: impl Into<String>, action: impl Into<String>) -> Self {
+        Self { resource: resource.into(), action: action.into() }
+    }
+
+    /// Whether this scope grants `required`, treating `"*"` in either field as a
+    /// wildcard (e.g. `repository:*` grants `repository:read`).
+    pub fn grants(&self, required: &Scope) -> bool {
+        (self.resource == "*" || self.resource == required.resource)
+            && (self.action == "*" || self.action == required.action)
+    }
+}
+
+impl std::fmt::Display for Scope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.resource, self.action)
+    }
+}
+
+impl std::str::FromStr for Scope {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (resource, action) = s
+            .split_once(':')
+            .ok_or_else(|| anyhow!("invalid scope '{s}', expected 'resource:action'"))?;
+        Ok(Self::new(resource, action))
+    }
+}
+
+/// The [`Scope`]s a [`UserRole`] is permitted to hold, widest first. `issue_scoped_token`
+/// intersects a caller's request against this set rather than granting it outright.
+fn role_scopes(role: &UserRole) -> Vec<Scope> {
+    match role {
+        UserRole::Admin => vec![Scope::new("*", "*")],
+        UserRole::Enterprise => vec![
+            Scope::new("repository", "read"),
+            Scope::new("repository", "write"),
+            Scope::new("agent", "execute"),
+        ],
+        UserRole::User => vec![Scope::new("repository", "read"), Scope::new("agent", "execute")],
+    }
+}
+
+/// Stable, machine-readable discriminant for [`AuthError`], serialized under the
+/// `code` field so the frontend can react programmatically (e.g. prompt re-login on
+/// `TokenExpired`, show a ban notice on `BlockedUser`) instead of string-matching
+/// English error messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum AuthErrorKind {
+    UnknownUser,
+    InvalidPassword,
+    BlockedUser,
+    TokenExpired,
+    InvalidToken,
+    InsufficientScope,
+    InvalidRefreshToken,
+    RefreshTokenExpired,
+    DuplicateUser,
+    WeakPassword,
+    InvalidInput,
+    UserNotFound,
+    AdminRequired,
+    InvalidScope,
+    /// An unexpected failure in a dependency (hashing, JWT signing) that isn't itself
+    /// an authentication decision.
+    Internal,
+}
+
+/// Error returned by [`AuthManager`] and surfaced to the frontend through the Tauri
+/// commands below, in place of a free-form string.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuthError {
+    pub code: AuthErrorKind,
+    pub message: String,
+}
+
+impl AuthError {
+    fn new(code: AuthErrorKind, message: impl Into<String>) -> Self {
+        Self { code, message: message.into() }
+    }
+}
+
+impl std::fmt::Display for AuthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}: {}", self.code, self.message)
+    }
+}
+
+impl std::error::Error for AuthError {}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 /// Enumeration of UserRole variants.
 pub enum UserRole {
@@ -38,6 +170,11 @@ pub struct User {
     pub created_at: chrono::DateTime<Utc>,
     pub last_login: Option<chrono::DateTime<Utc>>,
     pub enterprise_id: Option<String>,
+    /// Whether this account has been administratively blocked. Blocked accounts are
+    /// rejected up front by `login` and `refresh_access_token`.
+    pub blocked: bool,
+    pub blocked_reason: Option<String>,
+    pub blocked_at: Option<chrono::DateTime<Utc>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -50,61 +187,99 @@ pub struct Claims {
     pub exp: i64, // Expiration time
     pub iat: i64, // Issued at
     pub jti: String, // JWT ID
+    pub iss: String, // Issuer
+    pub aud: String, // Audience
+    pub scope: Vec<Scope>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 /// Represents a RefreshToken.
 pub struct RefreshToken {
     pub id: String,
     pub user_id: String,
+    /// Shared by every token descended from the same login via rotation, so the whole
+    /// chain can be revoked at once if one of them is replayed after being consumed.
+    pub family_id: String,
     pub token_hash: String,
     pub expires_at: chrono::DateTime<Utc>,
     pub created_at: chrono::DateTime<Utc>,
 }
 
+/// Metadata kept for a refresh token after it's been rotated away, so presenting it
+/// again can be recognized as a reuse (theft) signal rather than silently failing.
+#[derive(Debug, Clone)]
+struct ConsumedToken {
+    family_id: String,
+    consumed_at: chrono::DateTime<Utc>,
+}
+
 /// Represents a AuthManager.
 pub struct AuthManager {
     jwt_secret: String,
+    config: AuthConfig,
     users: Arc<RwLock<HashMap<String, User>>>, // In-memory for demo, use DB in production
     password_hashes: Arc<RwLock<HashMap<String, String>>>, // user_id -> password_hash
     refresh_tokens: Arc<RwLock<HashMap<String, RefreshToken>>>,
+    consumed_tokens: Arc<RwLock<HashMap<String, ConsumedToken>>>,
     argon2: Argon2<'static>,
+    /// When set, `login` delegates credential verification here instead of the local
+    /// Argon2 store (e.g. an LDAP/Active Directory backend).
+    backend: Arc<RwLock<Option<Arc<dyn AuthBackend>>>>,
 
 }
 
 impl AuthManager {
-    /// Create a new AuthManager with the specified JWT secret
+    /// Create a new AuthManager with the specified JWT secret and the default
+    /// [`AuthConfig`].
     ///
     /// # Arguments
     /// * `jwt_secret` - The secret key used for JWT token signing
     pub fn new(jwt_secret: String) -> Self {
+        Self::new_with_config(jwt_secret, AuthConfig::default())
+    }
+
+    /// Create a new AuthManager with the specified JWT secret and [`AuthConfig`].
+    ///
+    /// # Arguments
+    /// * `jwt_secret` - The secret key used for JWT token signing
+    /// * `config` - Token lifetimes, refresh-token entropy, algorithm, and issuer/audience claims
+    pub fn new_with_config(jwt_secret: String, config: AuthConfig) -> Self {
         Self {
             jwt_secret,
+            config,
             users: Arc::new(RwLock::new(HashMap::new())),
             password_hashes: Arc::new(RwLock::new(HashMap::new())),
             refresh_tokens: Arc::new(RwLock::new(HashMap::new())),
+            consumed_tokens: Arc::new(RwLock::new(HashMap::new())),
             argon2: Argon2::default(),
+            backend: Arc::new(RwLock::new(None)),
         }
     }
 
+    /// Configure an external [`AuthBackend`] for `login` to delegate credential
+    /// verification to, replacing the local Argon2 store as the source of truth.
+    pub async fn set_auth_backend(&self, backend: Arc<dyn AuthBackend>) {
+        *self.backend.write().await = Some(backend);
+    }
+
 
     /// Register a new user
-    pub async fn register(&self, username: String, email: String, password: String) -> Result<User> {
+    pub async fn register(&self, username: String, email: String, password: String) -> Result<User, AuthError> {
         // Validate input
         if username.len() < 3 {
-            return Err(anyhow!("Username must be at least 3 characters"));
+            return Err(AuthError::new(AuthErrorKind::InvalidInput, "Username must be at least 3 characters"));
         }
         if !email.contains('@') {
-            return Err(anyhow!("Invalid email format"));
+            return Err(AuthError::new(AuthErrorKind::InvalidInput, "Invalid email format"));
         }
         if password.len() < 8 {
-            return Err(anyhow!("Password must be at least 8 characters"));
+            return Err(AuthError::new(AuthErrorKind::WeakPassword, "Password must be at least 8 characters"));
         }
 
         // Check if user exists
         let users = self.users.read().await;
         if users.values().any(|u| u.username == username || u.email == email) {
-            return Err(anyhow!("User already exists"));
+            return Err(AuthError::new(AuthErrorKind::DuplicateUser, "User already exists"));
         }
         drop(users);
 
@@ -112,7 +287,8 @@ impl AuthManager {
         let salt = SaltString::generate(&mut OsRng);
         let password_hash = self
             .argon2
-            .hash_password(password.as_bytes(), &salt)?
+            .hash_password(password.as_bytes(), &salt)
+            .map_err(|e| AuthError::new(AuthErrorKind::Internal, e.to_string()))?
             .to_string();
 
         // Create user (in production, store in database)
@@ -124,6 +300,9 @@ impl AuthManager {
             created_at: Utc::now(),
             last_login: None,
             enterprise_id: None,
+            blocked: false,
+            blocked_reason: None,
+            blocked_at: None,
         };
 
         // Store user and password hash
@@ -139,145 +318,296 @@ impl AuthManager {
     }
 
 
-    /// Authenticate user and return tokens
-    pub async fn login(&self, username: String, password: String) -> Result<(String, String)> {
-        // Find user
+    /// Authenticate user and return tokens. Delegates to the configured
+    /// [`AuthBackend`] if one is set via [`Self::set_auth_backend`], otherwise verifies
+    /// against the local Argon2 store.
+    pub async fn login(&self, username: String, password: String) -> Result<(String, String), AuthError> {
+        let backend = self.backend.read().await.clone();
+        let user = match backend {
+            Some(backend) => {
+                let identity = backend.verify_credentials(&username, &password).await.map_err(|e| {
+                    debug!("login failed for '{username}' via external backend: {e}");
+                    AuthError::new(AuthErrorKind::InvalidPassword, "invalid username or password")
+                })?;
+                self.provision_from_identity(identity).await?
+            }
+            // Unknown-user and wrong-password both collapse to the same code and
+            // message here so the API boundary can't be used to enumerate accounts;
+            // the precise cause is still visible to internal logging via `e`.
+            None => self.verify_local_credentials(&username, &password).await.map_err(|e| {
+                debug!("login failed for '{username}': {e}");
+                AuthError::new(AuthErrorKind::InvalidPassword, "invalid username or password")
+            })?,
+        };
+
+        if user.blocked {
+            return Err(AuthError::new(AuthErrorKind::BlockedUser, "Account is blocked"));
+        }
+
+        // Update last login
+        let mut users = self.users.write().await;
+        if let Some(stored_user) = users.get_mut(&user.id) {
+            stored_user.last_login = Some(Utc::now());
+        }
+        drop(users);
+
+        // Generate tokens; a fresh login starts a brand-new rotation family.
+        let access_token = self.generate_access_token(&user)?;
+        let family_id = Uuid::new_v4().to_string();
+        let refresh_token = self.generate_refresh_token(&user.id, &family_id).await?;
+
+        Ok((access_token, refresh_token))
+    }
+
+    /// Verify a username/password pair against the local Argon2 store. Distinguishes
+    /// `UnknownUser` from `InvalidPassword` for callers that log the precise cause;
+    /// [`Self::login`] normalizes both before they reach the frontend.
+    async fn verify_local_credentials(&self, username: &str, password: &str) -> Result<User, AuthError> {
         let users = self.users.read().await;
         let user = users
             .values()
             .find(|u| u.username == username)
-            .ok_or_else(|| anyhow!("Invalid credentials"))?
+            .ok_or_else(|| AuthError::new(AuthErrorKind::UnknownUser, format!("no account for '{username}'")))?
             .clone();
         drop(users);
 
-        // Verify password hash
         let hashes = self.password_hashes.read().await;
-        let stored_hash = hashes
-            .get(&user.id)
-            .ok_or_else(|| anyhow!("Invalid credentials"))?;
-        
+        let stored_hash = hashes.get(&user.id).ok_or_else(|| {
+            AuthError::new(AuthErrorKind::InvalidPassword, "account has no local password set")
+        })?;
+
         let parsed_hash = PasswordHash::new(stored_hash)
-            .map_err(|_| anyhow!("Invalid credentials"))?;
-        
+            .map_err(|e| AuthError::new(AuthErrorKind::Internal, e.to_string()))?;
+
         self.argon2
             .verify_password(password.as_bytes(), &parsed_hash)
-            .map_err(|_| anyhow!("Invalid credentials"))?;
-        drop(hashes);
+            .map_err(|_| AuthError::new(AuthErrorKind::InvalidPassword, "incorrect password"))?;
 
-        // Update last login
+        Ok(user)
+    }
 
+    /// Look up the local `User` record for an externally-verified identity,
+    /// provisioning one on first login. Provisioned users have no entry in
+    /// `password_hashes`, since their password lives in the external backend.
+    async fn provision_from_identity(&self, identity: ExternalIdentity) -> Result<User, AuthError> {
         let mut users = self.users.write().await;
-        if let Some(stored_user) = users.get_mut(&user.id) {
-            stored_user.last_login = Some(Utc::now());
+        if let Some(existing) = users.values().find(|u| u.username == identity.username) {
+            return Ok(existing.clone());
         }
 
-        // Generate tokens
-        let access_token = self.generate_access_token(&user)?;
-        let refresh_token = self.generate_refresh_token(&user.id).await?;
-
-        Ok((access_token, refresh_token))
+        let user = User {
+            id: Uuid::new_v4().to_string(),
+            username: identity.username,
+            email: identity.email,
+            role: identity.role,
+            created_at: Utc::now(),
+            last_login: None,
+            enterprise_id: None,
+            blocked: false,
+            blocked_reason: None,
+            blocked_at: None,
+        };
+        users.insert(user.id.clone(), user.clone());
+        Ok(user)
     }
 
-    /// Refresh access token
-    pub async fn refresh_access_token(&self, refresh_token: String) -> Result<String> {
-        // Verify refresh token
+    /// Redeem a refresh token for a new access token, rotating it to a brand-new
+    /// refresh token in the same family so each token is usable exactly once. If the
+    /// presented token has already been consumed by an earlier rotation, that's treated
+    /// as a compromise signal and the entire family is revoked.
+    pub async fn refresh_access_token(&self, refresh_token: String) -> Result<(String, String), AuthError> {
+        let (token_id, secret) = Self::split_refresh_token(&refresh_token)
+            .ok_or_else(|| AuthError::new(AuthErrorKind::InvalidRefreshToken, "malformed refresh token"))?;
+
+        let consumed = self.consumed_tokens.read().await;
+        if let Some(record) = consumed.get(token_id) {
+            let family_id = record.family_id.clone();
+            drop(consumed);
+            self.revoke_family(&family_id).await?;
+            return Err(AuthError::new(
+                AuthErrorKind::InvalidRefreshToken,
+                "refresh token reuse detected; all sessions revoked",
+            ));
+        }
+        drop(consumed);
+
+        // Look the record up directly by id, then Argon2-verify only that one secret.
         let tokens = self.refresh_tokens.read().await;
         let token_data = tokens
-            .values()
-            .find(|t| self.verify_refresh_token(&refresh_token, t))
-            .ok_or_else(|| anyhow!("Invalid refresh token"))?
-            .clone();
+            .get(token_id)
+            .filter(|t| self.verify_refresh_token(secret, t))
+            .cloned()
+            .ok_or_else(|| AuthError::new(AuthErrorKind::InvalidRefreshToken, "unknown or incorrect refresh token"))?;
         drop(tokens);
 
         // Check if expired
         if token_data.expires_at < Utc::now() {
-            return Err(anyhow!("Refresh token expired"));
+            return Err(AuthError::new(AuthErrorKind::RefreshTokenExpired, "refresh token expired"));
         }
 
         // Get user
         let users = self.users.read().await;
         let user = users
             .get(&token_data.user_id)
-            .ok_or_else(|| anyhow!("User not found"))?
+            .ok_or_else(|| AuthError::new(AuthErrorKind::UserNotFound, "user not found"))?
             .clone();
+        drop(users);
+
+        if user.blocked {
+            return Err(AuthError::new(AuthErrorKind::BlockedUser, "Account is blocked"));
+        }
+
+        // Consume the presented token and rotate to a new one in the same family.
+        let mut tokens = self.refresh_tokens.write().await;
+        tokens.remove(token_id);
+        drop(tokens);
+
+        let mut consumed = self.consumed_tokens.write().await;
+        consumed.insert(
+            token_id.to_string(),
+            ConsumedToken { family_id: token_data.family_id.clone(), consumed_at: Utc::now() },
+        );
+        drop(consumed);
 
-        // Generate new access token
-        self.generate_access_token(&user)
+        let access_token = self.generate_access_token(&user)?;
+        let new_refresh_token = self.generate_refresh_token(&user.id, &token_data.family_id).await?;
+
+        Ok((access_token, new_refresh_token))
     }
 
     /// Validate JWT token
-    pub fn validate_token(&self, token: &str) -> Result<Claims> {
+    pub fn validate_token(&self, token: &str) -> Result<Claims, AuthError> {
+        let mut validation = Validation::new(self.config.algorithm);
+        validation.set_issuer(&[&self.config.issuer]);
+        validation.set_audience(&[&self.config.audience]);
+
         let token_data = decode::<Claims>(
             token,
             &DecodingKey::from_secret(self.jwt_secret.as_ref()),
-            &Validation::default(),
-        )?;
+            &validation,
+        )
+        .map_err(|e| match e.kind() {
+            jsonwebtoken::errors::ErrorKind::ExpiredSignature => {
+                AuthError::new(AuthErrorKind::TokenExpired, "access token has expired")
+            }
+            _ => AuthError::new(AuthErrorKind::InvalidToken, e.to_string()),
+        })?;
 
         Ok(token_data.claims)
     }
 
-    /// Generate access token
-    fn generate_access_token(&self, user: &User) -> Result<String> {
+    /// Generate access token, carrying every scope `user`'s role permits.
+    fn generate_access_token(&self, user: &User) -> Result<String, AuthError> {
+        self.encode_access_token(user, role_scopes(&user.role))
+    }
+
+    /// Issue a narrowed access token for `user`, granting only the intersection of
+    /// `requested_scopes` with what their role permits (least-privilege delegation).
+    pub fn issue_scoped_token(&self, user: &User, requested_scopes: &[Scope]) -> Result<String, AuthError> {
+        let permitted = role_scopes(&user.role);
+        let granted: Vec<Scope> = requested_scopes
+            .iter()
+            .filter(|requested| permitted.iter().any(|p| p.grants(requested)))
+            .cloned()
+            .collect();
+
+        self.encode_access_token(user, granted)
+    }
+
+    fn encode_access_token(&self, user: &User, scope: Vec<Scope>) -> Result<String, AuthError> {
         let now = Utc::now();
         let claims = Claims {
             sub: user.id.clone(),
             username: user.username.clone(),
             role: user.role.clone(),
             enterprise_id: user.enterprise_id.clone(),
-            exp: (now + Duration::minutes(15)).timestamp(), // 15 minutes
+            exp: (now + self.config.access_token_ttl).timestamp(),
             iat: now.timestamp(),
             jti: Uuid::new_v4().to_string(),
+            iss: self.config.issuer.clone(),
+            aud: self.config.audience.clone(),
+            scope,
         };
 
         let token = encode(
-            &Header::default(),
+            &Header::new(self.config.algorithm),
             &claims,
             &EncodingKey::from_secret(self.jwt_secret.as_ref()),
-        )?;
+        )
+        .map_err(|e| AuthError::new(AuthErrorKind::Internal, e.to_string()))?;
 
         Ok(token)
     }
 
-    /// Generate refresh token
-    async fn generate_refresh_token(&self, user_id: &str) -> Result<String> {
+    /// Validate a JWT and additionally require it to carry every scope in `required`.
+    pub fn validate_token_for(&self, token: &str, required: &[Scope]) -> Result<Claims, AuthError> {
+        let claims = self.validate_token(token)?;
+
+        let missing = required.iter().find(|req| !claims.scope.iter().any(|granted| granted.grants(req)));
+        if let Some(missing) = missing {
+            return Err(AuthError::new(
+                AuthErrorKind::InsufficientScope,
+                format!("token is missing required scope '{missing}'"),
+            ));
+        }
+
+        Ok(claims)
+    }
+
+    /// Generate a refresh token belonging to `family_id`, so rotations descended from
+    /// the same login can all be revoked together if one of them is replayed.
+    async fn generate_refresh_token(&self, user_id: &str, family_id: &str) -> Result<String, AuthError> {
         let token_id = Uuid::new_v4().to_string();
-        let token_string = Uuid::new_v4().to_string();
-        
-        // Hash the refresh token
+
+        let mut secret_bytes = vec![0u8; self.config.refresh_token_bytes];
+        rand::thread_rng().fill_bytes(&mut secret_bytes);
+        let secret = URL_SAFE_NO_PAD.encode(&secret_bytes);
+
+        // Hash only the secret, so a leak of stored records can't reconstruct tokens.
         let salt = SaltString::generate(&mut OsRng);
         let token_hash = self
             .argon2
-            .hash_password(token_string.as_bytes(), &salt)?
+            .hash_password(secret.as_bytes(), &salt)
+            .map_err(|e| AuthError::new(AuthErrorKind::Internal, e.to_string()))?
             .to_string();
 
         let refresh_token = RefreshToken {
-            id: token_id,
+            id: token_id.clone(),
             user_id: user_id.to_string(),
+            family_id: family_id.to_string(),
             token_hash,
-            expires_at: Utc::now() + Duration::days(30), // 30 days
+            expires_at: Utc::now() + self.config.refresh_token_ttl,
             created_at: Utc::now(),
         };
 
-        // Store refresh token
+        // Store refresh token keyed by id, so lookups are O(1) instead of scanning
+        // every stored token through a (deliberately slow) Argon2 verification.
         let mut tokens = self.refresh_tokens.write().await;
-        tokens.insert(token_string.clone(), refresh_token);
+        tokens.insert(token_id.clone(), refresh_token);
 
-        Ok(token_string)
+        Ok(format!("{token_id}.{secret}"))
     }
 
-    /// Verify refresh token
-    fn verify_refresh_token(&self, token: &str, stored: &RefreshToken) -> bool {
+    /// Split a presented `"{token_id}.{secret}"` refresh token into its parts.
+    fn split_refresh_token(token: &str) -> Option<(&str, &str)> {
+        token.split_once('.')
+    }
+
+    /// Verify a presented secret against a single looked-up `RefreshToken` record.
+    fn verify_refresh_token(&self, secret: &str, stored: &RefreshToken) -> bool {
         let parsed_hash = PasswordHash::new(&stored.token_hash);
         match parsed_hash {
-            Ok(hash) => self.argon2.verify_password(token.as_bytes(), &hash).is_ok(),
+            Ok(hash) => self.argon2.verify_password(secret.as_bytes(), &hash).is_ok(),
             Err(_) => false,
         }
     }
 
     /// Revoke refresh token
-    pub async fn revoke_refresh_token(&self, token: String) -> Result<()> {
-        let mut tokens = self.refresh_tokens.write().await;
-        tokens.remove(&token);
+    pub async fn revoke_refresh_token(&self, token: String) -> Result<(), AuthError> {
+        if let Some((token_id, _secret)) = Self::split_refresh_token(&token) {
+            let mut tokens = self.refresh_tokens.write().await;
+            tokens.remove(token_id);
+        }
         Ok(())
     }
 
@@ -294,28 +624,29 @@ impl AuthManager {
         email: String,
         password: String,
         enterprise_id: String,
-    ) -> Result<User> {
+    ) -> Result<User, AuthError> {
         // Similar to register but with enterprise role
         if username.len() < 3 {
-            return Err(anyhow!("Username must be at least 3 characters"));
+            return Err(AuthError::new(AuthErrorKind::InvalidInput, "Username must be at least 3 characters"));
         }
         if !email.contains('@') {
-            return Err(anyhow!("Invalid email format"));
+            return Err(AuthError::new(AuthErrorKind::InvalidInput, "Invalid email format"));
         }
         if password.len() < 8 {
-            return Err(anyhow!("Password must be at least 8 characters"));
+            return Err(AuthError::new(AuthErrorKind::WeakPassword, "Password must be at least 8 characters"));
         }
 
         let users = self.users.read().await;
         if users.values().any(|u| u.username == username || u.email == email) {
-            return Err(anyhow!("User already exists"));
+            return Err(AuthError::new(AuthErrorKind::DuplicateUser, "User already exists"));
         }
         drop(users);
 
         let salt = SaltString::generate(&mut OsRng);
         let password_hash = self
             .argon2
-            .hash_password(password.as_bytes(), &salt)?
+            .hash_password(password.as_bytes(), &salt)
+            .map_err(|e| AuthError::new(AuthErrorKind::Internal, e.to_string()))?
             .to_string();
 
         let user = User {
@@ -326,6 +657,9 @@ impl AuthManager {
             created_at: Utc::now(),
             last_login: None,
             enterprise_id: Some(enterprise_id),
+            blocked: false,
+            blocked_reason: None,
+            blocked_at: None,
         };
 
         // Store user and password hash
@@ -342,15 +676,58 @@ impl AuthManager {
 
 
     /// Promote user to admin
-    pub async fn promote_to_admin(&self, user_id: &str) -> Result<()> {
+    pub async fn promote_to_admin(&self, user_id: &str) -> Result<(), AuthError> {
         let mut users = self.users.write().await;
         if let Some(user) = users.get_mut(user_id) {
             user.role = UserRole::Admin;
             Ok(())
         } else {
-            Err(anyhow!("User not found"))
+            Err(AuthError::new(AuthErrorKind::UserNotFound, "User not found"))
         }
     }
+
+    /// Block a user account, recording an optional reason, and revoke every refresh
+    /// token outstanding for them. Access tokens already issued stay valid until they
+    /// expire; `validate_token` does not check the block.
+    pub async fn block_user(&self, user_id: &str, reason: Option<String>) -> Result<(), AuthError> {
+        let mut users = self.users.write().await;
+        let user = users
+            .get_mut(user_id)
+            .ok_or_else(|| AuthError::new(AuthErrorKind::UserNotFound, "User not found"))?;
+        user.blocked = true;
+        user.blocked_reason = reason;
+        user.blocked_at = Some(Utc::now());
+        drop(users);
+
+        self.revoke_all_for_user(user_id).await
+    }
+
+    /// Lift a block placed by `block_user`.
+    pub async fn unblock_user(&self, user_id: &str) -> Result<(), AuthError> {
+        let mut users = self.users.write().await;
+        let user = users
+            .get_mut(user_id)
+            .ok_or_else(|| AuthError::new(AuthErrorKind::UserNotFound, "User not found"))?;
+        user.blocked = false;
+        user.blocked_reason = None;
+        user.blocked_at = None;
+        Ok(())
+    }
+
+    /// Revoke every outstanding refresh token for `user_id`.
+    pub async fn revoke_all_for_user(&self, user_id: &str) -> Result<(), AuthError> {
+        let mut tokens = self.refresh_tokens.write().await;
+        tokens.retain(|_, t| t.user_id != user_id);
+        Ok(())
+    }
+
+    /// Revoke every outstanding refresh token descended from `family_id`, used when a
+    /// consumed token is replayed to shut down a potentially compromised session chain.
+    async fn revoke_family(&self, family_id: &str) -> Result<(), AuthError> {
+        let mut tokens = self.refresh_tokens.write().await;
+        tokens.retain(|_, t| t.family_id != family_id);
+        Ok(())
+    }
 }
 
 // Tauri command handlers
@@ -368,10 +745,8 @@ pub async fn register_user(
     username: String,
     email: String,
     password: String,
-) -> Result<User, String> {
-    auth.register(username, email, password)
-        .await
-        .map_err(|e| e.to_string())
+) -> Result<User, AuthError> {
+    auth.register(username, email, password).await
 }
 
 /// Tauri command to authenticate a user
@@ -389,10 +764,8 @@ pub async fn login_user(
     auth: tauri::State<'_, Arc<AuthManager>>,
     username: String,
     password: String,
-) -> Result<(String, String), String> {
-    auth.login(username, password)
-        .await
-        .map_err(|e| e.to_string())
+) -> Result<(String, String), AuthError> {
+    auth.login(username, password).await
 }
 
 /// Tauri command to refresh an access token
@@ -402,16 +775,15 @@ pub async fn login_user(
 /// * `refresh_token` - The refresh token to use
 ///
 /// # Returns
-/// A new access token
+/// A tuple containing (access_token, refresh_token) -- the refresh token is rotated on
+/// every call, so the caller must discard the one it presented and store the new one.
 #[tauri::command]
 /// Refreshes token.
 pub async fn refresh_token(
     auth: tauri::State<'_, Arc<AuthManager>>,
     refresh_token: String,
-) -> Result<String, String> {
-    auth.refresh_access_token(refresh_token)
-        .await
-        .map_err(|e| e.to_string())
+) -> Result<(String, String), AuthError> {
+    auth.refresh_access_token(refresh_token).await
 }
 
 /// Tauri command to validate a JWT token
@@ -427,9 +799,8 @@ pub async fn refresh_token(
 pub async fn validate_jwt_token(
     auth: tauri::State<'_, Arc<AuthManager>>,
     token: String,
-) -> Result<Claims, String> {
+) -> Result<Claims, AuthError> {
     auth.validate_token(&token)
-        .map_err(|e| e.to_string())
 }
 
 /// Tauri command to logout a user by revoking their refresh token
@@ -442,10 +813,24 @@ pub async fn validate_jwt_token(
 pub async fn logout(
     auth: tauri::State<'_, Arc<AuthManager>>,
     refresh_token: String,
-) -> Result<(), String> {
-    auth.revoke_refresh_token(refresh_token)
-        .await
-        .map_err(|e| e.to_string())
+) -> Result<(), AuthError> {
+    auth.revoke_refresh_token(refresh_token).await
+}
+
+/// Tauri command to revoke every outstanding refresh token for the caller, logging out
+/// all of their sessions (e.g. after a suspected token leak)
+///
+/// # Arguments
+/// * `auth` - The AuthManager state
+/// * `token` - A valid access token identifying the caller
+#[tauri::command]
+/// Revokes all sessions for the calling user.
+pub async fn revoke_all_sessions(
+    auth: tauri::State<'_, Arc<AuthManager>>,
+    token: String,
+) -> Result<(), AuthError> {
+    let claims = auth.validate_token(&token)?;
+    auth.revoke_all_for_user(&claims.sub).await
 }
 
 /// Tauri command to get the current authenticated user
@@ -461,8 +846,8 @@ pub async fn logout(
 pub async fn get_current_user(
     auth: tauri::State<'_, Arc<AuthManager>>,
     token: String,
-) -> Result<Option<User>, String> {
-    let claims = auth.validate_token(&token).map_err(|e| e.to_string())?;
+) -> Result<Option<User>, AuthError> {
+    let claims = auth.validate_token(&token)?;
     Ok(auth.get_user(&claims.sub).await)
 }
 
@@ -482,10 +867,8 @@ pub async fn create_enterprise_user(
     email: String,
     password: String,
     enterprise_id: String,
-) -> Result<User, String> {
-    auth.create_enterprise_user(username, email, password, enterprise_id)
-        .await
-        .map_err(|e| e.to_string())
+) -> Result<User, AuthError> {
+    auth.create_enterprise_user(username, email, password, enterprise_id).await
 }
 
 /// Tauri command to promote a user to admin role
@@ -498,8 +881,84 @@ pub async fn create_enterprise_user(
 pub async fn promote_user_to_admin(
     auth: tauri::State<'_, Arc<AuthManager>>,
     user_id: String,
-) -> Result<(), String> {
-    auth.promote_to_admin(&user_id)
+) -> Result<(), AuthError> {
+    auth.promote_to_admin(&user_id).await
+}
+
+/// Tauri command to request a narrowed access token for a specific operation
+/// (least-privilege delegation)
+///
+/// # Arguments
+/// * `auth` - The AuthManager state
+/// * `token` - A valid access token identifying the caller
+/// * `scopes` - Requested scopes as `"resource:action"` strings, e.g. `"agent:execute"`
+#[tauri::command]
+/// Requests a scoped token.
+pub async fn request_scoped_token(
+    auth: tauri::State<'_, Arc<AuthManager>>,
+    token: String,
+    scopes: Vec<String>,
+) -> Result<String, AuthError> {
+    let claims = auth.validate_token(&token)?;
+    let user = auth
+        .get_user(&claims.sub)
         .await
-        .map_err(|e| e.to_string())
+        .ok_or_else(|| AuthError::new(AuthErrorKind::UserNotFound, "User not found"))?;
+
+    if user.blocked {
+        return Err(AuthError::new(AuthErrorKind::BlockedUser, "Account is blocked"));
+    }
+
+    let requested: Vec<Scope> = scopes
+        .into_iter()
+        .map(|s| s.parse())
+        .collect::<Result<Vec<Scope>>>()
+        .map_err(|e| AuthError::new(AuthErrorKind::InvalidScope, e.to_string()))?;
+
+    auth.issue_scoped_token(&user, &requested)
+}
+
+/// Confirm `admin_token` belongs to a currently-valid `UserRole::Admin` session.
+fn require_admin(auth: &AuthManager, admin_token: &str) -> Result<(), AuthError> {
+    let claims = auth.validate_token(admin_token)?;
+    match claims.role {
+        UserRole::Admin => Ok(()),
+        _ => Err(AuthError::new(AuthErrorKind::AdminRequired, "Admin role required")),
+    }
+}
+
+/// Tauri command to block a user account, admin-only
+///
+/// # Arguments
+/// * `auth` - The AuthManager state
+/// * `admin_token` - A JWT belonging to an admin, authorizing this action
+/// * `user_id` - The ID of the user to block
+/// * `reason` - An optional human-readable reason recorded on the account
+#[tauri::command]
+/// Blocks a user account.
+pub async fn block_user(
+    auth: tauri::State<'_, Arc<AuthManager>>,
+    admin_token: String,
+    user_id: String,
+    reason: Option<String>,
+) -> Result<(), AuthError> {
+    require_admin(&auth, &admin_token)?;
+    auth.block_user(&user_id, reason).await
+}
+
+/// Tauri command to lift a block placed on a user account, admin-only
+///
+/// # Arguments
+/// * `auth` - The AuthManager state
+/// * `admin_token` - A JWT belonging to an admin, authorizing this action
+/// * `user_id` - The ID of the user to unblock
+#[tauri::command]
+/// Unblocks a user account.
+pub async fn unblock_user(
+    auth: tauri::State<'_, Arc<AuthManager>>,
+    admin_token: String,
+    user_id: String,
+) -> Result<(), AuthError> {
+    require_admin(&auth, &admin_token)?;
+    auth.unblock_user(&user_id).await
 }
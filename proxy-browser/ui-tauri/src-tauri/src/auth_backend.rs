@@ -0,0 +1,140 @@
+//! Pluggable authentication backends
+//!
+//! Abstracts credential verification behind [`AuthBackend`] so `AuthManager::login` can
+//! delegate to an external identity provider instead of only checking the in-memory
+//! Argon2 store, starting with [`LdapBackend`] for enterprises that need to authenticate
+//! against a corporate directory rather than a local user table.
+
+use crate::auth::UserRole;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use ldap3::{LdapConnAsync, Scope, SearchEntry};
+
+/// An identity verified by an external [`AuthBackend`], used to provision or look up
+/// the local `User` record that JWT/refresh-token issuance still operates on.
+#[derive(Debug, Clone)]
+pub struct ExternalIdentity {
+    pub username: String,
+    pub email: String,
+    pub role: UserRole,
+}
+
+/// Verifies a username/password pair against an external identity source.
+#[async_trait]
+pub trait AuthBackend: Send + Sync {
+    async fn verify_credentials(&self, username: &str, password: &str) -> Result<ExternalIdentity>;
+}
+
+/// Maps an LDAP/Active Directory group DN to the [`UserRole`] its members should be
+/// provisioned with locally.
+#[derive(Debug, Clone)]
+pub struct GroupRoleMapping {
+    pub group_dn: String,
+    pub role: UserRole,
+}
+
+/// Configuration for [`LdapBackend`].
+#[derive(Debug, Clone)]
+pub struct LdapConfig {
+    /// e.g. `ldap://dc.example.com:389`
+    pub url: String,
+    /// Base DN to search for user entries under, e.g. `ou=people,dc=example,dc=com`.
+    pub base_dn: String,
+    /// Directory attribute holding the login name to search by, e.g. `uid` or `sAMAccountName`.
+    pub username_attr: String,
+    /// Directory attribute holding the user's email, e.g. `mail`.
+    pub email_attr: String,
+    /// Directory attribute listing the groups a user belongs to, e.g. `memberOf`.
+    pub group_attr: String,
+    /// Group-DN-to-role mappings, checked in order; the first match wins.
+    pub group_roles: Vec<GroupRoleMapping>,
+    /// Role assigned when no `group_roles` entry matches.
+    pub default_role: UserRole,
+}
+
+/// An [`AuthBackend`] that authenticates against an LDAP/Active Directory server: looks
+/// up the user's entry, binds as them to verify the password, then maps their directory
+/// groups to a [`UserRole`] via `config.group_roles`.
+pub struct LdapBackend {
+    config: LdapConfig,
+}
+
+impl LdapBackend {
+    pub fn new(config: LdapConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl AuthBackend for LdapBackend {
+    async fn verify_credentials(&self, username: &str, password: &str) -> Result<ExternalIdentity> {
+        // Reject up front: many directories treat a bind with a valid DN and an empty
+        // password as an unauthenticated bind that succeeds (RFC 4513 §5.1.2), which
+        // would let anyone log in as a known username with no password at all.
+        if password.is_empty() {
+            return Err(anyhow!("password must not be empty"));
+        }
+
+        let (conn, mut ldap) = LdapConnAsync::new(&self.config.url).await?;
+        ldap3::drive!(conn);
+
+        let filter = format!(
+            "({}={})",
+            self.config.username_attr,
+            escape_ldap_filter_value(username)
+        );
+        let (results, _) = ldap
+            .search(
+                &self.config.base_dn,
+                Scope::Subtree,
+                &filter,
+                vec![self.config.email_attr.as_str(), self.config.group_attr.as_str()],
+            )
+            .await?
+            .success()?;
+
+        let entry = results
+            .into_iter()
+            .next()
+            .map(SearchEntry::construct)
+            .ok_or_else(|| anyhow!("no directory entry found for '{username}'"))?;
+
+        // The search above only located the entry; binding as the user is what
+        // actually verifies their password.
+        ldap.simple_bind(&entry.dn, password).await?.success()?;
+
+        let email = entry
+            .attrs
+            .get(&self.config.email_attr)
+            .and_then(|values| values.first())
+            .cloned()
+            .unwrap_or_else(|| format!("{username}@unknown"));
+
+        let groups = entry.attrs.get(&self.config.group_attr).cloned().unwrap_or_default();
+        let role = self
+            .config
+            .group_roles
+            .iter()
+            .find(|mapping| groups.contains(&mapping.group_dn))
+            .map(|mapping| mapping.role.clone())
+            .unwrap_or_else(|| self.config.default_role.clone());
+
+        Ok(ExternalIdentity { username: username.to_string(), email, role })
+    }
+}
+
+/// Escape characters with special meaning in an LDAP search filter (RFC 4515), so a
+/// username containing `*`, `(`, `)`, `\`, or NUL can't alter the filter's structure.
+fn escape_ldap_filter_value(value: &str) -> String {
+    value
+        .chars()
+        .flat_map(|c| match c {
+            '\\' => "\\5c".chars().collect::<Vec<_>>(),
+            '*' => "\\2a".chars().collect(),
+            '(' => "\\28".chars().collect(),
+            ')' => "\\29".chars().collect(),
+            '\0' => "\\00".chars().collect(),
+            other => vec![other],
+        })
+        .collect()
+}
@@ -0,0 +1,191 @@
+//! WebDriver BiDi-style automation WebSocket server
+//!
+//! [`start_session`] binds a local `ws://127.0.0.1:<port>/session/<uuid>` socket,
+//! analogous to a WebDriver session opting into `webSocketUrl: true`, and hands the
+//! one connection it accepts a JSON command/event protocol over
+//! [`WebviewManager`](crate::webview_manager::WebviewManager):
+//!
+//! - Command frames (`{"id": ..., "command": "...", "params": {...}}`) map onto
+//!   `createTab`, `navigate`, `closeTab`, `listTabs`, `focusTab` and get a response
+//!   frame echoing the same `id`.
+//! - Event frames (`{"event": "...", ...}`, no `id`) are pushed unprompted as tabs
+//!   navigate, retitle, or close -- see
+//!   [`AutomationEvent`](crate::webview_manager::AutomationEvent).
+//!
+//! Gated behind `WebviewManager::set_automation_enabled` since a connected client
+//! gets full tab control.
+
+use crate::webview_manager::WebviewManager;
+use anyhow::{anyhow, Result};
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tokio_tungstenite::tungstenite::http::StatusCode;
+use tokio_tungstenite::tungstenite::Message;
+use tracing::warn;
+use uuid::Uuid;
+
+/// A command frame as sent by an automation client.
+#[derive(Debug, Deserialize)]
+struct CommandFrame {
+    id: Value,
+    command: String,
+    #[serde(default)]
+    params: Value,
+}
+
+/// Bind an ephemeral local port and return the `ws://127.0.0.1:<port>/session/<uuid>`
+/// URL a client should connect to. Only the first connection to that port is served;
+/// the listener and its session end once that connection closes.
+pub async fn start_session(app_handle: AppHandle) -> Result<String> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let port = listener.local_addr()?.port();
+    let session_id = Uuid::new_v4();
+    let url = format!("ws://127.0.0.1:{}/session/{}", port, session_id);
+
+    tokio::spawn(async move {
+        let (stream, _) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(err) => {
+                warn!(
+                    "automation session '{}' failed to accept: {}",
+                    session_id, err
+                );
+                return;
+            }
+        };
+        if let Err(err) = serve(app_handle, stream, session_id).await {
+            warn!("automation session '{}' ended: {}", session_id, err);
+        }
+    });
+
+    Ok(url)
+}
+
+/// Upgrade `stream` to a WebSocket and relay commands/events until it closes. Rejects
+/// the handshake unless the request path is exactly `/session/<session_id>`, so that
+/// any other local process racing `listener.accept()` for this ephemeral port can't
+/// steal the session merely by connecting first -- the UUID in the URL is the
+/// capability token, not decoration.
+async fn serve(app_handle: AppHandle, stream: TcpStream, session_id: Uuid) -> Result<()> {
+    let expected_path = format!("/session/{}", session_id);
+    let ws = tokio_tungstenite::accept_hdr_async(
+        stream,
+        move |request: &Request, response: Response| {
+            if request.uri().path() == expected_path {
+                Ok(response)
+            } else {
+                let mut rejection = ErrorResponse::new(None);
+                *rejection.status_mut() = StatusCode::FORBIDDEN;
+                Err(rejection)
+            }
+        },
+    )
+    .await
+    .map_err(|err| anyhow!("websocket handshake rejected: {err}"))?;
+    let (mut sink, mut source) = ws.split();
+    let mut events = app_handle.state::<WebviewManager>().subscribe_events();
+
+    loop {
+        tokio::select! {
+            incoming = source.next() => {
+                let Some(incoming) = incoming else { break };
+                let message = incoming?;
+                if !message.is_text() {
+                    continue;
+                }
+                let response = dispatch(&app_handle, message.into_text()?).await;
+                sink.send(Message::Text(response.to_string())).await?;
+            }
+            event = events.recv() => {
+                // A lagged receiver just means this subscriber missed some events
+                // under load; resume relaying rather than tearing down the session.
+                if let Ok(event) = event {
+                    sink.send(Message::Text(serde_json::to_string(&event)?)).await?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse and run one command frame, returning the JSON response frame to send back.
+async fn dispatch(app_handle: &AppHandle, text: String) -> Value {
+    let frame: CommandFrame = match serde_json::from_str(&text) {
+        Ok(frame) => frame,
+        Err(err) => return json!({ "error": format!("malformed command frame: {err}") }),
+    };
+
+    let manager = app_handle.state::<WebviewManager>();
+    let result = match frame.command.as_str() {
+        "createTab" => {
+            let url = frame
+                .params
+                .get("url")
+                .and_then(Value::as_str)
+                .map(str::to_string);
+            let proxy_url = frame
+                .params
+                .get("proxyUrl")
+                .and_then(Value::as_str)
+                .map(str::to_string);
+            manager
+                .create_tab_with_proxy(url, proxy_url)
+                .await
+                .map(|tab| json!(tab))
+                .map_err(|err| err.to_string())
+        }
+        "navigate" => {
+            let tab_id = frame
+                .params
+                .get("tabId")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let url = frame
+                .params
+                .get("url")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            manager
+                .navigate(tab_id, url)
+                .await
+                .map(|_| Value::Null)
+                .map_err(|err| err.to_string())
+        }
+        "closeTab" => {
+            let tab_id = frame
+                .params
+                .get("tabId")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            manager
+                .close_tab(tab_id)
+                .await
+                .map(|_| Value::Null)
+                .map_err(|err| err.to_string())
+        }
+        "listTabs" => Ok(json!(manager.list_tabs().await)),
+        "focusTab" => {
+            let tab_id = frame
+                .params
+                .get("tabId")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            manager
+                .focus_tab(tab_id)
+                .await
+                .map(|_| Value::Null)
+                .map_err(|err| err.to_string())
+        }
+        other => Err(format!("unknown command '{other}'")),
+    };
+
+    match result {
+        Ok(result) => json!({ "id": frame.id, "result": result }),
+        Err(error) => json!({ "id": frame.id, "error": error }),
+    }
+}
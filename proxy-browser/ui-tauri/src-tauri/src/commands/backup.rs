@@ -2,9 +2,13 @@
 //! 
 //! This module contains all commands for backup and restore operations.
 
-use browser_core::{BackupManager, BackupData, BackupOptions, BackupInfo};
+use browser_core::{
+    generate_recovery_keypair, AutoBackupSettings, AutoVerifySettings, BackupData, BackupInfo,
+    BackupManager, BackupOptions, BackupScheduler, PruneOptions, PruneReport,
+    RemoteDestinationConfig, SyncReport, VerifyReport,
+};
 use serde::{Deserialize, Serialize};
-use tauri::State;
+use tauri::{AppHandle, State, WebviewWindow};
 use tracing::{info, error, debug};
 use std::sync::Arc;
 
@@ -16,6 +20,29 @@ pub struct BackupResponse {
     pub backup_id: Option<String>,
 }
 
+/// A freshly generated X25519 recovery keypair, hex-encoded for transport to the UI.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryKeypairResponse {
+    pub public_key: String,
+    pub private_key: String,
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn from_hex(hex: &str) -> Result<[u8; 32], String> {
+    if hex.len() != 64 {
+        return Err("expected a 64-character hex-encoded key".to_string());
+    }
+    let mut out = [0u8; 32];
+    for (i, chunk) in hex.as_bytes().chunks(2).enumerate() {
+        let byte_str = std::str::from_utf8(chunk).map_err(|e| e.to_string())?;
+        out[i] = u8::from_str_radix(byte_str, 16).map_err(|e| e.to_string())?;
+    }
+    Ok(out)
+}
+
 /// Creates a new backup.
 #[tauri::command]
 pub async fn create_backup(
@@ -54,10 +81,13 @@ pub async fn list_backups(
 /// Restores from a backup.
 #[tauri::command]
 pub async fn restore_backup(
+    app_handle: AppHandle,
+    window: WebviewWindow,
     backup_manager: State<'_, Arc<BackupManager>>,
     backup_id: String,
     password: Option<String>,
 ) -> Result<BackupResponse, String> {
+    crate::ipc_guard::guard_remote(&app_handle, &window, "restore_backup").await?;
     info!("Restoring backup: {}", backup_id);
     match backup_manager.restore_backup(&backup_id, password.as_deref()).await {
         Ok(_) => Ok(BackupResponse {
@@ -75,9 +105,12 @@ pub async fn restore_backup(
 /// Deletes a backup.
 #[tauri::command]
 pub async fn delete_backup(
+    app_handle: AppHandle,
+    window: WebviewWindow,
     backup_manager: State<'_, Arc<BackupManager>>,
     backup_id: String,
 ) -> Result<BackupResponse, String> {
+    crate::ipc_guard::guard_remote(&app_handle, &window, "delete_backup").await?;
     info!("Deleting backup: {}", backup_id);
     match backup_manager.delete_backup(&backup_id).await {
         Ok(_) => Ok(BackupResponse {
@@ -118,9 +151,10 @@ pub async fn export_backup(
 pub async fn import_backup(
     backup_manager: State<'_, Arc<BackupManager>>,
     path: String,
+    password: Option<String>,
 ) -> Result<BackupResponse, String> {
     info!("Importing backup from {}", path);
-    match backup_manager.import_backup(&path).await {
+    match backup_manager.import_backup(&path, password.as_deref()).await {
         Ok(backup_info) => Ok(BackupResponse {
             success: true,
             message: format!("Backup imported: {}", backup_info.filename),
@@ -132,3 +166,272 @@ pub async fn import_backup(
         }
     }
 }
+
+/// Sets (or replaces) the recurring automatic-backup schedule.
+#[tauri::command]
+pub async fn set_backup_schedule(
+    scheduler: State<'_, Arc<BackupScheduler>>,
+    schedule: String,
+    options: Option<BackupOptions>,
+) -> Result<AutoBackupSettings, String> {
+    info!("Setting backup schedule: {}", schedule);
+    scheduler
+        .set_schedule(schedule, options.unwrap_or_default())
+        .await
+        .map_err(|e| {
+            error!("Failed to set backup schedule: {}", e);
+            e.to_string()
+        })
+}
+
+/// Returns the currently configured automatic-backup schedule, if any.
+#[tauri::command]
+pub async fn get_backup_schedule(
+    scheduler: State<'_, Arc<BackupScheduler>>,
+) -> Result<Option<AutoBackupSettings>, String> {
+    debug!("Getting backup schedule");
+    Ok(scheduler.get_schedule().await)
+}
+
+/// Clears the automatic-backup schedule and stops the recurring task.
+#[tauri::command]
+pub async fn clear_backup_schedule(
+    scheduler: State<'_, Arc<BackupScheduler>>,
+) -> Result<BackupResponse, String> {
+    info!("Clearing backup schedule");
+    scheduler.clear_schedule().await.map_err(|e| {
+        error!("Failed to clear backup schedule: {}", e);
+        e.to_string()
+    })?;
+    Ok(BackupResponse {
+        success: true,
+        message: "Backup schedule cleared".to_string(),
+        backup_id: None,
+    })
+}
+
+/// Previews which backups a retention policy would keep vs. remove, without deleting
+/// anything.
+#[tauri::command]
+pub async fn preview_prune_backups(
+    backup_manager: State<'_, Arc<BackupManager>>,
+    options: PruneOptions,
+) -> Result<PruneReport, String> {
+    debug!("Previewing backup prune with options: {:?}", options);
+    Ok(backup_manager.plan_prune(&options).await)
+}
+
+/// Applies a retention policy, deleting every backup it doesn't select to keep.
+#[tauri::command]
+pub async fn prune_backups(
+    backup_manager: State<'_, Arc<BackupManager>>,
+    options: PruneOptions,
+) -> Result<PruneReport, String> {
+    info!("Pruning backups with options: {:?}", options);
+    backup_manager.prune_backups(&options).await.map_err(|e| {
+        error!("Failed to prune backups: {}", e);
+        e.to_string()
+    })
+}
+
+/// Verifies a single backup's integrity, reporting any checksum problem found.
+#[tauri::command]
+pub async fn verify_backup(
+    backup_manager: State<'_, Arc<BackupManager>>,
+    backup_id: String,
+) -> Result<VerifyReport, String> {
+    info!("Verifying backup: {}", backup_id);
+    backup_manager.verify_backup(&backup_id).await.map_err(|e| {
+        error!("Failed to verify backup {}: {}", backup_id, e);
+        e.to_string()
+    })
+}
+
+/// Verifies every known backup's integrity.
+#[tauri::command]
+pub async fn verify_all_backups(
+    backup_manager: State<'_, Arc<BackupManager>>,
+) -> Result<Vec<VerifyReport>, String> {
+    info!("Verifying all backups");
+    backup_manager.verify_all_backups().await.map_err(|e| {
+        error!("Failed to verify backups: {}", e);
+        e.to_string()
+    })
+}
+
+/// Sets (or replaces) the recurring automatic-verification schedule.
+#[tauri::command]
+pub async fn set_verify_schedule(
+    scheduler: State<'_, Arc<BackupScheduler>>,
+    schedule: String,
+) -> Result<AutoVerifySettings, String> {
+    info!("Setting verify schedule: {}", schedule);
+    scheduler.set_verify_schedule(schedule).await.map_err(|e| {
+        error!("Failed to set verify schedule: {}", e);
+        e.to_string()
+    })
+}
+
+/// Returns the currently configured automatic-verification schedule, if any.
+#[tauri::command]
+pub async fn get_verify_schedule(
+    scheduler: State<'_, Arc<BackupScheduler>>,
+) -> Result<Option<AutoVerifySettings>, String> {
+    debug!("Getting verify schedule");
+    Ok(scheduler.get_verify_schedule().await)
+}
+
+/// Clears the automatic-verification schedule and stops the recurring task.
+#[tauri::command]
+pub async fn clear_verify_schedule(
+    scheduler: State<'_, Arc<BackupScheduler>>,
+) -> Result<BackupResponse, String> {
+    info!("Clearing verify schedule");
+    scheduler.clear_verify_schedule().await.map_err(|e| {
+        error!("Failed to clear verify schedule: {}", e);
+        e.to_string()
+    })?;
+    Ok(BackupResponse {
+        success: true,
+        message: "Verify schedule cleared".to_string(),
+        backup_id: None,
+    })
+}
+
+/// Generates a new X25519 recovery keypair for encrypted backups. The private key is
+/// returned once and must be saved by the caller -- it is never stored by this crate.
+#[tauri::command]
+pub fn generate_backup_recovery_keypair() -> RecoveryKeypairResponse {
+    let (public_key, private_key) = generate_recovery_keypair();
+    RecoveryKeypairResponse {
+        public_key: to_hex(&public_key),
+        private_key: to_hex(&private_key),
+    }
+}
+
+/// Configures where remote export/import commands read and write backup objects.
+#[tauri::command]
+pub async fn configure_remote_destination(
+    app_handle: AppHandle,
+    window: WebviewWindow,
+    backup_manager: State<'_, Arc<BackupManager>>,
+    config: RemoteDestinationConfig,
+) -> Result<BackupResponse, String> {
+    crate::ipc_guard::guard_remote(&app_handle, &window, "configure_remote_destination").await?;
+    info!("Configuring remote backup destination");
+    backup_manager.configure_remote_destination(config).await.map_err(|e| {
+        error!("Failed to configure remote backup destination: {}", e);
+        e.to_string()
+    })?;
+    Ok(BackupResponse {
+        success: true,
+        message: "Remote backup destination configured".to_string(),
+        backup_id: None,
+    })
+}
+
+/// Uploads a backup (and any chunks its destination doesn't already have) to the
+/// configured remote destination.
+#[tauri::command]
+pub async fn export_backup_remote(
+    app_handle: AppHandle,
+    window: WebviewWindow,
+    backup_manager: State<'_, Arc<BackupManager>>,
+    backup_id: String,
+    key_prefix: String,
+) -> Result<BackupResponse, String> {
+    crate::ipc_guard::guard_remote(&app_handle, &window, "export_backup_remote").await?;
+    info!("Exporting backup {} to remote destination under '{}'", backup_id, key_prefix);
+    backup_manager.export_backup_remote(&backup_id, &key_prefix).await.map_err(|e| {
+        error!("Failed to export backup {} remotely: {}", backup_id, e);
+        e.to_string()
+    })?;
+    Ok(BackupResponse {
+        success: true,
+        message: format!("Backup uploaded under '{}'", key_prefix),
+        backup_id: Some(backup_id),
+    })
+}
+
+/// Downloads a backup (and its chunks, if any) from the configured remote destination
+/// and imports it into this manager's local catalog.
+#[tauri::command]
+pub async fn import_backup_remote(
+    app_handle: AppHandle,
+    window: WebviewWindow,
+    backup_manager: State<'_, Arc<BackupManager>>,
+    backup_id: String,
+    key_prefix: String,
+    password: Option<String>,
+) -> Result<BackupResponse, String> {
+    crate::ipc_guard::guard_remote(&app_handle, &window, "import_backup_remote").await?;
+    info!("Importing backup {} from remote destination under '{}'", backup_id, key_prefix);
+    match backup_manager.import_backup_remote(&backup_id, &key_prefix, password.as_deref()).await {
+        Ok(backup_info) => Ok(BackupResponse {
+            success: true,
+            message: format!("Backup imported: {}", backup_info.filename),
+            backup_id: Some(backup_info.id),
+        }),
+        Err(e) => {
+            error!("Failed to import backup {} remotely: {}", backup_id, e);
+            Err(e.to_string())
+        }
+    }
+}
+
+/// Lists the ids of backups stored under `key_prefix` on the configured remote
+/// destination, without downloading any payload or chunk data.
+#[tauri::command]
+pub async fn list_remote_backups(
+    app_handle: AppHandle,
+    window: WebviewWindow,
+    backup_manager: State<'_, Arc<BackupManager>>,
+    key_prefix: String,
+) -> Result<Vec<String>, String> {
+    crate::ipc_guard::guard_remote(&app_handle, &window, "list_remote_backups").await?;
+    debug!("Listing remote backups under '{}'", key_prefix);
+    backup_manager.list_remote_backups(&key_prefix).await.map_err(|e| {
+        error!("Failed to list remote backups: {}", e);
+        e.to_string()
+    })
+}
+
+/// Uploads every local backup under `key_prefix` to the configured remote
+/// destination, skipping any object whose content hasn't changed since the last sync.
+#[tauri::command]
+pub async fn sync_backups(
+    app_handle: AppHandle,
+    window: WebviewWindow,
+    backup_manager: State<'_, Arc<BackupManager>>,
+    key_prefix: String,
+) -> Result<SyncReport, String> {
+    crate::ipc_guard::guard_remote(&app_handle, &window, "sync_backups").await?;
+    info!("Syncing backups to remote destination under '{}'", key_prefix);
+    backup_manager.sync_backups(&key_prefix).await.map_err(|e| {
+        error!("Failed to sync backups: {}", e);
+        e.to_string()
+    })
+}
+
+/// Restores an encrypted backup using a recovery private key instead of its password.
+#[tauri::command]
+pub async fn restore_backup_with_recovery_key(
+    backup_manager: State<'_, Arc<BackupManager>>,
+    backup_id: String,
+    recovery_private_key: String,
+) -> Result<BackupResponse, String> {
+    info!("Restoring backup {} with a recovery key", backup_id);
+    let key = from_hex(&recovery_private_key)?;
+    backup_manager
+        .restore_backup_with_recovery_key(&backup_id, &key)
+        .await
+        .map(|_| BackupResponse {
+            success: true,
+            message: "Backup restored successfully".to_string(),
+            backup_id: Some(backup_id.clone()),
+        })
+        .map_err(|e| {
+            error!("Failed to restore backup {} with recovery key: {}", backup_id, e);
+            e.to_string()
+        })
+}
@@ -2,10 +2,10 @@
 //! 
 //! This module contains all commands for IP detection and virtual IP generation.
 
-use browser_core::{PublicIpDetector, PublicIpInfo, FreeIpProviderManager};
+use browser_core::{PublicIpDetector, PublicIpInfo, FreeIpProviderManager, IpChangeEvent, IpWatcher, NoopGeoLookup};
 use virtual_ip::{IPGenerator, VirtualIP};
 use serde::{Deserialize, Serialize};
-use tauri::State;
+use tauri::{AppHandle, Emitter, State};
 use tracing::{info, error, debug};
 use std::sync::Arc;
 
@@ -112,3 +112,52 @@ pub async fn refresh_ip_providers(
         }
     }
 }
+
+/// Payload of the `ip-changed` event emitted while [`start_ip_watch`] is running.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpChangedPayload {
+    pub ip: String,
+    pub country: Option<String>,
+    pub isp: Option<String>,
+    pub previous_ip: Option<String>,
+}
+
+impl From<IpChangeEvent> for IpChangedPayload {
+    fn from(event: IpChangeEvent) -> Self {
+        Self {
+            ip: event.current.ip,
+            country: event.current.country,
+            isp: event.current.isp,
+            previous_ip: event.previous.map(|previous| previous.ip),
+        }
+    }
+}
+
+/// Starts a background loop that re-detects the public IP every `interval_secs`
+/// seconds, emitting an `ip-changed` event to the frontend whenever the detected IP,
+/// country, or ISP changes. Replaces any watch loop already running.
+#[tauri::command]
+pub async fn start_ip_watch(
+    app_handle: AppHandle,
+    watcher: State<'_, Arc<IpWatcher>>,
+    interval_secs: u64,
+) -> Result<(), String> {
+    info!("Starting IP watch loop every {}s", interval_secs);
+    let watcher = watcher.inner().clone();
+    watcher
+        .start(interval_secs, Arc::new(NoopGeoLookup), move |event| {
+            if let Err(e) = app_handle.emit("ip-changed", IpChangedPayload::from(event)) {
+                error!("Failed to emit ip-changed event: {}", e);
+            }
+        })
+        .await;
+    Ok(())
+}
+
+/// Stops the background IP watch loop started by [`start_ip_watch`], if any.
+#[tauri::command]
+pub async fn stop_ip_watch(watcher: State<'_, Arc<IpWatcher>>) -> Result<(), String> {
+    info!("Stopping IP watch loop");
+    watcher.stop().await;
+    Ok(())
+}
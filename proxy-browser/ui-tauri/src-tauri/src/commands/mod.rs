@@ -7,6 +7,9 @@ pub mod browser;
 pub mod storage;
 pub mod backup;
 pub mod ip;
+pub mod proxy_pool;
+pub mod screenshot;
+pub mod share;
 
 // Re-export all commands for easy access
 pub use proxy::*;
@@ -14,3 +17,6 @@ pub use browser::*;
 pub use storage::*;
 pub use backup::*;
 pub use ip::*;
+pub use proxy_pool::*;
+pub use screenshot::*;
+pub use share::*;
@@ -4,7 +4,7 @@
 
 use browser_core::{ProxyManager, ProxySettings, ProxyType, FreeProxy};
 use serde::{Deserialize, Serialize};
-use tauri::State;
+use tauri::{AppHandle, State, WebviewWindow};
 use tracing::{info, error, debug};
 use std::sync::Arc;
 
@@ -55,9 +55,12 @@ pub async fn get_active_proxy(
 /// Sets the active proxy.
 #[tauri::command]
 pub async fn set_active_proxy(
+    app_handle: AppHandle,
+    window: WebviewWindow,
     proxy_manager: State<'_, Arc<ProxyManager>>,
     proxy: FreeProxy,
 ) -> Result<ProxyResponse, String> {
+    crate::ipc_guard::guard_remote(&app_handle, &window, "set_active_proxy").await?;
     info!("Setting active proxy: {}:{}", proxy.ip, proxy.port);
     match proxy_manager.set_active_proxy(proxy).await {
         Ok(_) => Ok(ProxyResponse {
@@ -74,9 +77,12 @@ pub async fn set_active_proxy(
 /// Rotates to the next proxy based on the configured strategy.
 #[tauri::command]
 pub async fn rotate_proxy(
+    app_handle: AppHandle,
+    window: WebviewWindow,
     proxy_manager: State<'_, Arc<ProxyManager>>,
     strategy: Option<String>,
 ) -> Result<Option<FreeProxy>, String> {
+    crate::ipc_guard::guard_remote(&app_handle, &window, "rotate_proxy").await?;
     info!("Rotating proxy with strategy: {:?}", strategy);
     match proxy_manager.rotate_proxy(strategy.as_deref()).await {
         Ok(proxy) => Ok(proxy),
@@ -0,0 +1,42 @@
+//! Proxy pool health/rotation Tauri commands
+//!
+//! `commands::proxy` exposes the older single-active-proxy model. This module sits on
+//! top of `BrowserEngineManager`'s `ProxyPool` instead, which tracks per-proxy health
+//! scores and supports rotation strategies beyond a single active proxy.
+
+use browser_core::{BrowserEngineManager, ProxyPoolStatus, ProxyRotationStrategy, ProxySettings};
+use std::sync::Arc;
+use tauri::State;
+use tracing::{debug, info};
+
+/// Each pooled proxy's settings and latest observed health (score, sample count,
+/// last-checked/last-used time), so the UI can sort and prune dead proxies.
+#[tauri::command]
+pub async fn get_proxy_pool_stats(
+    manager: State<'_, Arc<BrowserEngineManager>>,
+) -> Result<Vec<ProxyPoolStatus>, String> {
+    debug!("Getting proxy pool stats");
+    Ok(manager.get_proxy_pool_status().await)
+}
+
+/// Changes the pool's rotation strategy.
+#[tauri::command]
+pub async fn set_proxy_rotation_strategy(
+    manager: State<'_, Arc<BrowserEngineManager>>,
+    strategy: ProxyRotationStrategy,
+) -> Result<(), String> {
+    info!("Setting proxy rotation strategy to {:?}", strategy);
+    manager.set_rotation_strategy(strategy).await;
+    Ok(())
+}
+
+/// Removes every pooled proxy whose health score has fallen below `min_score`.
+/// Returns the removed proxies.
+#[tauri::command]
+pub async fn prune_dead_proxies(
+    manager: State<'_, Arc<BrowserEngineManager>>,
+    min_score: f64,
+) -> Result<Vec<ProxySettings>, String> {
+    info!("Pruning proxies with score below {}", min_score);
+    Ok(manager.prune_dead_proxies(min_score).await)
+}
@@ -0,0 +1,39 @@
+//! Screenshot-related Tauri commands
+//!
+//! This module contains commands for capturing and safety-checking screenshots.
+
+use browser_core::{ScreenshotManager, ScreenshotOptions, ScreenshotResult};
+use std::sync::Arc;
+use tauri::State;
+use tracing::{debug, error};
+
+/// Captures a viewport screenshot of the given tab, running it through the
+/// `ScreenshotManager`'s configured safety check (if any) so UI flows that
+/// auto-screenshot arbitrary user-supplied URLs can gate or flag the result before
+/// showing it.
+#[tauri::command]
+pub async fn capture_tab_screenshot(
+    screenshot_manager: State<'_, Arc<ScreenshotManager>>,
+    tab_id: String,
+    options: Option<ScreenshotOptions>,
+) -> Result<ScreenshotResult, String> {
+    debug!("Capturing screenshot for tab {}", tab_id);
+    let options = options.unwrap_or_default();
+    match screenshot_manager
+        .capture_viewport(&tab_id, &options)
+        .await
+    {
+        Ok(result) => Ok(result),
+        Err(e) => {
+            error!("Failed to capture screenshot for tab {}: {}", tab_id, e);
+            Err(e.to_string())
+        }
+    }
+}
+
+/// Reports whether a previously captured screenshot is below a caller-supplied safety
+/// threshold, without re-running classification.
+#[tauri::command]
+pub async fn is_screenshot_safe(result: ScreenshotResult, threshold: f32) -> Result<bool, String> {
+    Ok(result.is_safe(threshold))
+}
@@ -0,0 +1,43 @@
+//! Screenshot share-link Tauri commands
+//!
+//! This module contains commands for publishing and resolving expiring,
+//! password-protected screenshot share links.
+
+use browser_core::{ScreenshotResult, ShareStore};
+use std::sync::Arc;
+use tauri::State;
+use tracing::{debug, error};
+
+/// Publishes `result` as a time-limited, optionally password-protected share link.
+/// Returns the share token.
+#[tauri::command]
+pub async fn share_screenshot(
+    share_store: State<'_, Arc<ShareStore>>,
+    result: ScreenshotResult,
+    password: Option<String>,
+    lifetime_hours: u64,
+) -> Result<String, String> {
+    debug!("Publishing screenshot share link (lifetime: {}h)", lifetime_hours);
+    share_store
+        .publish(&result, password, lifetime_hours)
+        .await
+        .map_err(|e| {
+            error!("Failed to publish screenshot share: {}", e);
+            e.to_string()
+        })
+}
+
+/// Resolves a share token back to its screenshot, verifying `password` against the
+/// stored hash first if the share was published with one.
+#[tauri::command]
+pub async fn resolve_share(
+    share_store: State<'_, Arc<ShareStore>>,
+    token: String,
+    password: Option<String>,
+) -> Result<ScreenshotResult, String> {
+    debug!("Resolving screenshot share {}", token);
+    share_store.resolve(&token, password).await.map_err(|e| {
+        error!("Failed to resolve screenshot share {}: {}", token, e);
+        e.to_string()
+    })
+}
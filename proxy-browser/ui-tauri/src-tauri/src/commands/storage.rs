@@ -1,12 +1,12 @@
 //! Storage-related Tauri commands
-//! 
+//!
 //! This module contains all commands for managing stored data like cookies, history, and bookmarks.
 
-use browser_core::{StorageEngine, Cookie, HistoryEntry, Bookmark};
+use browser_core::{Bookmark, Cookie, HistoryEntry, StorageEngine};
 use serde::{Deserialize, Serialize};
-use tauri::State;
-use tracing::{info, error, debug};
 use std::sync::Arc;
+use tauri::{AppHandle, State, WebviewWindow};
+use tracing::{debug, error, info};
 
 /// Response structure for storage operations.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,8 +18,11 @@ pub struct StorageResponse {
 /// Gets all cookies.
 #[tauri::command]
 pub async fn get_cookies(
+    app_handle: AppHandle,
+    window: WebviewWindow,
     storage_engine: State<'_, Arc<StorageEngine>>,
 ) -> Result<Vec<Cookie>, String> {
+    crate::ipc_guard::guard_remote(&app_handle, &window, "get_cookies").await?;
     debug!("Getting cookies");
     match storage_engine.get_cookies().await {
         Ok(cookies) => Ok(cookies),
@@ -33,10 +36,13 @@ pub async fn get_cookies(
 /// Deletes a cookie.
 #[tauri::command]
 pub async fn delete_cookie(
+    app_handle: AppHandle,
+    window: WebviewWindow,
     storage_engine: State<'_, Arc<StorageEngine>>,
     domain: String,
     name: String,
 ) -> Result<StorageResponse, String> {
+    crate::ipc_guard::guard_remote(&app_handle, &window, "delete_cookie").await?;
     info!("Deleting cookie: {} from {}", name, domain);
     match storage_engine.delete_cookie(&domain, &name).await {
         Ok(_) => Ok(StorageResponse {
@@ -53,9 +59,12 @@ pub async fn delete_cookie(
 /// Gets browsing history.
 #[tauri::command]
 pub async fn get_history(
+    app_handle: AppHandle,
+    window: WebviewWindow,
     storage_engine: State<'_, Arc<StorageEngine>>,
     limit: Option<usize>,
 ) -> Result<Vec<HistoryEntry>, String> {
+    crate::ipc_guard::guard_remote(&app_handle, &window, "get_history").await?;
     debug!("Getting history with limit: {:?}", limit);
     match storage_engine.get_history(limit.unwrap_or(100)).await {
         Ok(history) => Ok(history),
@@ -69,12 +78,18 @@ pub async fn get_history(
 /// Adds a history entry.
 #[tauri::command]
 pub async fn add_history_entry(
+    app_handle: AppHandle,
+    window: WebviewWindow,
     storage_engine: State<'_, Arc<StorageEngine>>,
     url: String,
     title: Option<String>,
 ) -> Result<StorageResponse, String> {
+    crate::ipc_guard::guard_remote(&app_handle, &window, "add_history_entry").await?;
     debug!("Adding history entry: {}", url);
-    match storage_engine.add_history_entry(&url, title.as_deref()).await {
+    match storage_engine
+        .add_history_entry(&url, title.as_deref())
+        .await
+    {
         Ok(_) => Ok(StorageResponse {
             success: true,
             message: "History entry added".to_string(),
@@ -89,9 +104,12 @@ pub async fn add_history_entry(
 /// Deletes a history entry.
 #[tauri::command]
 pub async fn delete_history_entry(
+    app_handle: AppHandle,
+    window: WebviewWindow,
     storage_engine: State<'_, Arc<StorageEngine>>,
     id: String,
 ) -> Result<StorageResponse, String> {
+    crate::ipc_guard::guard_remote(&app_handle, &window, "delete_history_entry").await?;
     info!("Deleting history entry: {}", id);
     match storage_engine.delete_history_entry(&id).await {
         Ok(_) => Ok(StorageResponse {
@@ -108,8 +126,11 @@ pub async fn delete_history_entry(
 /// Clears all history.
 #[tauri::command]
 pub async fn clear_history(
+    app_handle: AppHandle,
+    window: WebviewWindow,
     storage_engine: State<'_, Arc<StorageEngine>>,
 ) -> Result<StorageResponse, String> {
+    crate::ipc_guard::guard_remote(&app_handle, &window, "clear_history").await?;
     info!("Clearing all history");
     match storage_engine.clear_history().await {
         Ok(_) => Ok(StorageResponse {
@@ -126,8 +147,11 @@ pub async fn clear_history(
 /// Gets all bookmarks.
 #[tauri::command]
 pub async fn get_bookmarks(
+    app_handle: AppHandle,
+    window: WebviewWindow,
     storage_engine: State<'_, Arc<StorageEngine>>,
 ) -> Result<Vec<Bookmark>, String> {
+    crate::ipc_guard::guard_remote(&app_handle, &window, "get_bookmarks").await?;
     debug!("Getting bookmarks");
     match storage_engine.get_bookmarks().await {
         Ok(bookmarks) => Ok(bookmarks),
@@ -141,13 +165,19 @@ pub async fn get_bookmarks(
 /// Adds a bookmark.
 #[tauri::command]
 pub async fn add_bookmark(
+    app_handle: AppHandle,
+    window: WebviewWindow,
     storage_engine: State<'_, Arc<StorageEngine>>,
     url: String,
     title: String,
     folder: Option<String>,
 ) -> Result<StorageResponse, String> {
+    crate::ipc_guard::guard_remote(&app_handle, &window, "add_bookmark").await?;
     info!("Adding bookmark: {} - {}", title, url);
-    match storage_engine.add_bookmark(&url, &title, folder.as_deref()).await {
+    match storage_engine
+        .add_bookmark(&url, &title, folder.as_deref())
+        .await
+    {
         Ok(_) => Ok(StorageResponse {
             success: true,
             message: "Bookmark added".to_string(),
@@ -162,14 +192,20 @@ pub async fn add_bookmark(
 /// Updates a bookmark.
 #[tauri::command]
 pub async fn update_bookmark(
+    app_handle: AppHandle,
+    window: WebviewWindow,
     storage_engine: State<'_, Arc<StorageEngine>>,
     id: String,
     url: Option<String>,
     title: Option<String>,
     folder: Option<String>,
 ) -> Result<StorageResponse, String> {
+    crate::ipc_guard::guard_remote(&app_handle, &window, "update_bookmark").await?;
     info!("Updating bookmark: {}", id);
-    match storage_engine.update_bookmark(&id, url.as_deref(), title.as_deref(), folder.as_deref()).await {
+    match storage_engine
+        .update_bookmark(&id, url.as_deref(), title.as_deref(), folder.as_deref())
+        .await
+    {
         Ok(_) => Ok(StorageResponse {
             success: true,
             message: "Bookmark updated".to_string(),
@@ -184,9 +220,12 @@ pub async fn update_bookmark(
 /// Deletes a bookmark.
 #[tauri::command]
 pub async fn delete_bookmark(
+    app_handle: AppHandle,
+    window: WebviewWindow,
     storage_engine: State<'_, Arc<StorageEngine>>,
     id: String,
 ) -> Result<StorageResponse, String> {
+    crate::ipc_guard::guard_remote(&app_handle, &window, "delete_bookmark").await?;
     info!("Deleting bookmark: {}", id);
     match storage_engine.delete_bookmark(&id).await {
         Ok(_) => Ok(StorageResponse {
@@ -0,0 +1,75 @@
+//! IPC origin guard
+//!
+//! Windows created by `WebviewManager::create_tab_with_proxy` load arbitrary external
+//! URLs, so any Tauri command invoked from one of those windows may really be coming
+//! from a visited site rather than this app's own UI. [`guard_remote`] rejects such
+//! invocations for storage, webview-management, proxy, and backup commands --
+//! `get_cookies`, `delete_cookie`, history, bookmarks, every `*_webview_tab` command,
+//! `set_active_proxy`/`rotate_proxy`, and `restore_backup`/`delete_backup`/
+//! `configure_remote_destination`/`export_backup_remote`/`import_backup_remote`/
+//! `list_remote_backups`/`sync_backups` -- unless the command has been explicitly
+//! added to the [`RemoteIpcAllowlist`], which starts out empty.
+
+use crate::webview_manager::WebviewManager;
+use std::collections::HashSet;
+use tauri::{AppHandle, Manager, WebviewWindow};
+use tokio::sync::RwLock;
+
+/// Commands remote (tab) windows are permitted to invoke, by name. Empty by default,
+/// so every storage/navigation command is denied to remote content until an
+/// integrator explicitly opts a command in.
+#[derive(Debug, Default)]
+pub struct RemoteIpcAllowlist(RwLock<HashSet<String>>);
+
+impl RemoteIpcAllowlist {
+    pub fn new() -> Self {
+        Self(RwLock::new(HashSet::new()))
+    }
+
+    /// Permit `command` to be invoked from a remote browsing tab.
+    pub async fn allow(&self, command: &str) {
+        self.0.write().await.insert(command.to_string());
+    }
+
+    /// Revoke a previously granted allowance for `command`.
+    pub async fn disallow(&self, command: &str) {
+        self.0.write().await.remove(command);
+    }
+
+    async fn is_allowed(&self, command: &str) -> bool {
+        self.0.read().await.contains(command)
+    }
+}
+
+/// Whether `window` is a remote browsing tab rather than the app's own chrome, i.e.
+/// whether [`WebviewManager`] is tracking it as a
+/// [`WebviewTab`](crate::webview_manager::WebviewTab).
+pub async fn is_remote_window(app_handle: &AppHandle, window: &WebviewWindow) -> bool {
+    app_handle
+        .state::<WebviewManager>()
+        .has_window_label(window.label())
+        .await
+}
+
+/// Reject `command` with an error if it was invoked from a remote browsing tab and
+/// isn't on the [`RemoteIpcAllowlist`]. Call this first thing in every storage and
+/// webview-management command handler.
+pub async fn guard_remote(
+    app_handle: &AppHandle,
+    window: &WebviewWindow,
+    command: &str,
+) -> Result<(), String> {
+    if !is_remote_window(app_handle, window).await {
+        return Ok(());
+    }
+    if app_handle
+        .state::<RemoteIpcAllowlist>()
+        .is_allowed(command)
+        .await
+    {
+        return Ok(());
+    }
+    Err(format!(
+        "command '{command}' is not permitted from remote content"
+    ))
+}
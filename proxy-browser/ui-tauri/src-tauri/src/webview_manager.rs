@@ -5,15 +5,55 @@
 //! - Navigation control (forward, back, reload)
 //! - Tab lifecycle management (create, close, focus)
 //! - Tab state tracking (URL, title, loading status)
+//! - Cross-device tab sync: each device periodically writes its own tab set to a
+//!   shared sync directory and reads back every other device's, so a synced folder
+//!   (Dropbox-style, or a networked drive) is enough to see "tabs from your other
+//!   devices" without a dedicated sync server
+//! - A pre-warmed window pool so `create_tab_with_proxy` can usually hand out an
+//!   already-created hidden window instead of paying `WebviewWindowBuilder`'s cost
+//!   synchronously on every call
+//! - Per-tab proxy isolation: a proxied tab gets its own WebView2 environment built
+//!   with a `--proxy-server` argument, and `set_tab_proxy` tears down and rebuilds a
+//!   tab's window rather than mutating a process-global proxy every tab would share
+//! - An opt-in automation WebSocket server (`crate::automation_server`) exposing
+//!   `createTab`/`navigate`/`closeTab`/`listTabs`/`focusTab` commands and
+//!   `navigationChanged`/`titleChanged`/`tabClosed` events for remote tab control
+//! - Per-tab content settings: cookie/storage/geolocation/third-party-request
+//!   counters and a blocked-origin list, fed by injected page-side hooks reporting
+//!   through `record_resource_event`, for a per-tab privacy panel
+//!
+//! Every `*_webview_tab` command here is gated by `crate::ipc_guard::guard_remote`,
+//! since the window invoking it may be one of this manager's own browsing tabs --
+//! i.e. a page the user visited, not this app's UI.
 
 use anyhow::{anyhow, Result};
+use browser_core::ProxySettings;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 use tauri::{AppHandle, Manager, WebviewWindow};
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, Mutex, RwLock};
+use tracing::warn;
 use uuid::Uuid;
 
+/// Name of the file (under a manager's `sync_dir`) holding this device's stable,
+/// restart-surviving sync client ID.
+const CLIENT_ID_FILE_NAME: &str = ".client_id";
+/// Subdirectory of `sync_dir` holding one JSON [`ClientRecord`] per client, so each
+/// device only ever writes its own file and never races another device's write.
+const CLIENTS_SUBDIR: &str = "clients";
+/// Default age after which another device's [`ClientRecord`] is treated as stale and
+/// excluded from [`WebviewManager::pull_tabs`], absent an explicit override.
+const DEFAULT_STALE_AFTER_SECS: u64 = 24 * 60 * 60;
+/// Default number of hidden windows [`WindowPool`] keeps pre-created.
+const DEFAULT_WINDOW_POOL_SIZE: usize = 4;
+/// Backlog size of the [`AutomationEvent`] broadcast channel. A slow/disconnected
+/// automation subscriber drops the oldest events past this rather than blocking tab
+/// operations.
+const AUTOMATION_EVENT_CHANNEL_SIZE: usize = 256;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 /// Represents a WebviewTab.
 pub struct WebviewTab {
@@ -25,6 +65,227 @@ pub struct WebviewTab {
     pub can_go_back: bool,
     pub can_go_forward: bool,
     pub created_at: std::time::SystemTime,
+    /// URLs this tab has navigated through, oldest first, including the current `url`.
+    pub url_history: Vec<String>,
+    pub icon: Option<String>,
+    pub last_used: std::time::SystemTime,
+    /// The `scheme://[user:pass@]host:port` proxy this tab's window was built with, if
+    /// any, credentials included. The window's `--proxy-server` argument is built
+    /// without the credentials, and no handler in this module answers the proxy's auth
+    /// challenge with them.
+    pub proxy_url: Option<String>,
+    /// Resource access/blocking counters for this tab, updated via
+    /// [`WebviewManager::record_resource_event`].
+    pub content_settings: ContentSettings,
+}
+
+/// Per-tab counters and blocked-origin list mirroring
+/// [`browser_core::ContentSettings`], but fed by injected-JS resource hooks reported
+/// through [`WebviewManager::record_resource_event`] rather than CDP `Network`/
+/// `Storage` events -- this process has no CDP access to the webview it hosts.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContentSettings {
+    pub cookies_set: u32,
+    pub cookies_blocked: u32,
+    pub local_storage_accesses: u32,
+    pub geolocation_prompts: u32,
+    pub third_party_requests: u32,
+    /// Origins a cookie was blocked for, de-duplicated.
+    pub blocked_origins: Vec<String>,
+}
+
+/// A resource access/block an injected page-side hook reported for a tab, passed to
+/// [`WebviewManager::record_resource_event`] and the `report_tab_resource_event`
+/// command. Wire values are camelCase to match the rest of this module's
+/// externally-facing event names (`navigationChanged`, `titleChanged`, ...).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ResourceEventKind {
+    CookieSet,
+    CookieBlocked,
+    LocalStorageAccess,
+    GeolocationPrompt,
+    ThirdPartyRequest,
+}
+
+/// A tab as published to the cross-device sync store, stripped of the local,
+/// device-specific plumbing (`window_label`, loading/back-forward state) a remote
+/// device has no use for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteTab {
+    pub url_history: Vec<String>,
+    pub title: String,
+    pub icon: Option<String>,
+    pub last_used: SystemTime,
+}
+
+impl From<&WebviewTab> for RemoteTab {
+    fn from(tab: &WebviewTab) -> Self {
+        Self {
+            url_history: tab.url_history.clone(),
+            title: tab.title.clone(),
+            icon: tab.icon.clone(),
+            last_used: tab.last_used,
+        }
+    }
+}
+
+/// One device's full tab set, as stored at `<sync_dir>/clients/<client_id>.json`.
+/// Reconciliation across devices is last-writer-wins *per record*: a newer
+/// `last_modified` wholly replaces an older one rather than merging tab lists.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientRecord {
+    pub client_id: String,
+    pub device_name: String,
+    pub last_modified: SystemTime,
+    pub tabs: Vec<RemoteTab>,
+}
+
+/// Another device's open tabs, grouped for a "tabs from your other devices" UI panel.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteDeviceTabs {
+    pub client_id: String,
+    pub device_name: String,
+    pub tabs: Vec<RemoteTab>,
+}
+
+/// An unsolicited frame pushed to every [`WebviewManager::subscribe_events`]
+/// subscriber as tabs change, so an automation client (see
+/// `crate::automation_server`) finds out about navigation without polling
+/// `listTabs`. The `event` tag is the wire name an external client matches on;
+/// everything else stays snake_case like the rest of this module's JSON.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event")]
+pub enum AutomationEvent {
+    #[serde(rename = "navigationChanged")]
+    NavigationChanged {
+        tab_id: String,
+        url: String,
+        title: String,
+    },
+    #[serde(rename = "titleChanged")]
+    TitleChanged { tab_id: String, title: String },
+    #[serde(rename = "tabClosed")]
+    TabClosed { tab_id: String },
+}
+
+/// A hidden, pre-created `WebviewWindow` sitting idle in a [`WindowPool`], ready to be
+/// checked out and shown as a new tab without paying window-creation latency on the
+/// caller's critical path.
+struct PooledWindow {
+    window_label: String,
+    window: WebviewWindow,
+}
+
+/// Pre-warmed window pool hit/miss counts and current idle size, as returned by
+/// [`WebviewManager::pool_stats`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+pub struct PoolStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub idle: usize,
+    pub target_size: usize,
+}
+
+/// Maintains `target_size` hidden, pre-created windows so `create_tab_with_proxy` can
+/// check one out instead of paying `WebviewWindowBuilder`'s creation cost
+/// synchronously. Checked-out windows are not returned to the pool -- once shown as a
+/// live tab, a background task tops the pool back up with a freshly created hidden
+/// window instead.
+struct WindowPool {
+    idle: Mutex<Vec<PooledWindow>>,
+    target_size: usize,
+    label_counter: RwLock<u32>,
+    hits: RwLock<u64>,
+    misses: RwLock<u64>,
+}
+
+impl WindowPool {
+    fn new(target_size: usize) -> Self {
+        Self {
+            idle: Mutex::new(Vec::new()),
+            target_size,
+            label_counter: RwLock::new(0),
+            hits: RwLock::new(0),
+            misses: RwLock::new(0),
+        }
+    }
+
+    async fn next_label(&self) -> String {
+        let mut counter = self.label_counter.write().await;
+        *counter += 1;
+        format!("pool_{}", *counter)
+    }
+
+    /// Create one hidden, blank window and push it onto the idle list.
+    async fn spawn_one(&self, app_handle: &AppHandle) -> Result<()> {
+        let label = self.next_label().await;
+        let window = tauri::WebviewWindowBuilder::new(
+            app_handle,
+            &label,
+            tauri::WebviewUrl::External("about:blank".parse()?),
+        )
+        .title("New Tab - Virtual IP Browser")
+        .inner_size(1200.0, 800.0)
+        .min_inner_size(400.0, 300.0)
+        .center()
+        .decorations(true)
+        .resizable(true)
+        .visible(false)
+        .build()?;
+
+        self.idle.lock().await.push(PooledWindow {
+            window_label: label,
+            window,
+        });
+        Ok(())
+    }
+
+    /// Top the idle pool back up to `target_size`, spawning windows one at a time so a
+    /// burst of checkouts doesn't race a burst of creations against each other.
+    async fn refill(&self, app_handle: &AppHandle) {
+        loop {
+            let deficit = {
+                let idle = self.idle.lock().await;
+                self.target_size.saturating_sub(idle.len())
+            };
+            if deficit == 0 {
+                break;
+            }
+            if let Err(err) = self.spawn_one(app_handle).await {
+                warn!("failed to pre-warm a pooled webview window: {}", err);
+                break;
+            }
+        }
+    }
+
+    /// Check out an idle window, recording a hit, or report a miss if the pool is
+    /// currently empty.
+    async fn checkout(&self) -> Option<PooledWindow> {
+        let pooled = self.idle.lock().await.pop();
+        if pooled.is_some() {
+            *self.hits.write().await += 1;
+        } else {
+            *self.misses.write().await += 1;
+        }
+        pooled
+    }
+
+    async fn stats(&self) -> PoolStats {
+        PoolStats {
+            hits: *self.hits.read().await,
+            misses: *self.misses.read().await,
+            idle: self.idle.lock().await.len(),
+            target_size: self.target_size,
+        }
+    }
+
+    /// Close every idle pooled window, e.g. on application shutdown.
+    async fn drain(&self) {
+        for pooled in self.idle.lock().await.drain(..) {
+            let _ = pooled.window.close();
+        }
+    }
 }
 
 /// Represents a WebviewManager.
@@ -32,48 +293,267 @@ pub struct WebviewManager {
     app_handle: AppHandle,
     tabs: Arc<RwLock<HashMap<String, WebviewTab>>>,
     window_counter: RwLock<u32>,
+    /// Directory backing cross-device tab sync, or `None` when this manager was
+    /// built with [`WebviewManager::new`] and sync is disabled.
+    sync_dir: Option<PathBuf>,
+    /// Stable across restarts once persisted via [`WebviewManager::load`].
+    client_id: String,
+    device_name: String,
+    stale_after: Duration,
+    window_pool: Arc<WindowPool>,
+    /// Credentials for tabs whose proxy requires auth, keyed by `window_label`, kept
+    /// out of any `--proxy-server` argument or environment variable. Nothing in this
+    /// module reads them to answer a proxy auth challenge. An entry is removed when
+    /// `set_tab_proxy` replaces the window behind its label; `close_tab` leaves it.
+    pending_proxy_auth: Arc<RwLock<HashMap<String, (String, String)>>>,
+    /// Whether `start_automation_session` is allowed to bind a control socket.
+    /// Defaults to `false`: the automation server grants full tab control to
+    /// whatever can reach the bound port, so it must be opted into explicitly.
+    automation_enabled: bool,
+    event_tx: broadcast::Sender<AutomationEvent>,
 }
 
 impl WebviewManager {
-    /// Creates a new new.
+    /// Creates a new, sync-disabled manager. Its `client_id` is random and
+    /// unpersisted; prefer [`WebviewManager::load`] for cross-device tab sync.
     pub fn new(app_handle: AppHandle) -> Self {
-        Self {
+        let (event_tx, _) = broadcast::channel(AUTOMATION_EVENT_CHANNEL_SIZE);
+        let manager = Self {
+            app_handle,
+            tabs: Arc::new(RwLock::new(HashMap::new())),
+            window_counter: RwLock::new(0),
+            sync_dir: None,
+            client_id: Uuid::new_v4().to_string(),
+            device_name: local_device_name(),
+            stale_after: Duration::from_secs(DEFAULT_STALE_AFTER_SECS),
+            window_pool: Arc::new(WindowPool::new(DEFAULT_WINDOW_POOL_SIZE)),
+            pending_proxy_auth: Arc::new(RwLock::new(HashMap::new())),
+            automation_enabled: false,
+            event_tx,
+        };
+        manager.spawn_pool_refill();
+        manager
+    }
+
+    /// Creates a manager with cross-device tab sync enabled against `sync_dir`,
+    /// loading (or generating, on first run) this device's stable `client_id`.
+    pub async fn load(app_handle: AppHandle, sync_dir: PathBuf) -> Result<Self> {
+        tokio::fs::create_dir_all(&sync_dir).await?;
+        let client_id = Self::load_or_generate_client_id(&sync_dir).await?;
+
+        let (event_tx, _) = broadcast::channel(AUTOMATION_EVENT_CHANNEL_SIZE);
+        let manager = Self {
             app_handle,
             tabs: Arc::new(RwLock::new(HashMap::new())),
             window_counter: RwLock::new(0),
+            sync_dir: Some(sync_dir),
+            client_id,
+            device_name: local_device_name(),
+            stale_after: Duration::from_secs(DEFAULT_STALE_AFTER_SECS),
+            window_pool: Arc::new(WindowPool::new(DEFAULT_WINDOW_POOL_SIZE)),
+            pending_proxy_auth: Arc::new(RwLock::new(HashMap::new())),
+            automation_enabled: false,
+            event_tx,
+        };
+        manager.spawn_pool_refill();
+        Ok(manager)
+    }
+
+    /// Opts into (or back out of) the `start_automation_session` WebSocket control
+    /// channel. Off by default -- see [`WebviewManager::automation_enabled`].
+    pub fn set_automation_enabled(&mut self, enabled: bool) {
+        self.automation_enabled = enabled;
+    }
+
+    /// Subscribe to [`AutomationEvent`]s as tabs navigate, retitle, or close. Used by
+    /// `crate::automation_server` to relay events to a connected automation client.
+    pub fn subscribe_events(&self) -> broadcast::Receiver<AutomationEvent> {
+        self.event_tx.subscribe()
+    }
+
+    /// Binds a local `ws://127.0.0.1:<port>/session/<uuid>` control socket and returns
+    /// its URL, or an error if [`WebviewManager::set_automation_enabled`] hasn't been
+    /// called with `true`. See `crate::automation_server` for the wire protocol.
+    pub async fn start_automation_session(&self) -> Result<String> {
+        if !self.automation_enabled {
+            return Err(anyhow!(
+                "automation server is disabled; call set_automation_enabled(true) first"
+            ));
+        }
+        crate::automation_server::start_session(self.app_handle.clone()).await
+    }
+
+    /// Reconfigures the pre-warmed window pool's target size and immediately kicks off
+    /// a background refill to the new size.
+    pub fn set_window_pool_size(&mut self, size: usize) {
+        self.window_pool = Arc::new(WindowPool::new(size));
+        self.spawn_pool_refill();
+    }
+
+    /// Hands off pool top-up to a background task so callers (e.g. `create_tab_with_proxy`)
+    /// never wait on window creation they don't need.
+    fn spawn_pool_refill(&self) {
+        let pool = self.window_pool.clone();
+        let app_handle = self.app_handle.clone();
+        tokio::spawn(async move { pool.refill(&app_handle).await });
+    }
+
+    /// Current pre-warmed window pool hit/miss counts and idle size.
+    pub async fn pool_stats(&self) -> PoolStats {
+        self.window_pool.stats().await
+    }
+
+    /// Closes every idle pre-warmed window. Call on application shutdown so hidden
+    /// pooled windows don't linger after the manager itself is torn down.
+    pub async fn drain_pool(&self) {
+        self.window_pool.drain().await;
+    }
+
+    /// Overrides the default staleness window used by [`WebviewManager::pull_tabs`]
+    /// and [`WebviewManager::list_remote_tabs`].
+    pub fn set_stale_after(&mut self, stale_after: Duration) {
+        self.stale_after = stale_after;
+    }
+
+    async fn load_or_generate_client_id(sync_dir: &Path) -> Result<String> {
+        let id_path = sync_dir.join(CLIENT_ID_FILE_NAME);
+
+        if let Ok(existing) = tokio::fs::read_to_string(&id_path).await {
+            let trimmed = existing.trim();
+            if !trimmed.is_empty() {
+                return Ok(trimmed.to_string());
+            }
         }
+
+        let id = Uuid::new_v4().to_string();
+        tokio::fs::write(&id_path, &id).await?;
+        Ok(id)
+    }
+
+    fn clients_dir(&self) -> Option<PathBuf> {
+        self.sync_dir.as_ref().map(|dir| dir.join(CLIENTS_SUBDIR))
     }
 
-    /// Create a new webview tab with native window and proxy settings
+    /// Serialize this device's current tabs into a [`ClientRecord`], bump its
+    /// `last_modified`, and write it to `<sync_dir>/clients/<client_id>.json` via a
+    /// temp file renamed into place. A no-op when sync is disabled.
+    pub async fn push_tabs(&self) -> Result<()> {
+        let Some(clients_dir) = self.clients_dir() else {
+            return Ok(());
+        };
+
+        let record = ClientRecord {
+            client_id: self.client_id.clone(),
+            device_name: self.device_name.clone(),
+            last_modified: SystemTime::now(),
+            tabs: self
+                .tabs
+                .read()
+                .await
+                .values()
+                .map(RemoteTab::from)
+                .collect(),
+        };
+
+        tokio::fs::create_dir_all(&clients_dir).await?;
+        let path = clients_dir.join(format!("{}.json", self.client_id));
+        let temp_path = clients_dir.join(format!(".{}.tmp", self.client_id));
+        tokio::fs::write(&temp_path, serde_json::to_vec_pretty(&record)?).await?;
+        tokio::fs::rename(&temp_path, path).await?;
+        Ok(())
+    }
+
+    /// Read every other device's [`ClientRecord`] from the sync store, de-duplicated
+    /// by `client_id` (the newer `last_modified` wins on a collision) and with
+    /// records older than `stale_after` dropped. Returns an empty list when sync is
+    /// disabled or the sync store hasn't been written to yet.
+    pub async fn pull_tabs(&self) -> Result<Vec<ClientRecord>> {
+        let Some(clients_dir) = self.clients_dir() else {
+            return Ok(Vec::new());
+        };
+
+        let mut dir = match tokio::fs::read_dir(&clients_dir).await {
+            Ok(dir) => dir,
+            Err(_) => return Ok(Vec::new()),
+        };
+
+        let now = SystemTime::now();
+        let mut by_client: HashMap<String, ClientRecord> = HashMap::new();
+        while let Some(entry) = dir.next_entry().await? {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+
+            let record = match tokio::fs::read(&path).await {
+                Ok(bytes) => match serde_json::from_slice::<ClientRecord>(&bytes) {
+                    Ok(record) => record,
+                    Err(_) => continue,
+                },
+                Err(_) => continue,
+            };
+
+            if record.client_id == self.client_id {
+                continue;
+            }
+            if now.duration_since(record.last_modified).unwrap_or_default() > self.stale_after {
+                continue;
+            }
+
+            match by_client.get(&record.client_id) {
+                Some(existing) if existing.last_modified >= record.last_modified => {}
+                _ => {
+                    by_client.insert(record.client_id.clone(), record);
+                }
+            }
+        }
+
+        Ok(by_client.into_values().collect())
+    }
+
+    /// Other devices' open tabs, grouped by device, for a "tabs from your other
+    /// devices" UI panel.
+    pub async fn list_remote_tabs(&self) -> Result<Vec<RemoteDeviceTabs>> {
+        Ok(self
+            .pull_tabs()
+            .await?
+            .into_iter()
+            .map(|record| RemoteDeviceTabs {
+                client_id: record.client_id,
+                device_name: record.device_name,
+                tabs: record.tabs,
+            })
+            .collect())
+    }
+
+    /// Create a new webview tab with native window and proxy settings. When `proxy_url`
+    /// is set, the window is built with its own `--proxy-server` additional-browser
+    /// argument (Windows/WebView2) so the proxy applies only to this tab, rather than
+    /// the old process-global `HTTP_PROXY`/`HTTPS_PROXY` env vars every tab used to
+    /// share.
     pub fn create_tab_with_proxy_sync(
         &self,
         initial_url: Option<String>,
         proxy_url: Option<String>,
         window_label: String,
         tab_id: String,
-    ) -> Result<(WebviewTab, WebviewWindow)> {
+    ) -> Result<(WebviewTab, WebviewWindow, Option<ProxySettings>)> {
         let url = initial_url.unwrap_or_else(|| "https://www.google.com".to_string());
 
-        // Apply proxy settings if provided (environment variables for now)
-        let title = if let Some(ref proxy) = proxy_url {
-            // For now, use environment variables (affects all windows)
-            // NOTE: Per-webview proxy configuration requires WebView2 environment setup
-            // which must be done before the WebView is created. Current implementation
-            // uses environment variables as a fallback. For true per-tab isolation,
-            // consider using the IntegratedChromium engine from browser-core crate
-            // which provides full CDP-based proxy control per browser context.
-
-            if cfg!(target_os = "windows") {
-                std::env::set_var("HTTP_PROXY", proxy);
-                std::env::set_var("HTTPS_PROXY", proxy);
+        let (title, browser_arg, proxy) = match &proxy_url {
+            Some(proxy_url) => {
+                let (proxy, arg) = resolve_proxy(proxy_url)?;
+                (
+                    format!("New Tab - Virtual IP Browser ({})", proxy_url),
+                    arg,
+                    Some(proxy),
+                )
             }
-            format!("New Tab - Virtual IP Browser ({})", proxy)
-        } else {
-            "New Tab - Virtual IP Browser".to_string()
+            None => ("New Tab - Virtual IP Browser".to_string(), None, None),
         };
 
         // Create new webview window with Tauri v2 API
-        let window = tauri::WebviewWindowBuilder::new(
+        let mut builder = tauri::WebviewWindowBuilder::new(
             &self.app_handle,
             &window_label,
             tauri::WebviewUrl::External(url.parse()?),
@@ -83,9 +563,13 @@ impl WebviewManager {
         .min_inner_size(400.0, 300.0)
         .center()
         .decorations(true)
-        .resizable(true)
-        .build()?;
+        .resizable(true);
+        if let Some(arg) = &browser_arg {
+            builder = builder.additional_browser_args(arg);
+        }
+        let window = builder.build()?;
 
+        let now = std::time::SystemTime::now();
         let tab = WebviewTab {
             tab_id: tab_id.clone(),
             window_label: window_label.clone(),
@@ -94,36 +578,181 @@ impl WebviewManager {
             is_loading: false,
             can_go_back: false,
             can_go_forward: false,
-            created_at: std::time::SystemTime::now(),
+            created_at: now,
+            url_history: vec![url],
+            icon: None,
+            last_used: now,
+            proxy_url,
+            content_settings: ContentSettings::default(),
         };
 
-        Ok((tab, window))
+        Ok((tab, window, proxy))
     }
 
-    /// Create a new webview tab with native window and proxy settings
+    /// Create a new webview tab with native window and proxy settings. A proxy-less
+    /// request tries to check out a pre-warmed window from the [`WindowPool`] first,
+    /// falling back to building a fresh window synchronously on a pool miss. A
+    /// proxied request always builds fresh: pooled windows are pre-created with no
+    /// proxy, and WebView2's additional browser arguments can only be set before a
+    /// window exists, so a pooled window could never be retargeted at a proxy anyway.
     pub async fn create_tab_with_proxy(
         &self,
         initial_url: Option<String>,
         proxy_url: Option<String>,
     ) -> Result<WebviewTab> {
         let tab_id = Uuid::new_v4().to_string();
-        let counter = {
-            let mut c = self.window_counter.write().await;
-            *c += 1;
-            *c
+        let url = initial_url.unwrap_or_else(|| "https://www.google.com".to_string());
+
+        let pooled = if proxy_url.is_none() {
+            self.window_pool.checkout().await
+        } else {
+            None
         };
-        let window_label = format!("tab_{}", counter);
 
-        // Do synchronous window creation first
-        let (tab, _window) =
-            self.create_tab_with_proxy_sync(initial_url, proxy_url, window_label, tab_id.clone())?;
+        let tab = if let Some(pooled) = pooled {
+            self.activate_pooled_window(pooled, &tab_id, &url)?
+        } else {
+            let counter = {
+                let mut c = self.window_counter.write().await;
+                *c += 1;
+                *c
+            };
+            let window_label = format!("tab_{}", counter);
+            let (tab, _window, proxy) = self.create_tab_with_proxy_sync(
+                Some(url),
+                proxy_url,
+                window_label.clone(),
+                tab_id.clone(),
+            )?;
+            self.register_proxy_auth(&window_label, proxy.as_ref())
+                .await;
+            tab
+        };
 
         // Store tab reference
         self.tabs.write().await.insert(tab_id.clone(), tab.clone());
+        // A pool miss leaves the pool unchanged and a hit just consumed one slot; either
+        // way, top it back up in the background rather than on this call's critical path.
+        self.spawn_pool_refill();
+
+        if let Err(err) = self.push_tabs().await {
+            warn!(
+                "failed to persist tab set after creating tab '{}': {}",
+                tab_id, err
+            );
+        }
 
         Ok(tab)
     }
 
+    /// Show and navigate a pool-checked-out hidden window, producing the [`WebviewTab`]
+    /// that now tracks it. Pooled windows are always proxy-less -- see
+    /// [`WebviewManager::create_tab_with_proxy`].
+    fn activate_pooled_window(
+        &self,
+        pooled: PooledWindow,
+        tab_id: &str,
+        url: &str,
+    ) -> Result<WebviewTab> {
+        pooled.window.set_title("New Tab - Virtual IP Browser")?;
+        pooled
+            .window
+            .eval(format!("window.location.href = '{}';", url))?;
+        pooled.window.show()?;
+
+        let now = std::time::SystemTime::now();
+        Ok(WebviewTab {
+            tab_id: tab_id.to_string(),
+            window_label: pooled.window_label,
+            url: url.to_string(),
+            title: "New Tab".to_string(),
+            is_loading: false,
+            can_go_back: false,
+            can_go_forward: false,
+            created_at: now,
+            url_history: vec![url.to_string()],
+            icon: None,
+            last_used: now,
+            proxy_url: None,
+            content_settings: ContentSettings::default(),
+        })
+    }
+
+    /// Record `proxy`'s credentials, if it has any, against `window_label` in
+    /// `pending_proxy_auth` (clearing that label's entry if it has none), rather than
+    /// embedding them in a `--proxy-server` argument or an environment variable.
+    async fn register_proxy_auth(&self, window_label: &str, proxy: Option<&ProxySettings>) {
+        let mut pending = self.pending_proxy_auth.write().await;
+        match proxy.filter(|proxy| proxy.requires_auth()) {
+            Some(proxy) => {
+                pending.insert(
+                    window_label.to_string(),
+                    (
+                        proxy.username.clone().unwrap_or_default(),
+                        proxy.password.clone().unwrap_or_default(),
+                    ),
+                );
+            }
+            None => {
+                pending.remove(window_label);
+            }
+        }
+    }
+
+    /// Tear down and recreate `tab_id`'s window against a new proxy (or no proxy, if
+    /// `proxy_url` is `None`), giving the tab a fresh WebView2 environment rather than
+    /// reconfiguring the proxy of a window that's already running.
+    pub async fn set_tab_proxy(&self, tab_id: &str, proxy_url: Option<String>) -> Result<()> {
+        let old = {
+            let tabs = self.tabs.read().await;
+            tabs.get(tab_id)
+                .ok_or_else(|| anyhow!("Tab not found"))?
+                .clone()
+        };
+
+        if let Some(window) = self.app_handle.get_webview_window(&old.window_label) {
+            window.close()?;
+        }
+        self.pending_proxy_auth
+            .write()
+            .await
+            .remove(&old.window_label);
+
+        let window_label = {
+            let mut c = self.window_counter.write().await;
+            *c += 1;
+            format!("tab_{}", *c)
+        };
+        let (mut tab, _window, proxy) = self.create_tab_with_proxy_sync(
+            Some(old.url.clone()),
+            proxy_url,
+            window_label.clone(),
+            tab_id.to_string(),
+        )?;
+        self.register_proxy_auth(&window_label, proxy.as_ref())
+            .await;
+
+        // Recreating the window resets loading/back-forward/history bookkeeping that
+        // `create_tab_with_proxy_sync` has no way to know about; carry it over.
+        tab.url_history = old.url_history;
+        tab.title = old.title;
+        tab.can_go_back = old.can_go_back;
+        tab.can_go_forward = old.can_go_forward;
+        tab.created_at = old.created_at;
+        tab.content_settings = old.content_settings;
+
+        self.tabs.write().await.insert(tab_id.to_string(), tab);
+
+        if let Err(err) = self.push_tabs().await {
+            warn!(
+                "failed to persist tab set after changing tab '{}'s proxy: {}",
+                tab_id, err
+            );
+        }
+
+        Ok(())
+    }
+
     /// Navigate a tab to a new URL
     pub async fn navigate(&self, tab_id: &str, url: &str) -> Result<()> {
         let tabs = self.tabs.read().await;
@@ -138,6 +767,13 @@ impl WebviewManager {
             if let Some(tab) = tabs.get_mut(tab_id) {
                 tab.url = url.to_string();
                 tab.is_loading = true;
+                tab.url_history.push(url.to_string());
+                tab.last_used = std::time::SystemTime::now();
+                let _ = self.event_tx.send(AutomationEvent::NavigationChanged {
+                    tab_id: tab_id.to_string(),
+                    url: tab.url.clone(),
+                    title: tab.title.clone(),
+                });
             }
         }
 
@@ -149,6 +785,18 @@ impl WebviewManager {
         self.tabs.read().await.values().cloned().collect()
     }
 
+    /// Whether `window_label` belongs to a tab created by
+    /// [`WebviewManager::create_tab_with_proxy`] -- i.e. a window that loads arbitrary
+    /// external content rather than this app's own UI. Used by
+    /// `crate::ipc_guard` to tell remote browsing windows apart from trusted ones.
+    pub async fn has_window_label(&self, window_label: &str) -> bool {
+        self.tabs
+            .read()
+            .await
+            .values()
+            .any(|tab| tab.window_label == window_label)
+    }
+
     /// Close a tab
     pub async fn close_tab(&self, tab_id: &str) -> Result<()> {
         let tabs = self.tabs.read().await;
@@ -160,6 +808,16 @@ impl WebviewManager {
 
         drop(tabs);
         self.tabs.write().await.remove(tab_id);
+        let _ = self.event_tx.send(AutomationEvent::TabClosed {
+            tab_id: tab_id.to_string(),
+        });
+
+        if let Err(err) = self.push_tabs().await {
+            warn!(
+                "failed to persist tab set after closing tab '{}': {}",
+                tab_id, err
+            );
+        }
 
         Ok(())
     }
@@ -188,10 +846,66 @@ impl WebviewManager {
     pub async fn update_tab_title(&self, tab_id: &str, title: String) -> Result<()> {
         let mut tabs = self.tabs.write().await;
         if let Some(tab) = tabs.get_mut(tab_id) {
-            tab.title = title;
+            tab.title = title.clone();
+            let _ = self.event_tx.send(AutomationEvent::TitleChanged {
+                tab_id: tab_id.to_string(),
+                title,
+            });
         }
         Ok(())
     }
+
+    /// Record a resource access/block an injected page-side hook reported for
+    /// `tab_id`. `origin` is only consulted for [`ResourceEventKind::CookieBlocked`],
+    /// where it's appended to [`ContentSettings::blocked_origins`].
+    pub async fn record_resource_event(
+        &self,
+        tab_id: &str,
+        kind: ResourceEventKind,
+        origin: Option<String>,
+    ) -> Result<()> {
+        let mut tabs = self.tabs.write().await;
+        let tab = tabs
+            .get_mut(tab_id)
+            .ok_or_else(|| anyhow!("Tab not found"))?;
+        let settings = &mut tab.content_settings;
+        match kind {
+            ResourceEventKind::CookieSet => settings.cookies_set += 1,
+            ResourceEventKind::CookieBlocked => {
+                settings.cookies_blocked += 1;
+                if let Some(origin) = origin {
+                    if !settings.blocked_origins.contains(&origin) {
+                        settings.blocked_origins.push(origin);
+                    }
+                }
+            }
+            ResourceEventKind::LocalStorageAccess => settings.local_storage_accesses += 1,
+            ResourceEventKind::GeolocationPrompt => settings.geolocation_prompts += 1,
+            ResourceEventKind::ThirdPartyRequest => settings.third_party_requests += 1,
+        }
+        Ok(())
+    }
+
+    /// `tab_id`'s current [`ContentSettings`] snapshot.
+    pub async fn get_tab_content_settings(&self, tab_id: &str) -> Result<ContentSettings> {
+        self.tabs
+            .read()
+            .await
+            .get(tab_id)
+            .map(|tab| tab.content_settings.clone())
+            .ok_or_else(|| anyhow!("Tab not found"))
+    }
+
+    /// Reset `tab_id`'s [`ContentSettings`] counters. Only the per-tab bookkeeping is
+    /// reset: the cookies and storage of the tab's current origin are not purged.
+    pub async fn clear_tab_site_data(&self, tab_id: &str) -> Result<()> {
+        let mut tabs = self.tabs.write().await;
+        let tab = tabs
+            .get_mut(tab_id)
+            .ok_or_else(|| anyhow!("Tab not found"))?;
+        tab.content_settings = ContentSettings::default();
+        Ok(())
+    }
 }
 
 // Tauri command handlers
@@ -199,9 +913,11 @@ impl WebviewManager {
 /// Creates a new webview tab with proxy.
 pub async fn create_webview_tab_with_proxy(
     app_handle: tauri::AppHandle,
+    window: tauri::WebviewWindow,
     url: Option<String>,
     proxy_url: Option<String>,
 ) -> Result<WebviewTab, String> {
+    crate::ipc_guard::guard_remote(&app_handle, &window, "create_webview_tab_with_proxy").await?;
     let manager = app_handle.state::<WebviewManager>();
     manager
         .create_tab_with_proxy(url, proxy_url)
@@ -213,8 +929,10 @@ pub async fn create_webview_tab_with_proxy(
 /// Creates a new webview tab.
 pub async fn create_webview_tab(
     app_handle: tauri::AppHandle,
+    window: tauri::WebviewWindow,
     url: Option<String>,
 ) -> Result<WebviewTab, String> {
+    crate::ipc_guard::guard_remote(&app_handle, &window, "create_webview_tab").await?;
     let manager = app_handle.state::<WebviewManager>();
     manager
         .create_tab_with_proxy(url, None)
@@ -226,9 +944,11 @@ pub async fn create_webview_tab(
 /// Performs navigate webview tab operation.
 pub async fn navigate_webview_tab(
     app_handle: tauri::AppHandle,
+    window: tauri::WebviewWindow,
     tab_id: String,
     url: String,
 ) -> Result<(), String> {
+    crate::ipc_guard::guard_remote(&app_handle, &window, "navigate_webview_tab").await?;
     let manager = app_handle.state::<WebviewManager>();
     manager
         .navigate(&tab_id, &url)
@@ -236,27 +956,76 @@ pub async fn navigate_webview_tab(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+/// Tears down and recreates a tab's window against a new (or cleared) proxy.
+pub async fn set_webview_tab_proxy(
+    app_handle: tauri::AppHandle,
+    window: tauri::WebviewWindow,
+    tab_id: String,
+    proxy_url: Option<String>,
+) -> Result<(), String> {
+    crate::ipc_guard::guard_remote(&app_handle, &window, "set_webview_tab_proxy").await?;
+    let manager = app_handle.state::<WebviewManager>();
+    manager
+        .set_tab_proxy(&tab_id, proxy_url)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 /// Closes webview tab.
-pub async fn close_webview_tab(app_handle: tauri::AppHandle, tab_id: String) -> Result<(), String> {
+pub async fn close_webview_tab(
+    app_handle: tauri::AppHandle,
+    window: tauri::WebviewWindow,
+    tab_id: String,
+) -> Result<(), String> {
+    crate::ipc_guard::guard_remote(&app_handle, &window, "close_webview_tab").await?;
     let manager = app_handle.state::<WebviewManager>();
     manager.close_tab(&tab_id).await.map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 /// Performs focus webview tab operation.
-pub async fn focus_webview_tab(app_handle: tauri::AppHandle, tab_id: String) -> Result<(), String> {
+pub async fn focus_webview_tab(
+    app_handle: tauri::AppHandle,
+    window: tauri::WebviewWindow,
+    tab_id: String,
+) -> Result<(), String> {
+    crate::ipc_guard::guard_remote(&app_handle, &window, "focus_webview_tab").await?;
     let manager = app_handle.state::<WebviewManager>();
     manager.focus_tab(&tab_id).await.map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 /// Gets the webview tabs.
-pub async fn get_webview_tabs(app_handle: tauri::AppHandle) -> Result<Vec<WebviewTab>, String> {
+pub async fn get_webview_tabs(
+    app_handle: tauri::AppHandle,
+    window: tauri::WebviewWindow,
+) -> Result<Vec<WebviewTab>, String> {
+    crate::ipc_guard::guard_remote(&app_handle, &window, "get_webview_tabs").await?;
     let manager = app_handle.state::<WebviewManager>();
     Ok(manager.list_tabs().await)
 }
 
+#[tauri::command]
+/// Returns the pre-warmed window pool's hit/miss counts and current idle size.
+pub async fn pool_stats(app_handle: tauri::AppHandle) -> Result<PoolStats, String> {
+    let manager = app_handle.state::<WebviewManager>();
+    Ok(manager.pool_stats().await)
+}
+
+#[tauri::command]
+/// Binds the WebDriver BiDi-style automation WebSocket server and returns its URL.
+/// Fails unless automation has been opted into via
+/// [`WebviewManager::set_automation_enabled`].
+pub async fn start_automation_session(app_handle: tauri::AppHandle) -> Result<String, String> {
+    let manager = app_handle.state::<WebviewManager>();
+    manager
+        .start_automation_session()
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 /// Performs navigation changed operation.
 pub async fn navigation_changed(
@@ -268,17 +1037,75 @@ pub async fn navigation_changed(
     can_go_forward: bool,
 ) -> Result<(), String> {
     let manager = app_handle.state::<WebviewManager>();
-    let mut tabs = manager.tabs.write().await;
-    if let Some(tab) = tabs.get_mut(&tab_id) {
-        tab.url = url;
-        tab.title = title;
-        tab.can_go_back = can_go_back;
-        tab.can_go_forward = can_go_forward;
-        tab.is_loading = false;
+    {
+        let mut tabs = manager.tabs.write().await;
+        if let Some(tab) = tabs.get_mut(&tab_id) {
+            if tab.url != url {
+                tab.url_history.push(url.clone());
+            }
+            tab.url = url;
+            tab.title = title;
+            tab.can_go_back = can_go_back;
+            tab.can_go_forward = can_go_forward;
+            tab.is_loading = false;
+            tab.last_used = std::time::SystemTime::now();
+            let _ = manager.event_tx.send(AutomationEvent::NavigationChanged {
+                tab_id: tab_id.clone(),
+                url: tab.url.clone(),
+                title: tab.title.clone(),
+            });
+        }
+    }
+
+    if let Err(err) = manager.push_tabs().await {
+        warn!(
+            "failed to persist tab set after navigating tab '{}': {}",
+            tab_id, err
+        );
     }
+
     Ok(())
 }
 
+#[tauri::command]
+/// Lists other devices' open tabs, grouped by device, from the sync store.
+pub async fn list_remote_tabs(
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<RemoteDeviceTabs>, String> {
+    let manager = app_handle.state::<WebviewManager>();
+    manager.list_remote_tabs().await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+/// Opens a local tab pointed at a remote device's tab, identified by that device's
+/// `client_id` and the tab's index within its [`RemoteDeviceTabs::tabs`].
+pub async fn open_remote_tab(
+    app_handle: tauri::AppHandle,
+    client_id: String,
+    index: usize,
+) -> Result<WebviewTab, String> {
+    let manager = app_handle.state::<WebviewManager>();
+    let records = manager.pull_tabs().await.map_err(|e| e.to_string())?;
+    let record = records
+        .into_iter()
+        .find(|record| record.client_id == client_id)
+        .ok_or_else(|| "Remote client not found".to_string())?;
+    let remote_tab = record
+        .tabs
+        .get(index)
+        .ok_or_else(|| "Remote tab not found".to_string())?;
+    let url = remote_tab
+        .url_history
+        .last()
+        .cloned()
+        .ok_or_else(|| "Remote tab has no URL".to_string())?;
+
+    manager
+        .create_tab_with_proxy(Some(url), None)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 /// Performs title changed operation.
 pub async fn title_changed(
@@ -292,3 +1119,70 @@ pub async fn title_changed(
         .await
         .map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+/// Reports a resource access/block an injected page-side hook observed in `tab_id`,
+/// for display in the tab's per-tab privacy panel.
+pub async fn report_tab_resource_event(
+    app_handle: tauri::AppHandle,
+    tab_id: String,
+    kind: ResourceEventKind,
+    origin: Option<String>,
+) -> Result<(), String> {
+    let manager = app_handle.state::<WebviewManager>();
+    manager
+        .record_resource_event(&tab_id, kind, origin)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+/// Returns a tab's current [`ContentSettings`] snapshot.
+pub async fn get_tab_content_settings(
+    app_handle: tauri::AppHandle,
+    tab_id: String,
+) -> Result<ContentSettings, String> {
+    let manager = app_handle.state::<WebviewManager>();
+    manager
+        .get_tab_content_settings(&tab_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+/// Resets a tab's [`ContentSettings`] counters. See
+/// [`WebviewManager::clear_tab_site_data`] for what this does and doesn't purge.
+pub async fn clear_tab_site_data(
+    app_handle: tauri::AppHandle,
+    tab_id: String,
+) -> Result<(), String> {
+    let manager = app_handle.state::<WebviewManager>();
+    manager
+        .clear_tab_site_data(&tab_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// A best-effort, human-readable name for this machine, used as `ClientRecord::device_name`.
+/// Falls back to a fixed placeholder rather than failing when no hostname is set.
+fn local_device_name() -> String {
+    std::env::var("COMPUTERNAME")
+        .or_else(|_| std::env::var("HOSTNAME"))
+        .unwrap_or_else(|_| "Unknown Device".to_string())
+}
+
+/// Parse a `scheme://[user:pass@]host:port` proxy URL into a [`ProxySettings`] and,
+/// on Windows, the WebView2 `--proxy-server` additional-browser argument it implies.
+/// The argument never carries credentials -- those stay on the returned
+/// [`ProxySettings`], which callers record via [`WebviewManager::register_proxy_auth`].
+fn resolve_proxy(proxy_url: &str) -> Result<(ProxySettings, Option<String>)> {
+    let proxy = ProxySettings::parse_url(proxy_url).map_err(|e| anyhow!(e))?;
+    let arg = if cfg!(target_os = "windows") {
+        proxy
+            .proxy_server_arg()
+            .map(|server| format!("--proxy-server={}", server))
+    } else {
+        None
+    };
+    Ok((proxy, arg))
+}